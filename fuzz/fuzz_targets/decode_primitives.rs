@@ -0,0 +1,85 @@
+#![no_main]
+
+use std::convert::{TryFrom, TryInto};
+
+use libfuzzer_sys::fuzz_target;
+use reddsa::{
+    adaptor::{AdaptorPoint, AdaptorSecret, PreSignature},
+    batch, frost, orchard, sapling, vrf, SigType, Signature, SigningKey, SpendAuth,
+    VerificationKey, VerificationKeyBytes,
+};
+
+fuzz_target!(|data: &[u8]| {
+    check::<sapling::SpendAuth>(data);
+    check::<sapling::Binding>(data);
+    check::<orchard::SpendAuth>(data);
+    check::<orchard::Binding>(data);
+    check_spend_auth::<sapling::SpendAuth>(data);
+    check_spend_auth::<orchard::SpendAuth>(data);
+
+    if let Some(bytes) = prefix::<{ batch::ITEM_BYTES }>(data) {
+        if let Ok(item) = batch::Item::<sapling::SpendAuth, sapling::Binding>::from_bytes(&bytes) {
+            assert_eq!(item.to_bytes(), bytes);
+        }
+        if let Ok(item) = batch::Item::<orchard::SpendAuth, orchard::Binding>::from_bytes(&bytes) {
+            assert_eq!(item.to_bytes(), bytes);
+        }
+    }
+
+    if let Some(bytes) = prefix::<{ vrf::PROOF_BYTES }>(data) {
+        let proof = vrf::Proof::<orchard::SpendAuth>::from(bytes);
+        let _ = proof.output();
+        assert_eq!(<[u8; vrf::PROOF_BYTES]>::from(proof), bytes);
+    }
+});
+
+/// Returns the first `N` bytes of `data`, if there are that many.
+fn prefix<const N: usize>(data: &[u8]) -> Option<[u8; N]> {
+    data.get(..N).map(|bytes| bytes.try_into().unwrap())
+}
+
+/// Decodes scalars, points and signatures, and checks that whatever decodes
+/// encodes back to the same bytes.
+fn check<T: SigType>(data: &[u8]) {
+    if let Some(bytes) = prefix::<32>(data) {
+        if let Ok(sk) = SigningKey::<T>::try_from(bytes) {
+            assert_eq!(<[u8; 32]>::from(sk), bytes);
+        }
+
+        let vk_bytes = VerificationKeyBytes::<T>::from(bytes);
+        assert_eq!(<[u8; 32]>::from(vk_bytes), bytes);
+        let vk = VerificationKey::<T>::try_from(vk_bytes);
+        let public = frost::Public::<T>::try_from(bytes);
+        assert_eq!(vk.is_ok(), public.is_ok());
+        if let Ok(public) = public {
+            assert_eq!(<[u8; 32]>::from(public), bytes);
+        }
+    }
+
+    if let Some(bytes) = prefix::<64>(data) {
+        if let Ok(signature) = Signature::<T>::try_from(bytes) {
+            assert!(signature.r_affine().is_ok());
+            assert_eq!(<[u8; 64]>::from(signature), bytes);
+        }
+
+        if let Ok(nonces) = frost::SigningNonces::<T>::try_from(bytes) {
+            assert_eq!(<[u8; 64]>::from(&nonces), bytes);
+        }
+    }
+}
+
+/// Decodes the adaptor signature types, which only exist for `SpendAuth`.
+fn check_spend_auth<T: SpendAuth>(data: &[u8]) {
+    if let Some(bytes) = prefix::<32>(data) {
+        if let Ok(secret) = AdaptorSecret::<T>::try_from(bytes) {
+            assert_eq!(<[u8; 32]>::from(&secret), bytes);
+        }
+        if let Ok(point) = AdaptorPoint::<T>::try_from(bytes) {
+            assert_eq!(<[u8; 32]>::from(point), bytes);
+        }
+    }
+
+    if let Some(bytes) = prefix::<64>(data) {
+        assert_eq!(<[u8; 64]>::from(PreSignature::<T>::from(bytes)), bytes);
+    }
+}
@@ -0,0 +1,33 @@
+#![no_main]
+
+use std::convert::TryFrom;
+
+use libfuzzer_sys::fuzz_target;
+use reddsa::{
+    messages::{codec, Decode, Encode, Message},
+    orchard, sapling, SigningKey, VerificationKey,
+};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = Message::from_bytes(data) {
+        assert_eq!(Message::from_bytes(&message.to_bytes()).unwrap(), message);
+    }
+    round_trip::<codec::Json>(data);
+    round_trip::<codec::Postcard>(data);
+
+    if let Ok(bytes) = <[u8; 32]>::try_from(data) {
+        let _ = SigningKey::<sapling::SpendAuth>::try_from(bytes);
+        let _ = SigningKey::<orchard::SpendAuth>::try_from(bytes);
+        let _ = VerificationKey::<sapling::SpendAuth>::try_from(bytes);
+        let _ = VerificationKey::<sapling::Binding>::try_from(bytes);
+        let _ = VerificationKey::<orchard::SpendAuth>::try_from(bytes);
+        let _ = VerificationKey::<orchard::Binding>::try_from(bytes);
+    }
+});
+
+/// Checks that a message decoded by the backend `B` survives re-encoding.
+fn round_trip<B: Encode<Message> + Decode<Message>>(data: &[u8]) {
+    if let Ok(message) = B::decode(data) {
+        assert_eq!(B::decode(&B::encode(&message)).unwrap(), message);
+    }
+}
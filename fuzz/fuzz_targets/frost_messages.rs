@@ -0,0 +1,231 @@
+#![no_main]
+
+use std::convert::TryFrom;
+
+use arbitrary::Unstructured;
+use libfuzzer_sys::{arbitrary, fuzz_target};
+use rand_chacha::{rand_core::SeedableRng, ChaChaRng};
+use reddsa::{
+    frost,
+    messages::{
+        AggregateSignature, Header, Message, ParticipantId, Payload, SignatureShare,
+        SigningCommitments, SigningPackage,
+    },
+    sapling, Signature,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = session(&mut Unstructured::new(data));
+});
+
+/// The links between the participants, on which the fuzzer can tamper with
+/// the serialized messages.
+struct Network<'a, 'b> {
+    u: &'a mut Unstructured<'b>,
+    /// Whether any message has been tampered with.
+    tampered: bool,
+}
+
+impl Network<'_, '_> {
+    /// Serializes `payload` from `sender` to `receiver`, possibly flips some
+    /// of its bits, and decodes what the receiver gets with `decode`.
+    ///
+    /// Returns `None` if the receiver rejects the message, which must only
+    /// happen if a message has been tampered with.
+    fn send<T>(
+        &mut self,
+        sender: ParticipantId,
+        receiver: ParticipantId,
+        payload: Payload,
+        decode: impl FnOnce(&Header, &Payload) -> Result<T, ()>,
+    ) -> arbitrary::Result<Option<T>> {
+        let message = Message::new(Header::new(sender, receiver).unwrap(), payload).unwrap();
+        let mut bytes = message.to_bytes();
+        if self.u.arbitrary()? {
+            let i = self.u.choose_index(bytes.len())?;
+            bytes[i] ^= self.u.int_in_range(1..=u8::MAX)?;
+            self.tampered = true;
+        }
+
+        let decoded = Message::from_bytes(&bytes)
+            .map_err(drop)
+            .and_then(|message| decode(message.header(), message.payload()));
+        Ok(self.accept(decoded))
+    }
+
+    /// Returns the result of a step of the protocol, which must only fail if a
+    /// message has been tampered with.
+    fn accept<T, E>(&self, result: Result<T, E>) -> Option<T> {
+        assert!(result.is_ok() || self.tampered);
+        result.ok()
+    }
+}
+
+/// Runs a signing session in which every message goes through its serialized
+/// form, and the fuzzer can tamper with any of them. The session must succeed
+/// if no message was tampered with, and must never produce a signature that
+/// does not verify under the group public key.
+fn session(u: &mut Unstructured<'_>) -> arbitrary::Result<()> {
+    let seed: Vec<u8> = u.arbitrary()?;
+    let num_signers = u.int_in_range(2..=5)?;
+    let threshold = u.int_in_range(2..=num_signers)?;
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(&seed, num_signers, threshold)
+            .unwrap();
+
+    let mut rng = ChaChaRng::from_seed(u.arbitrary()?);
+    let session_id = frost::SessionId::from(u.arbitrary::<[u8; 32]>()?);
+    let message: Vec<u8> = u.arbitrary()?;
+    let mut net = Network { u, tampered: false };
+
+    // The dealer sends each signer its share.
+    let mut share_packages = Vec::new();
+    for share in &shares[..threshold as usize] {
+        let signer = ParticipantId::Signer(share.index - 1);
+        let payload = Payload::SharePackage(share.into());
+        let decoded =
+            net.send(
+                ParticipantId::Dealer,
+                signer,
+                payload,
+                |header, payload| match payload {
+                    Payload::SharePackage(package) => {
+                        frost::SharePackage::<sapling::SpendAuth>::try_from((
+                            header.receiver(),
+                            package,
+                        ))
+                        .map_err(drop)
+                    }
+                    _ => Err(()),
+                },
+            )?;
+        match decoded {
+            Some(package) => share_packages.push(package),
+            None => return Ok(()),
+        }
+    }
+
+    // Each signer sends its commitments to the aggregator.
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for package in &share_packages {
+        let (nonce, commitment) = frost::preprocess(1, package.index, &mut rng);
+        let signer = ParticipantId::Signer(package.index - 1);
+        let payload = Payload::SigningCommitments(SigningCommitments::from(&commitment[0]));
+        let decoded = net.send(
+            signer,
+            ParticipantId::Aggregator,
+            payload,
+            |header, payload| match payload {
+                Payload::SigningCommitments(commitments) => {
+                    frost::SigningCommitments::<sapling::SpendAuth>::try_from((
+                        header.sender(),
+                        commitments,
+                    ))
+                    .map_err(drop)
+                }
+                _ => Err(()),
+            },
+        )?;
+        match decoded {
+            Some(commitment) => commitments.push(commitment),
+            None => return Ok(()),
+        }
+        nonces.push(nonce[0]);
+    }
+
+    let signing_package = match net.accept(frost::SigningPackage::new(
+        &pubkeys,
+        session_id,
+        commitments,
+        message.clone(),
+    )) {
+        Some(signing_package) => signing_package,
+        None => return Ok(()),
+    };
+
+    // The aggregator sends the signing package to each signer, who replies
+    // with a signature share.
+    let mut signature_shares = Vec::new();
+    for (package, nonce) in share_packages.iter().zip(nonces) {
+        let signer = ParticipantId::Signer(package.index - 1);
+        let payload = Payload::SigningPackage(SigningPackage::from(&signing_package));
+        let received =
+            net.send(
+                ParticipantId::Aggregator,
+                signer,
+                payload,
+                |_, payload| match payload {
+                    Payload::SigningPackage(signing_package) => {
+                        frost::SigningPackage::<sapling::SpendAuth>::try_from(signing_package)
+                            .map_err(drop)
+                    }
+                    _ => Err(()),
+                },
+            )?;
+        let received = match received {
+            Some(received) => received,
+            None => return Ok(()),
+        };
+        let signature_share = match net.accept(frost::sign(&received, session_id, nonce, package)) {
+            Some(signature_share) => signature_share,
+            None => return Ok(()),
+        };
+
+        let payload = Payload::SignatureShare(SignatureShare::from(signature_share));
+        let decoded = net.send(
+            signer,
+            ParticipantId::Aggregator,
+            payload,
+            |header, payload| match payload {
+                Payload::SignatureShare(share) => {
+                    frost::SignatureShare::<sapling::SpendAuth>::try_from((header.sender(), share))
+                        .map_err(drop)
+                }
+                _ => Err(()),
+            },
+        )?;
+        match decoded {
+            Some(signature_share) => signature_shares.push(signature_share),
+            None => return Ok(()),
+        }
+    }
+
+    let signature = match net.accept(frost::aggregate(
+        &signing_package,
+        &signature_shares,
+        &pubkeys,
+    )) {
+        Some(signature) => signature,
+        None => return Ok(()),
+    };
+    assert!(pubkeys.group_public.verify(&message, &signature).is_ok());
+
+    // The aggregator publishes the signature, and each signer checks it
+    // against the group public key it received from the dealer.
+    for package in &share_packages {
+        let signer = ParticipantId::Signer(package.index - 1);
+        let payload = Payload::AggregateSignature(AggregateSignature::from(signature));
+        let received =
+            net.send(
+                ParticipantId::Aggregator,
+                signer,
+                payload,
+                |_, payload| match payload {
+                    Payload::AggregateSignature(signature) => Ok(Signature::from(signature)),
+                    _ => Err(()),
+                },
+            )?;
+        let received: Signature<sapling::SpendAuth> = match received {
+            Some(received) => received,
+            None => return Ok(()),
+        };
+        if package.group_public.verify(&message, &received).is_ok() {
+            assert!(pubkeys.group_public.verify(&message, &received).is_ok());
+        } else {
+            assert!(net.tampered);
+        }
+    }
+
+    Ok(())
+}
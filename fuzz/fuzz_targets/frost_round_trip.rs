@@ -0,0 +1,62 @@
+#![no_main]
+
+use arbitrary::Unstructured;
+use libfuzzer_sys::{arbitrary, fuzz_target};
+use rand_chacha::{rand_core::SeedableRng, ChaChaRng};
+use reddsa::{frost, sapling};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = round_trip(&mut Unstructured::new(data));
+});
+
+/// Runs a full signing session with a possibly mutated signature share, and
+/// checks that aggregation succeeds exactly when no share was mutated.
+fn round_trip(u: &mut Unstructured<'_>) -> arbitrary::Result<()> {
+    let seed: Vec<u8> = u.arbitrary()?;
+    let num_signers = u.int_in_range(1..=8)?;
+    let threshold = u.int_in_range(1..=num_signers)?;
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(&seed, num_signers, threshold)
+            .unwrap();
+
+    let mut rng = ChaChaRng::from_seed(u.arbitrary()?);
+    let session_id = frost::SessionId::from(u.arbitrary::<[u8; 32]>()?);
+    let message: Vec<u8> = u.arbitrary()?;
+
+    let signers = &shares[..threshold as usize];
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for share in signers {
+        let (nonce, commitment) = frost::preprocess(1, share.index, &mut rng);
+        nonces.push(nonce[0]);
+        commitments.push(commitment[0]);
+    }
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.clone()).unwrap();
+
+    let mut signature_shares: Vec<_> = signers
+        .iter()
+        .zip(nonces)
+        .map(|(share, nonce)| frost::sign(&signing_package, session_id, nonce, share).unwrap())
+        .collect();
+
+    let mutated = u.arbitrary()?;
+    if mutated {
+        let i = u.choose_index(signature_shares.len())?;
+        signature_shares[i] = u.arbitrary()?;
+    }
+
+    match frost::aggregate(&signing_package, &signature_shares, &pubkeys) {
+        Ok(signature) => {
+            assert!(!mutated);
+            assert!(pubkeys.group_public.verify(&message, &signature).is_ok());
+        }
+        Err(_) => assert!(mutated),
+    }
+
+    // Aggregating over an arbitrary signing package must fail gracefully.
+    let arbitrary_package: frost::SigningPackage<sapling::SpendAuth> = u.arbitrary()?;
+    let _ = frost::aggregate(&arbitrary_package, &signature_shares, &pubkeys);
+
+    Ok(())
+}
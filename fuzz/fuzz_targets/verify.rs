@@ -0,0 +1,17 @@
+#![no_main]
+
+use std::convert::TryFrom;
+
+use libfuzzer_sys::fuzz_target;
+use reddsa::{sapling, Signature, VerificationKey, VerificationKeyBytes};
+
+fuzz_target!(|input: (
+    VerificationKeyBytes<sapling::SpendAuth>,
+    Signature<sapling::SpendAuth>,
+    Vec<u8>
+)| {
+    let (vk_bytes, signature, message) = input;
+    if let Ok(vk) = VerificationKey::try_from(vk_bytes) {
+        let _ = vk.verify(&message, &signature);
+    }
+});
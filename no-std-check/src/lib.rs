@@ -0,0 +1,56 @@
+//! Checks that FROST signing builds and runs without `std`.
+//!
+//! CI builds this crate for an embedded target (`thumbv7em-none-eabihf`), which
+//! has no `std`, and runs its tests on the host.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use rand_core::{CryptoRng, RngCore};
+use reddsa::{frost, orchard};
+
+/// Runs a complete 2-of-3 FROST signing session over RedPallas with a trusted
+/// dealer, and checks the aggregated signature against the group public key.
+pub fn signing_session<R: RngCore + CryptoRng>(
+    mut rng: R,
+    message: &[u8],
+) -> Result<(), frost::Error> {
+    let (shares, pubkeys) = frost::keygen_with_dealer::<_, orchard::SpendAuth>(3, 2, &mut rng)?;
+
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for share in shares.iter().take(2) {
+        let (share_nonces, share_commitments) = frost::preprocess(1, share.index, &mut rng);
+        nonces.push(share_nonces[0]);
+        commitments.push(share_commitments[0]);
+    }
+
+    let session_id = frost::SessionId::new(&mut rng);
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.to_vec())?;
+    let signature_shares = shares
+        .iter()
+        .zip(nonces)
+        .map(|(share, nonce)| frost::sign(&signing_package, session_id, nonce, share))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let signature = frost::aggregate(&signing_package, &signature_shares, &pubkeys)?;
+    pubkeys
+        .group_public
+        .verify(message, &signature)
+        .map_err(|_| frost::Error::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::{rand_core::SeedableRng, ChaChaRng};
+
+    #[test]
+    fn signing_session() {
+        let rng = ChaChaRng::seed_from_u64(0);
+        assert_eq!(super::signing_session(rng, b"no_std"), Ok(()));
+    }
+}
@@ -0,0 +1,304 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Adaptor signatures for `SpendAuth` keys.
+//!
+//! A [`PreSignature`] is made by a [`SigningKey`] for a message and an
+//! [`AdaptorPoint`] \\(T = tB\\). Anyone can check a pre-signature against the
+//! verification key and the adaptor point, but it only becomes a valid
+//! [`Signature`] once it is [adapted](PreSignature::adapt) with the
+//! [`AdaptorSecret`] \\(t\\). Conversely, whoever holds the pre-signature can
+//! [extract](PreSignature::extract) \\(t\\) from the completed signature once it
+//! is published.
+//!
+//! This is the building block of atomic swaps and other scriptless-script
+//! constructions on top of spend authorization: publishing the signature that
+//! spends one output reveals the secret needed to spend another.
+//!
+//! With \\(R' = rB\\) the signer's nonce commitment, a pre-signature is the pair
+//! \\((R, s')\\) where
+//!
+//! - \\(R = R' + T\\);
+//! - \\(c = H^\\star(\underline{R} || \underline{vk} || M)\\);
+//! - \\(s' = r + c \cdot sk\\),
+//!
+//! and the adapted signature is \\((R, s' + t)\\).
+
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    marker::PhantomData,
+};
+
+use group::{cofactor::CofactorGroup, ff::PrimeField, Group, GroupEncoding};
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use crate::{
+    private::SealedScalar,
+    signing_key::{Fingerprint, SecretScalar},
+    Error, HStar, Signature, SigningKey, SpendAuth, VerificationKey,
+};
+
+/// The secret discrete logarithm \\(t\\) of an [`AdaptorPoint`].
+///
+/// With the `zeroize` feature, the secret is wiped from memory when it is
+/// dropped. The `Debug` output only shows a fingerprint of its
+/// [`AdaptorPoint`].
+#[derive(Clone)]
+pub struct AdaptorSecret<T: SpendAuth>(SecretScalar<T::Scalar>);
+
+#[cfg(feature = "zeroize")]
+impl<T: SpendAuth> Drop for AdaptorSecret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: SpendAuth> AdaptorSecret<T> {
+    /// Generate a new adaptor secret.
+    pub fn new<R: RngCore + CryptoRng>(mut rng: R) -> Self {
+        let mut bytes = [0; 64];
+        rng.fill_bytes(&mut bytes);
+        let t = T::Scalar::from_bytes_wide(&bytes);
+        #[cfg(feature = "zeroize")]
+        bytes.zeroize();
+        AdaptorSecret(SecretScalar(t))
+    }
+}
+
+impl<T: SpendAuth> fmt::Debug for AdaptorSecret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let point_bytes: [u8; 32] = AdaptorPoint::from(self).into();
+        f.debug_struct("AdaptorSecret")
+            .field("fingerprint", &Fingerprint::new(&[&point_bytes]))
+            .finish()
+    }
+}
+
+impl<T: SpendAuth> From<&AdaptorSecret<T>> for [u8; 32] {
+    fn from(secret: &AdaptorSecret<T>) -> [u8; 32] {
+        secret.0 .0.to_repr().as_ref().try_into().unwrap()
+    }
+}
+
+impl<T: SpendAuth> TryFrom<[u8; 32]> for AdaptorSecret<T> {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Self::Error> {
+        let mut repr = <T::Scalar as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(&bytes);
+        Option::from(T::Scalar::from_repr(repr))
+            .map(|t| AdaptorSecret(SecretScalar(t)))
+            .ok_or(Error::MalformedAdaptorSecret)
+    }
+}
+
+/// An adaptor point \\(T = tB\\), to which a [`PreSignature`] is bound.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AdaptorPoint<T: SpendAuth> {
+    point: T::Point,
+}
+
+impl<T: SpendAuth> From<&AdaptorSecret<T>> for AdaptorPoint<T> {
+    fn from(secret: &AdaptorSecret<T>) -> Self {
+        AdaptorPoint {
+            point: T::basepoint_mul(&secret.0 .0),
+        }
+    }
+}
+
+impl<T: SpendAuth> From<AdaptorPoint<T>> for [u8; 32] {
+    fn from(adaptor: AdaptorPoint<T>) -> [u8; 32] {
+        adaptor.point.to_bytes().as_ref().try_into().unwrap()
+    }
+}
+
+/// Decodes an adaptor point received from a counterparty.
+///
+/// Returns [`Error::MalformedAdaptorPoint`] if the bytes are not a canonical
+/// point encoding, and [`Error::InvalidAdaptorPoint`] if the point is the
+/// identity or is not in the prime-order subgroup. With the identity, a
+/// pre-signature would already be a valid signature; and on Jubjub, a
+/// small-order component \(T_4\) in \(T = tB + T_4\) still lets the adapted
+/// signature pass the cofactored verification, while
+/// [`PreSignature::extract`] could never recover \(t\).
+impl<T: SpendAuth> TryFrom<[u8; 32]> for AdaptorPoint<T> {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Self::Error> {
+        let mut repr = <T::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&bytes);
+        let point: T::Point =
+            Option::from(T::Point::from_bytes(&repr)).ok_or(Error::MalformedAdaptorPoint)?;
+        if bool::from(point.is_identity()) || !bool::from(point.is_torsion_free()) {
+            return Err(Error::InvalidAdaptorPoint);
+        }
+        Ok(AdaptorPoint { point })
+    }
+}
+
+/// A pre-signature, which becomes a valid [`Signature`] when it is adapted
+/// with the [`AdaptorSecret`] of its [`AdaptorPoint`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PreSignature<T: SpendAuth> {
+    r_bytes: [u8; 32],
+    s_bytes: [u8; 32],
+    _marker: PhantomData<T>,
+}
+
+impl<T: SpendAuth> From<[u8; 64]> for PreSignature<T> {
+    fn from(bytes: [u8; 64]) -> PreSignature<T> {
+        let mut r_bytes = [0; 32];
+        r_bytes.copy_from_slice(&bytes[0..32]);
+        let mut s_bytes = [0; 32];
+        s_bytes.copy_from_slice(&bytes[32..64]);
+        PreSignature {
+            r_bytes,
+            s_bytes,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: SpendAuth> From<PreSignature<T>> for [u8; 64] {
+    fn from(pre_sig: PreSignature<T>) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[0..32].copy_from_slice(&pre_sig.r_bytes[..]);
+        bytes[32..64].copy_from_slice(&pre_sig.s_bytes[..]);
+        bytes
+    }
+}
+
+fn decode_scalar<T: SpendAuth>(bytes: &[u8; 32]) -> Result<T::Scalar, Error> {
+    let mut repr = <T::Scalar as PrimeField>::Repr::default();
+    repr.as_mut().copy_from_slice(bytes);
+    Option::from(T::Scalar::from_repr(repr)).ok_or(Error::InvalidSignature)
+}
+
+impl<T: SpendAuth> PreSignature<T> {
+    /// Complete this pre-signature into a signature with the adaptor secret.
+    ///
+    /// The result is only a valid signature if `secret` is the discrete
+    /// logarithm of the adaptor point the pre-signature was made for, and the
+    /// pre-signature is valid.
+    pub fn adapt(&self, secret: &AdaptorSecret<T>) -> Result<Signature<T>, Error> {
+        let s = decode_scalar::<T>(&self.s_bytes)? + secret.0 .0;
+        Ok(Signature {
+            r_bytes: self.r_bytes,
+            s_bytes: s.to_repr().as_ref().try_into().unwrap(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Extract the adaptor secret from `signature`, the published completion of
+    /// this pre-signature for `adaptor`.
+    ///
+    /// Returns an error if `signature` is not a completion of this
+    /// pre-signature.
+    pub fn extract(
+        &self,
+        adaptor: &AdaptorPoint<T>,
+        signature: &Signature<T>,
+    ) -> Result<AdaptorSecret<T>, Error> {
+        if signature.r_bytes != self.r_bytes {
+            return Err(Error::InvalidSignature);
+        }
+        let t = decode_scalar::<T>(&signature.s_bytes)? - decode_scalar::<T>(&self.s_bytes)?;
+        let secret = AdaptorSecret(SecretScalar(t));
+        if AdaptorPoint::from(&secret) != *adaptor {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(secret)
+    }
+}
+
+impl<T: SpendAuth> SigningKey<T> {
+    /// Create a pre-signature on `msg` bound to `adaptor`.
+    pub fn pre_sign<R: RngCore + CryptoRng>(
+        &self,
+        mut rng: R,
+        adaptor: &AdaptorPoint<T>,
+        msg: &[u8],
+    ) -> PreSignature<T> {
+        // Choose the nonce as in `SigningKey::sign`, additionally binding it to
+        // the adaptor point.
+        #[allow(unused_mut)]
+        let mut random_bytes = {
+            let mut bytes = [0; 80];
+            rng.fill_bytes(&mut bytes);
+            bytes
+        };
+        let adaptor_bytes = <[u8; 32]>::from(*adaptor);
+
+        #[allow(unused_mut)]
+        let mut nonce = SecretScalar(
+            HStar::<T>::default()
+                .update(&random_bytes[..])
+                .update(&self.pk.bytes.bytes[..])
+                .update(&adaptor_bytes[..])
+                .update(msg)
+                .finalize(),
+        );
+        #[cfg(feature = "zeroize")]
+        random_bytes.zeroize();
+
+        let r = T::basepoint_mul(&nonce.0) + adaptor.point;
+        let r_bytes: [u8; 32] = r.to_bytes().as_ref().try_into().unwrap();
+
+        let c = HStar::<T>::default()
+            .update(&r_bytes[..])
+            .update(&self.pk.bytes.bytes[..])
+            .update(msg)
+            .finalize();
+
+        let s = nonce.0 + (c * self.sk.0);
+        #[cfg(feature = "zeroize")]
+        nonce.zeroize();
+
+        PreSignature {
+            r_bytes,
+            s_bytes: s.to_repr().as_ref().try_into().unwrap(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: SpendAuth> VerificationKey<T> {
+    /// Verify that `pre_sig` is a pre-signature on `msg` by this verification
+    /// key, bound to `adaptor`.
+    ///
+    /// If it is, adapting it with the adaptor secret yields a valid signature.
+    #[allow(non_snake_case)]
+    pub fn verify_pre_signature(
+        &self,
+        adaptor: &AdaptorPoint<T>,
+        msg: &[u8],
+        pre_sig: &PreSignature<T>,
+    ) -> Result<(), Error> {
+        let mut repr = <T::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&pre_sig.r_bytes);
+        let R: T::Point =
+            Option::from(T::Point::from_bytes(&repr)).ok_or(Error::InvalidSignature)?;
+        let s = decode_scalar::<T>(&pre_sig.s_bytes)?;
+
+        let c = HStar::<T>::default()
+            .update(&pre_sig.r_bytes[..])
+            .update(&self.bytes.bytes[..])
+            .update(msg)
+            .finalize();
+
+        // h * ( s' * B - c * A - R + T ) == 0
+        let check = T::basepoint() * s - self.point * c - R + adaptor.point;
+
+        if check.is_small_order().into() {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Precomputed tables for fixed-base scalar multiplication by the RedDSA
+//! basepoints.
+
+use alloc::vec::Vec;
+use core::ops::Add;
+
+use group::{ff::PrimeField, Curve, Group};
+use jubjub::{AffineNielsPoint, ExtendedNielsPoint};
+use lazy_static::lazy_static;
+use pasta_curves::{arithmetic::CurveExt, pallas};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+use crate::{
+    orchard::{self, complete::CompletePoint},
+    private::Sealed,
+    sapling,
+    scalar_mul::LookupTable8,
+};
+
+/// Holds the multiples \\(j \cdot 16^i B\\) for \\(0 \le i < 64\\) and
+/// \\(0 \le j < 16\\) of a basepoint \\(B\\).
+///
+/// A scalar multiplication with the table is 64 mixed additions of entries
+/// chosen in constant time from the radix-16 digits of the scalar, with no
+/// doublings.
+///
+/// The entry for a zero digit is the identity, so the addition must be
+/// complete and branch-free for the multiplication to be constant time. That
+/// holds for Jubjub's twisted Edwards addition, but not for the mixed addition
+/// of `pasta_curves`, which branches on the identity, so Pallas tables hold
+/// [`CompletePoint`]s and are summed with complete projective addition.
+pub(crate) struct BasepointTable<A>(Vec<[A; 16]>);
+
+impl<A: Copy + ConditionallySelectable> BasepointTable<A> {
+    /// Build the table for `basepoint`, converting the normalized multiples with
+    /// `f`.
+    fn new<P>(basepoint: P, f: impl Fn(&P::AffineRepr) -> A) -> Self
+    where
+        P: Curve,
+        P::AffineRepr: Copy,
+    {
+        let mut multiples = Vec::with_capacity(64 * 16);
+        let mut base = basepoint;
+        for _ in 0..64 {
+            let mut multiple = P::identity();
+            for _ in 0..16 {
+                multiples.push(multiple);
+                multiple += base;
+            }
+            // `multiple` is now 16 times the base of this window.
+            base = multiple;
+        }
+
+        let mut affine = vec![basepoint.to_affine(); multiples.len()];
+        P::batch_normalize(&multiples, &mut affine);
+
+        BasepointTable(
+            affine
+                .chunks(16)
+                .map(|window| {
+                    let mut entries = [f(&window[0]); 16];
+                    for (entry, point) in entries.iter_mut().zip(window) {
+                        *entry = f(point);
+                    }
+                    entries
+                })
+                .collect(),
+        )
+    }
+
+    /// Compute \\(sB\\) for the scalar `s`, in constant time.
+    pub(crate) fn mul<P, S>(&self, s: &S) -> P
+    where
+        P: Group + Add<A, Output = P>,
+        S: PrimeField,
+    {
+        self.sum(P::identity(), s)
+    }
+
+    /// Add the entries selected by the digits of `s` to `identity`.
+    fn sum<P, S>(&self, identity: P, s: &S) -> P
+    where
+        P: Add<A, Output = P>,
+        S: PrimeField,
+    {
+        let repr = s.to_repr();
+        let mut acc = identity;
+        for (i, window) in self.0.iter().enumerate() {
+            let byte = repr.as_ref()[i / 2];
+            let digit = if i % 2 == 0 { byte & 0x0f } else { byte >> 4 };
+
+            let mut entry = window[0];
+            for (j, candidate) in window.iter().enumerate().skip(1) {
+                entry.conditional_assign(candidate, (j as u8).ct_eq(&digit));
+            }
+            acc = acc + entry;
+        }
+        acc
+    }
+}
+
+impl BasepointTable<CompletePoint> {
+    /// Compute \\(sB\\) for the Pallas scalar `s`, in constant time.
+    pub(crate) fn mul_pallas(&self, s: &pallas::Scalar) -> pallas::Point {
+        self.sum(CompletePoint::identity(), s).to_point()
+    }
+}
+
+fn jubjub_table(basepoint: jubjub::ExtendedPoint) -> BasepointTable<AffineNielsPoint> {
+    BasepointTable::new(basepoint, jubjub::AffinePoint::to_niels)
+}
+
+fn pallas_table(basepoint: pallas::Point) -> BasepointTable<CompletePoint> {
+    BasepointTable::new(basepoint, CompletePoint::from_affine)
+}
+
+/// Build the tables of odd multiples of a Pallas basepoint \\(B\\) and of
+/// \\(\phi(B)\\), for variable-time double-base multiplication.
+fn pallas_naf_tables(basepoint: pallas::Point) -> [LookupTable8<pallas::Point>; 2] {
+    [
+        LookupTable8::from(&basepoint),
+        LookupTable8::from(&basepoint.endo()),
+    ]
+}
+
+lazy_static! {
+    pub(crate) static ref SAPLING_SPENDAUTH: BasepointTable<AffineNielsPoint> =
+        jubjub_table(sapling::SpendAuth::basepoint());
+    pub(crate) static ref SAPLING_BINDING: BasepointTable<AffineNielsPoint> =
+        jubjub_table(sapling::Binding::basepoint());
+    pub(crate) static ref SAPLING_SPENDAUTH_NAF: LookupTable8<ExtendedNielsPoint> =
+        LookupTable8::from(&sapling::SpendAuth::basepoint());
+    pub(crate) static ref SAPLING_BINDING_NAF: LookupTable8<ExtendedNielsPoint> =
+        LookupTable8::from(&sapling::Binding::basepoint());
+    pub(crate) static ref ORCHARD_SPENDAUTH: BasepointTable<CompletePoint> =
+        pallas_table(orchard::SpendAuth::basepoint());
+    pub(crate) static ref ORCHARD_BINDING: BasepointTable<CompletePoint> =
+        pallas_table(orchard::Binding::basepoint());
+    pub(crate) static ref ORCHARD_SPENDAUTH_NAF: [LookupTable8<pallas::Point>; 2] =
+        pallas_naf_tables(orchard::SpendAuth::basepoint());
+    pub(crate) static ref ORCHARD_BINDING_NAF: [LookupTable8<pallas::Point>; 2] =
+        pallas_naf_tables(orchard::Binding::basepoint());
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{ff::Field, Group};
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::SigType;
+
+    fn check<T: SigType>() {
+        let scalars = [
+            T::Scalar::zero(),
+            T::Scalar::one(),
+            -T::Scalar::one(),
+            T::Scalar::random(OsRng),
+            T::Scalar::random(OsRng),
+        ];
+        for s in scalars.iter() {
+            assert_eq!(T::basepoint_mul(s), T::basepoint() * s);
+        }
+
+        #[allow(non_snake_case)]
+        let A = T::Point::random(OsRng);
+        for b in scalars.iter() {
+            for a in scalars.iter() {
+                assert_eq!(
+                    T::vartime_double_base_mul(b, a, &A),
+                    T::basepoint() * b + A * a
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tables_match_scalar_mul() {
+        check::<sapling::SpendAuth>();
+        check::<sapling::Binding>();
+        check::<orchard::SpendAuth>();
+        check::<orchard::Binding>();
+    }
+}
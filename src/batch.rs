@@ -17,29 +17,47 @@
 //! caller code (which must assemble a batch of signatures across work-items),
 //! and loss of the ability to easily pinpoint failing signatures.
 //!
+//! [`Verifier`] requires the `alloc` feature. [`FixedVerifier`] holds a
+//! bounded number of items in fixed-size storage, for callers without an
+//! allocator. With the `batch-service` feature, [`service`] runs batch
+//! verification for concurrent async tasks.
 
-use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::convert::TryFrom;
 
+#[cfg(feature = "alloc")]
+use group::GroupEncoding;
 use group::{
     cofactor::CofactorGroup,
     ff::{Field, PrimeField},
-    GroupEncoding,
+    Group,
 };
 use rand_core::{CryptoRng, RngCore};
 
-use crate::{private::SealedScalar, scalar_mul::VartimeMultiscalarMul, *};
+#[cfg(feature = "alloc")]
+use crate::scalar_mul::VartimeMultiscalarMul;
+use crate::{private::SealedScalar, verification_key::decode_signature, *};
+
+#[cfg(feature = "batch-service")]
+pub mod service;
 
 // Shim to generate a random 128bit value in a [u64; 4], without
 // importing `rand`.
-fn gen_128_bits<R: RngCore + CryptoRng>(mut rng: R) -> [u64; 4] {
+//
+// 128-bit weights are enough: an invalid batch is only accepted if the
+// weights satisfy a linear equation, which happens with probability at most
+// 2^-128, the security level of the curves. Compared to full-width weights,
+// they halve the cost of the weighted terms of the multiscalar
+// multiplication.
+pub(crate) fn gen_128_bits<R: RngCore + CryptoRng>(mut rng: R) -> [u64; 4] {
     let mut bytes = [0u64; 4];
     bytes[0] = rng.next_u64();
     bytes[1] = rng.next_u64();
     bytes
 }
 
-#[derive(Clone, Debug)]
+#[derive(Copy, Clone, Debug)]
 enum Inner<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> {
     SpendAuth {
         vk_bytes: VerificationKeyBytes<S>,
@@ -53,22 +71,35 @@ enum Inner<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> {
     },
 }
 
+/// The length of the encoding of an [`Item`].
+pub const ITEM_BYTES: usize = 1 + 32 + 64 + 32;
+
 /// A batch verification item.
 ///
 /// This struct exists to allow batch processing to be decoupled from the
 /// lifetime of the message. This is useful when using the batch verification API
 /// in an async context.
-#[derive(Clone, Debug)]
+///
+/// Items can also be encoded with [`Item::to_bytes`], or serialized with
+/// `serde`, and sent to a different process or machine for verification.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerdeHelper<S, B>"))]
+#[cfg_attr(feature = "serde", serde(into = "SerdeHelper<S, B>"))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>")
+)]
 pub struct Item<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> {
     inner: Inner<S, B>,
 }
 
 impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Item<S, B> {
     /// Create a batch item from a `SpendAuth` signature.
-    pub fn from_spendauth<'msg, M: AsRef<[u8]>>(
+    pub fn from_spendauth<M: AsRef<[u8]>>(
         vk_bytes: VerificationKeyBytes<S>,
         sig: Signature<S>,
-        msg: &'msg M,
+        msg: &M,
     ) -> Self {
         // Compute c now to avoid dependency on the msg lifetime.
         let c = HStar::<S>::default()
@@ -82,10 +113,10 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Item<S, B>
     }
 
     /// Create a batch item from a `Binding` signature.
-    pub fn from_binding<'msg, M: AsRef<[u8]>>(
+    pub fn from_binding<M: AsRef<[u8]>>(
         vk_bytes: VerificationKeyBytes<B>,
         sig: Signature<B>,
-        msg: &'msg M,
+        msg: &M,
     ) -> Self {
         // Compute c now to avoid dependency on the msg lifetime.
         let c = HStar::<B>::default()
@@ -98,6 +129,56 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Item<S, B>
         }
     }
 
+    /// Encode this item as a type tag (`0` for a `SpendAuth` signature, `1` for a
+    /// `Binding` signature), followed by the verification key, the signature,
+    /// and the hash of the message and other data.
+    pub fn to_bytes(&self) -> [u8; ITEM_BYTES] {
+        let (tag, vk_bytes, sig, c) = match &self.inner {
+            Inner::SpendAuth { vk_bytes, sig, c } => {
+                (0, vk_bytes.bytes, <[u8; 64]>::from(*sig), c.to_repr())
+            }
+            Inner::Binding { vk_bytes, sig, c } => {
+                (1, vk_bytes.bytes, <[u8; 64]>::from(*sig), c.to_repr())
+            }
+        };
+
+        let mut bytes = [0; ITEM_BYTES];
+        bytes[0] = tag;
+        bytes[1..33].copy_from_slice(&vk_bytes);
+        bytes[33..97].copy_from_slice(&sig);
+        bytes[97..].copy_from_slice(c.as_ref());
+        bytes
+    }
+
+    /// Decode an item encoded with [`Item::to_bytes`].
+    ///
+    /// Returns [`Error::MalformedBatchItem`] if the type tag is unknown or the
+    /// hash is not a canonical scalar encoding. The verification key and
+    /// signature are only checked when the item is verified.
+    pub fn from_bytes(bytes: &[u8; ITEM_BYTES]) -> Result<Self, Error> {
+        let mut vk_bytes = [0; 32];
+        vk_bytes.copy_from_slice(&bytes[1..33]);
+        let mut sig = [0; 64];
+        sig.copy_from_slice(&bytes[33..97]);
+        let mut c = [0; 32];
+        c.copy_from_slice(&bytes[97..]);
+
+        let inner = match bytes[0] {
+            0 => Inner::SpendAuth {
+                vk_bytes: vk_bytes.into(),
+                sig: Signature::from_bytes_unchecked(sig),
+                c: scalar_from_bytes::<S>(c)?,
+            },
+            1 => Inner::Binding {
+                vk_bytes: vk_bytes.into(),
+                sig: Signature::from_bytes_unchecked(sig),
+                c: scalar_from_bytes::<B>(c)?,
+            },
+            _ => return Err(Error::MalformedBatchItem),
+        };
+        Ok(Self { inner })
+    }
+
     /// Perform non-batched verification of this `Item`.
     ///
     /// This is useful (in combination with `Item::clone`) for implementing fallback
@@ -118,29 +199,212 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Item<S, B>
     }
 }
 
+fn scalar_from_bytes<T: SigType>(bytes: [u8; 32]) -> Result<T::Scalar, Error> {
+    let mut repr = <T::Scalar as PrimeField>::Repr::default();
+    repr.as_mut().copy_from_slice(&bytes);
+    let maybe_scalar = T::Scalar::from_repr(repr);
+    if maybe_scalar.is_some().into() {
+        Ok(maybe_scalar.unwrap())
+    } else {
+        Err(Error::MalformedBatchItem)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(bound = "")]
+enum SerdeHelper<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> {
+    SpendAuth {
+        vk_bytes: VerificationKeyBytes<S>,
+        sig: Signature<S>,
+        c: [u8; 32],
+    },
+    Binding {
+        vk_bytes: VerificationKeyBytes<B>,
+        sig: Signature<B>,
+        c: [u8; 32],
+    },
+}
+
+#[cfg(feature = "serde")]
+impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> TryFrom<SerdeHelper<S, B>>
+    for Item<S, B>
+{
+    type Error = Error;
+
+    fn try_from(helper: SerdeHelper<S, B>) -> Result<Self, Self::Error> {
+        let inner = match helper {
+            SerdeHelper::SpendAuth { vk_bytes, sig, c } => Inner::SpendAuth {
+                vk_bytes,
+                sig,
+                c: scalar_from_bytes::<S>(c)?,
+            },
+            SerdeHelper::Binding { vk_bytes, sig, c } => Inner::Binding {
+                vk_bytes,
+                sig,
+                c: scalar_from_bytes::<B>(c)?,
+            },
+        };
+        Ok(Self { inner })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> From<Item<S, B>>
+    for SerdeHelper<S, B>
+{
+    fn from(item: Item<S, B>) -> Self {
+        let mut c = [0; 32];
+        match item.inner {
+            Inner::SpendAuth {
+                vk_bytes,
+                sig,
+                c: scalar,
+            } => {
+                c.copy_from_slice(scalar.to_repr().as_ref());
+                SerdeHelper::SpendAuth { vk_bytes, sig, c }
+            }
+            Inner::Binding {
+                vk_bytes,
+                sig,
+                c: scalar,
+            } => {
+                c.copy_from_slice(scalar.to_repr().as_ref());
+                SerdeHelper::Binding { vk_bytes, sig, c }
+            }
+        }
+    }
+}
+
+/// How [`Verifier::verify_each`] reports the results of a batch that fails.
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Return as soon as the combined check of the batch fails, without
+    /// checking any item on its own. Every item of a failed batch is reported
+    /// with the batch's error.
+    ///
+    /// This suits mempool admission, where a failed batch is rejected as a
+    /// whole.
+    FailFast,
+    /// Find the result of every item, by locating the invalid items as
+    /// [`Verifier::verify_with_failures`] does and verifying each of them on
+    /// its own.
+    ///
+    /// This suits block validation, where the invalid items must be reported.
+    Exhaustive,
+}
+
+#[cfg(feature = "alloc")]
+impl Default for Mode {
+    fn default() -> Self {
+        Mode::FailFast
+    }
+}
+
 /// A batch verification context.
+#[cfg(feature = "alloc")]
 pub struct Verifier<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> {
     /// Signature data queued for verification.
     signatures: Vec<Item<S, B>>,
+    /// How `verify_each` reports a failed batch.
+    mode: Mode,
+    /// The largest number of items verified in a single multiscalar
+    /// multiplication, if any.
+    max_batch_size: Option<usize>,
 }
 
+#[cfg(feature = "alloc")]
 impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Default for Verifier<S, B> {
     fn default() -> Self {
-        Verifier { signatures: vec![] }
+        Verifier {
+            signatures: vec![],
+            mode: Mode::default(),
+            max_batch_size: None,
+        }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Verifier<S, B> {
     /// Construct a new batch verifier.
     pub fn new() -> Verifier<S, B> {
         Verifier::default()
     }
 
+    /// Construct a new batch verifier that reports failed batches from
+    /// [`verify_each`](Self::verify_each) according to `mode`.
+    pub fn with_mode(mode: Mode) -> Verifier<S, B> {
+        Verifier {
+            signatures: vec![],
+            mode,
+            max_batch_size: None,
+        }
+    }
+
+    /// Returns how this verifier reports failed batches.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Verify the queued items in chunks of at most `max_batch_size` items.
+    ///
+    /// The memory used by a batch verification grows with the number of
+    /// items it verifies at once, so nodes that verify very large blocks, or
+    /// stream them during initial block download, can bound it by capping the
+    /// size of each chunk. The results of the chunks are combined, so every
+    /// verification method returns the same result as for a single batch.
+    /// Smaller chunks amortize less of the work, so verification is slower.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_batch_size` is zero.
+    pub fn set_max_batch_size(&mut self, max_batch_size: usize) {
+        assert!(max_batch_size > 0, "a batch must hold at least one item");
+        self.max_batch_size = Some(max_batch_size);
+    }
+
+    /// Returns the largest number of items this verifier checks at once, or
+    /// `None` if it checks all the queued items at once.
+    pub fn max_batch_size(&self) -> Option<usize> {
+        self.max_batch_size
+    }
+
     /// Queue an Item for verification.
     pub fn queue<I: Into<Item<S, B>>>(&mut self, item: I) {
         self.signatures.push(item.into());
     }
 
+    /// Queue a `SpendAuth` signature for verification from the encodings found
+    /// in a transaction: the randomized verification key `rk`, the sighash
+    /// that was signed, and the signature.
+    ///
+    /// Nothing is decoded when the signature is queued. The verification key
+    /// and the signature are decoded during verification, and each distinct
+    /// verification key is only decompressed once per batch.
+    pub fn queue_bytes<M: AsRef<[u8]>>(
+        &mut self,
+        rk_bytes: [u8; 32],
+        sighash: &M,
+        sig_bytes: [u8; 64],
+    ) {
+        self.signatures.push(Item::from_spendauth(
+            rk_bytes.into(),
+            Signature::from_bytes_unchecked(sig_bytes),
+            sighash,
+        ));
+    }
+
+    /// Move all items queued in `other` into this verifier.
+    ///
+    /// Verifiers are `Send` and `Sync`, so items can be queued into separate
+    /// verifiers on several threads, which are then merged for a single final
+    /// [`Verifier::verify`]. The merged verifier keeps the mode and maximum
+    /// batch size of `self`.
+    pub fn merge(&mut self, mut other: Verifier<S, B>) {
+        self.signatures.append(&mut other.signatures);
+    }
+
     /// Perform batch verification, returning `Ok(())` if all signatures were
     /// valid and `Err` otherwise.
     ///
@@ -152,7 +416,9 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Verifier<S,
     ///
     /// h_G * -[sum(z_i * s_i)]P_G + sum(\[z_i\]R_i) + sum([z_i * c_i]VK_i) = 0_G
     ///
-    /// so that we can use multiscalar multiplication speedups.
+    /// so that we can use multiscalar multiplication speedups. Signatures under
+    /// the same verification key share a single term [sum(z_i * c_i)]VK in
+    /// the second sum.
     ///
     /// where for each signature i,
     /// - VK_i is the verification key;
@@ -177,18 +443,155 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Verifier<S,
     /// notation in the [protocol specification §B.1][ps].
     ///
     /// [ps]: https://zips.z.cash/protocol/protocol.pdf#reddsabatchverify
-    #[allow(non_snake_case)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(items = self.signatures.len()),
+            err
+        )
+    )]
     pub fn verify<R: RngCore + CryptoRng>(self, mut rng: R) -> Result<(), Error> {
-        let n = self.signatures.len();
+        self.chunks()
+            .try_for_each(|items| Self::verify_items(items, &mut rng))
+    }
+
+    /// Perform batch verification, and if it fails, find out which of the
+    /// queued items are invalid.
+    ///
+    /// Returns `Ok(())` if all signatures were valid, and otherwise the indices
+    /// of the invalid items, in the order they were queued. Invalid items are
+    /// located by repeatedly splitting a failing batch in half and verifying
+    /// each half, so a few bad items in a large batch cost only a logarithmic
+    /// number of extra batch verifications.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(items = self.signatures.len()),
+            err(Debug)
+        )
+    )]
+    pub fn verify_with_failures<R: RngCore + CryptoRng>(
+        self,
+        mut rng: R,
+    ) -> Result<(), Vec<usize>> {
+        let failures = self.failures(&mut rng);
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Perform batch verification, and return the result of each queued item,
+    /// in the order they were queued.
+    ///
+    /// If the batch is valid, every item is `Ok(())` in either [`Mode`]. If
+    /// it is not, a [`Mode::FailFast`] verifier returns as soon as the
+    /// combined check fails and reports the batch's error for every item,
+    /// while a [`Mode::Exhaustive`] verifier reports the error of each invalid
+    /// item and `Ok(())` for the others.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(items = self.signatures.len(), mode = ?self.mode)
+        )
+    )]
+    pub fn verify_each<R: RngCore + CryptoRng>(self, mut rng: R) -> Vec<Result<(), Error>> {
+        match self.mode {
+            Mode::FailFast => {
+                let result = self
+                    .chunks()
+                    .try_for_each(|items| Self::verify_items(items, &mut rng));
+                vec![result; self.signatures.len()]
+            }
+            Mode::Exhaustive => {
+                let failures = self.failures(&mut rng);
+
+                let mut results = vec![Ok(()); self.signatures.len()];
+                for i in failures {
+                    // Verify the item on its own to find out why it failed.
+                    results[i] = Err(self.signatures[i]
+                        .verify_single()
+                        .err()
+                        .unwrap_or(Error::InvalidSignature));
+                }
+                results
+            }
+        }
+    }
+
+    /// Returns the queued items in chunks of at most the maximum batch size.
+    fn chunks(&self) -> core::slice::Chunks<'_, Item<S, B>> {
+        let size = self
+            .max_batch_size
+            .unwrap_or_else(|| self.signatures.len().max(1));
+        self.signatures.chunks(size)
+    }
+
+    /// Returns the indices of the invalid queued items, in the order they
+    /// were queued.
+    fn failures<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Vec<usize> {
+        let mut failures = Vec::new();
+        let mut offset = 0;
+        for items in self.chunks() {
+            Self::find_failures(items, offset, false, rng, &mut failures);
+            offset += items.len();
+        }
+        failures
+    }
+
+    /// Collects the indices of the invalid items of `items` into `failures`,
+    /// offsetting them by `offset`. If `known_bad` is set, `items` is already
+    /// known to fail batch verification.
+    fn find_failures<R: RngCore + CryptoRng>(
+        items: &[Item<S, B>],
+        offset: usize,
+        known_bad: bool,
+        rng: &mut R,
+        failures: &mut Vec<usize>,
+    ) {
+        if items.is_empty() || (!known_bad && Self::verify_items(items, &mut *rng).is_ok()) {
+            return;
+        }
+        if items.len() == 1 {
+            failures.push(offset);
+            return;
+        }
+
+        let mid = items.len() / 2;
+        let (left, right) = items.split_at(mid);
+        let failures_before = failures.len();
+        Self::find_failures(left, offset, false, rng, failures);
+        // Valid signatures always pass batch verification, so if the left half
+        // is valid the right half must contain the invalid items.
+        let left_valid = failures.len() == failures_before;
+        Self::find_failures(right, offset + mid, left_valid, rng, failures);
+    }
+
+    #[allow(non_snake_case)]
+    fn verify_items<R: RngCore + CryptoRng>(items: &[Item<S, B>], mut rng: R) -> Result<(), Error> {
+        let n = items.len();
 
         let mut VK_coeffs = Vec::with_capacity(n);
         let mut VKs = Vec::with_capacity(n);
-        let mut R_coeffs = Vec::with_capacity(self.signatures.len());
-        let mut Rs = Vec::with_capacity(self.signatures.len());
+        let mut R_coeffs = Vec::with_capacity(n);
+        let mut Rs = Vec::with_capacity(n);
         let mut P_spendauth_coeff = S::Scalar::zero();
         let mut P_binding_coeff = B::Scalar::zero();
 
-        for item in self.signatures.iter() {
+        // Verification keys are often repeated within a batch, so each distinct
+        // encoding is only decompressed once, and appears once in the
+        // multiscalar multiplication with the sum of its coefficients.
+        let mut VK_indices: BTreeMap<[u8; 32], usize> = BTreeMap::new();
+
+        for item in items.iter() {
             let (s_bytes, r_bytes, c) = match item.inner {
                 Inner::SpendAuth { sig, c, .. } => (sig.s_bytes, sig.r_bytes, c),
                 Inner::Binding { sig, c, .. } => (sig.s_bytes, sig.r_bytes, c),
@@ -219,12 +622,18 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Verifier<S,
                 }
             };
 
-            let VK = match item.inner {
-                Inner::SpendAuth { vk_bytes, .. } => {
-                    VerificationKey::<S>::try_from(vk_bytes.bytes)?.point
-                }
-                Inner::Binding { vk_bytes, .. } => {
-                    VerificationKey::<B>::try_from(vk_bytes.bytes)?.point
+            let vk_bytes = match item.inner {
+                Inner::SpendAuth { vk_bytes, .. } => vk_bytes.bytes,
+                Inner::Binding { vk_bytes, .. } => vk_bytes.bytes,
+            };
+            // SpendAuth and Binding keys are encoded in the same group.
+            let VK_index = match VK_indices.get(&vk_bytes) {
+                Some(index) => *index,
+                None => {
+                    VKs.push(VerificationKey::<S>::try_from(vk_bytes)?.point);
+                    VK_coeffs.push(S::Scalar::zero());
+                    VK_indices.insert(vk_bytes, VKs.len() - 1);
+                    VKs.len() - 1
                 }
             };
 
@@ -243,8 +652,7 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Verifier<S,
             R_coeffs.push(z);
             Rs.push(R);
 
-            VK_coeffs.push(S::Scalar::zero() + (z * c));
-            VKs.push(VK);
+            VK_coeffs[VK_index] += z * c;
         }
 
         use core::iter::once;
@@ -266,3 +674,169 @@ impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Verifier<S,
         }
     }
 }
+
+/// A batch verification context with room for at most `N` items, which does
+/// not allocate.
+///
+/// This is a variant of [`Verifier`] for embedded validators and kernels
+/// without an allocator. Items are kept in a fixed-size array, and verification
+/// checks the same equation as [`Verifier::verify`], with an interleaved
+/// double-and-add in place of the multiscalar multiplication, which needs heap
+/// storage. Verification keys that are repeated in the batch still share a
+/// single term.
+pub struct FixedVerifier<
+    S: SpendAuth,
+    B: Binding<Scalar = S::Scalar, Point = S::Point>,
+    const N: usize,
+> {
+    /// Signature data queued for verification, in the first `len` slots.
+    signatures: [Option<Item<S, B>>; N],
+    len: usize,
+}
+
+impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>, const N: usize> Default
+    for FixedVerifier<S, B, N>
+{
+    fn default() -> Self {
+        FixedVerifier {
+            signatures: [None; N],
+            len: 0,
+        }
+    }
+}
+
+impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>, const N: usize>
+    FixedVerifier<S, B, N>
+{
+    /// Construct a new batch verifier.
+    pub fn new() -> FixedVerifier<S, B, N> {
+        FixedVerifier::default()
+    }
+
+    /// Queue an Item for verification.
+    ///
+    /// Returns [`Error::BatchFull`] if `N` items are already queued.
+    pub fn queue<I: Into<Item<S, B>>>(&mut self, item: I) -> Result<(), Error> {
+        let slot = self.signatures.get_mut(self.len).ok_or(Error::BatchFull)?;
+        *slot = Some(item.into());
+        self.len += 1;
+        Ok(())
+    }
+
+    /// The number of queued items.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no items are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if `N` items are queued, so that the batch should be
+    /// verified before queueing more.
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Perform batch verification, returning `Ok(())` if all signatures were
+    /// valid and `Err` otherwise.
+    ///
+    /// See [`Verifier::verify`] for the batch verification equation.
+    #[allow(non_snake_case)]
+    pub fn verify<R: RngCore + CryptoRng>(self, mut rng: R) -> Result<(), Error> {
+        let identity = S::Point::identity();
+        let mut VK_bytes = [[0u8; 32]; N];
+        let mut VK_terms = [(S::Scalar::zero(), identity); N];
+        let mut R_terms = [(S::Scalar::zero(), identity); N];
+        let mut num_VKs = 0;
+        let mut P_spendauth_coeff = S::Scalar::zero();
+        let mut P_binding_coeff = B::Scalar::zero();
+
+        for (item, R_term) in self.signatures.iter().flatten().zip(R_terms.iter_mut()) {
+            let (vk_bytes, signature, c) = match item.inner {
+                Inner::SpendAuth { vk_bytes, sig, c } => {
+                    (vk_bytes.bytes, decode_signature(&sig), c)
+                }
+                Inner::Binding { vk_bytes, sig, c } => (vk_bytes.bytes, decode_signature(&sig), c),
+            };
+            let (R, s) = signature.ok_or(Error::InvalidSignature)?;
+
+            // SpendAuth and Binding keys are encoded in the same group.
+            let VK_index = match VK_bytes[..num_VKs]
+                .iter()
+                .position(|bytes| *bytes == vk_bytes)
+            {
+                Some(index) => index,
+                None => {
+                    VK_bytes[num_VKs] = vk_bytes;
+                    VK_terms[num_VKs].1 = VerificationKey::<S>::try_from(vk_bytes)?.point;
+                    num_VKs += 1;
+                    num_VKs - 1
+                }
+            };
+
+            let z = S::Scalar::from_raw(gen_128_bits(&mut rng));
+
+            let P_coeff = z * s;
+            match item.inner {
+                Inner::SpendAuth { .. } => {
+                    P_spendauth_coeff -= P_coeff;
+                }
+                Inner::Binding { .. } => {
+                    P_binding_coeff -= P_coeff;
+                }
+            };
+
+            *R_term = (z, R);
+            VK_terms[VK_index].0 += z * c;
+        }
+
+        let to_repr = |(c, P): (S::Scalar, S::Point)| (c.to_repr(), P);
+        let basepoint_terms = [
+            (P_spendauth_coeff, S::basepoint()),
+            (P_binding_coeff, B::basepoint()),
+        ]
+        .map(to_repr);
+        let VK_terms = VK_terms.map(to_repr);
+        let R_terms = R_terms.map(to_repr);
+
+        let check = interleaved_multiscalar_mul(
+            basepoint_terms
+                .iter()
+                .chain(VK_terms[..num_VKs].iter())
+                .chain(R_terms[..self.len].iter()),
+        );
+
+        if check.is_small_order().into() {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+/// Compute the sum of \\(cP\\) over the `(c, P)` terms, given the canonical
+/// encodings of the scalars, in variable time.
+///
+/// The double-and-add of every term runs in the same loop, so that all terms
+/// share the doublings without the heap storage of a multiscalar
+/// multiplication.
+fn interleaved_multiscalar_mul<'a, G, I>(terms: I) -> G
+where
+    G: Group + 'a,
+    I: Iterator<Item = &'a (<G::Scalar as PrimeField>::Repr, G)> + Clone,
+{
+    let bit = |c: &<G::Scalar as PrimeField>::Repr, i: usize| (c.as_ref()[i / 8] >> (i % 8)) & 1;
+
+    let mut r = G::identity();
+    for i in (0..G::Scalar::NUM_BITS as usize).rev() {
+        r = r.double();
+        for (c, point) in terms.clone() {
+            if bit(c, i) == 1 {
+                r += point;
+            }
+        }
+    }
+    r
+}
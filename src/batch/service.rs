@@ -0,0 +1,138 @@
+//! An asynchronous batch verification service.
+//!
+//! Node software that verifies signatures from many concurrent tasks, like
+//! Zebra does with `tower-batch`, gets the speedup of batch verification
+//! without assembling batches itself: each task sends its [`Item`] to a shared
+//! [`VerificationService`] and awaits the result for that item alone. The
+//! service collects items into a batch until it holds `max_items` of them, or
+//! until `max_latency` has passed since the first one arrived, and then
+//! verifies the batch on Tokio's blocking thread pool.
+//!
+//! Batches are verified in [`Mode::Exhaustive`], so if a batch fails, only the
+//! requests for its invalid items resolve to an error.
+
+use std::{time::Duration, vec::Vec};
+
+use rand_core::OsRng;
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::{self, Instant},
+};
+
+use super::{Item, Mode, Verifier};
+use crate::{Binding, Error, SpendAuth};
+
+/// An item awaiting verification, with the channel on which to send its
+/// result.
+type Request<S, B> = (Item<S, B>, oneshot::Sender<Result<(), Error>>);
+
+/// A handle to a batch verification service.
+///
+/// Handles are cheap to clone, and every clone sends its items to the same
+/// batches. The service shuts down once every handle is dropped, after
+/// verifying the items it has already received.
+pub struct VerificationService<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> {
+    requests: mpsc::Sender<Request<S, B>>,
+}
+
+impl<S: SpendAuth, B: Binding<Scalar = S::Scalar, Point = S::Point>> Clone
+    for VerificationService<S, B>
+{
+    fn clone(&self) -> Self {
+        VerificationService {
+            requests: self.requests.clone(),
+        }
+    }
+}
+
+impl<S, B> VerificationService<S, B>
+where
+    S: SpendAuth + 'static,
+    B: Binding<Scalar = S::Scalar, Point = S::Point> + 'static,
+    Item<S, B>: Send,
+{
+    /// Start a service that verifies a batch once it holds `max_items` items,
+    /// or once `max_latency` has passed since the first item of the batch was
+    /// received.
+    ///
+    /// The service runs as a task on the current Tokio runtime. At most
+    /// `max_items` requests wait for the service at a time; further calls to
+    /// [`verify`](Self::verify) wait for room.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_items` is zero, or if it is not called from within a
+    /// Tokio runtime.
+    pub fn new(max_items: usize, max_latency: Duration) -> VerificationService<S, B> {
+        assert!(max_items > 0, "a batch must hold at least one item");
+        let (requests, receiver) = mpsc::channel(max_items);
+        tokio::spawn(run(receiver, max_items, max_latency));
+        VerificationService { requests }
+    }
+
+    /// Verify `item` as part of the next batch, and return its own result.
+    ///
+    /// Returns [`Error::ServiceClosed`] if the service stopped before
+    /// verifying the item, which only happens if its runtime is shutting down.
+    pub async fn verify<I: Into<Item<S, B>>>(&self, item: I) -> Result<(), Error> {
+        let (result, receiver) = oneshot::channel();
+        self.requests
+            .send((item.into(), result))
+            .await
+            .map_err(|_| Error::ServiceClosed)?;
+        receiver.await.map_err(|_| Error::ServiceClosed)?
+    }
+}
+
+/// Collects requests into batches and verifies them, until every handle to
+/// the service is dropped.
+async fn run<S, B>(
+    mut receiver: mpsc::Receiver<Request<S, B>>,
+    max_items: usize,
+    max_latency: Duration,
+) where
+    S: SpendAuth + 'static,
+    B: Binding<Scalar = S::Scalar, Point = S::Point> + 'static,
+    Item<S, B>: Send,
+{
+    while let Some(first) = receiver.recv().await {
+        let deadline = Instant::now() + max_latency;
+        let mut batch = Vec::with_capacity(max_items);
+        batch.push(first);
+        while batch.len() < max_items {
+            match time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(request)) => batch.push(request),
+                // Either the deadline passed, or every handle was dropped and
+                // this is the last batch.
+                Ok(None) | Err(_) => break,
+            }
+        }
+        flush(batch).await;
+    }
+}
+
+/// Verifies a batch on the blocking thread pool, and sends each request its
+/// result.
+async fn flush<S, B>(batch: Vec<Request<S, B>>)
+where
+    S: SpendAuth + 'static,
+    B: Binding<Scalar = S::Scalar, Point = S::Point> + 'static,
+    Item<S, B>: Send,
+{
+    let (items, senders): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+    let verify = move || {
+        let mut verifier = Verifier::<S, B>::with_mode(Mode::Exhaustive);
+        for item in items {
+            verifier.queue(item);
+        }
+        verifier.verify_each(OsRng)
+    };
+    // If verification panics, the senders are dropped and every request of the
+    // batch resolves to `Error::ServiceClosed`.
+    if let Ok(results) = tokio::task::spawn_blocking(verify).await {
+        for (sender, result) in senders.into_iter().zip(results) {
+            // The caller may have stopped waiting for the result.
+            let _ = sender.send(result);
+        }
+    }
+}
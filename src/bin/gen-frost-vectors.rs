@@ -0,0 +1,48 @@
+//! Prints FROST test vectors for the RedPallas ciphersuite as JSON.
+//!
+//! Usage: `gen-frost-vectors [SEED [NUM_SIGNERS THRESHOLD [MESSAGE]]]`
+
+use std::{env, process};
+
+use reddsa::{frost::vectors, orchard};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let seed = args
+        .first()
+        .map(String::as_str)
+        .unwrap_or("reddsa FROST test vectors");
+    let (num_signers, threshold) = match (args.get(1), args.get(2)) {
+        (Some(num_signers), Some(threshold)) => (
+            parse_count(num_signers, "NUM_SIGNERS"),
+            parse_count(threshold, "THRESHOLD"),
+        ),
+        (None, None) => (3, 2),
+        _ => usage("NUM_SIGNERS and THRESHOLD must be given together"),
+    };
+    let message = args.get(3).map(String::as_str).unwrap_or("test message");
+
+    let vectors = vectors::generate::<orchard::SpendAuth>(
+        seed.as_bytes(),
+        num_signers,
+        threshold,
+        message.as_bytes(),
+    )
+    .unwrap_or_else(|error| usage(&error.to_string()));
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&vectors).expect("test vectors serialize to JSON")
+    );
+}
+
+fn parse_count(arg: &str, name: &str) -> u8 {
+    arg.parse()
+        .unwrap_or_else(|_| usage(&format!("{} must be a number from 1 to 255", name)))
+}
+
+fn usage(error: &str) -> ! {
+    eprintln!("error: {}", error);
+    eprintln!("usage: gen-frost-vectors [SEED [NUM_SIGNERS THRESHOLD [MESSAGE]]]");
+    process::exit(2)
+}
@@ -0,0 +1,404 @@
+//! A command-line tool for running FROST threshold signing ceremonies with
+//! RedJubjub keys and a trusted dealer.
+//!
+//! Every step reads and writes files, so that a ceremony can be carried out by
+//! moving files between participants. Protocol messages use the canonical
+//! encoding of [`reddsa::messages`]. A 2-of-3 ceremony looks like:
+//!
+//! ```text
+//! reddsa-frost keygen --signers 3 --threshold 2 --out dealer
+//! reddsa-frost commit --share dealer/share-1.msg --nonces nonces-1 --out commitments-1.msg
+//! reddsa-frost commit --share dealer/share-2.msg --nonces nonces-2 --out commitments-2.msg
+//! reddsa-frost package --public-key-package dealer/public-key-package --message msg.txt \
+//!     --commitments commitments-1.msg --commitments commitments-2.msg --out packages
+//! reddsa-frost sign --share dealer/share-1.msg --nonces nonces-1 \
+//!     --signing-package packages/signing-package-1.msg --session-id <ID> --out share-1.msg
+//! reddsa-frost sign --share dealer/share-2.msg --nonces nonces-2 \
+//!     --signing-package packages/signing-package-2.msg --session-id <ID> --out share-2.msg
+//! reddsa-frost aggregate --public-key-package dealer/public-key-package \
+//!     --signing-package packages/signing-package-1.msg \
+//!     --signature-share share-1.msg --signature-share share-2.msg --out signature.msg
+//! reddsa-frost verify --public-key-package dealer/public-key-package --message msg.txt \
+//!     --signature signature.msg
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::{TryFrom, TryInto},
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use rand_core::OsRng;
+use reddsa::{
+    frost,
+    messages::{
+        AggregateSignature, Header, Message, ParticipantId, Payload, SharePackage, SignatureShare,
+        SigningCommitments, SigningPackage,
+    },
+    sapling, Signature, VerificationKey,
+};
+
+type S = sapling::SpendAuth;
+
+const USAGE: &str = "usage: reddsa-frost <command> [options]
+
+commands:
+  keygen     --signers N --threshold T --out DIR
+  commit     --share FILE --nonces FILE --out FILE
+  package    --public-key-package FILE --message FILE --commitments FILE... --out DIR
+  sign       --share FILE --nonces FILE --signing-package FILE --session-id HEX --out FILE
+  aggregate  --public-key-package FILE --signing-package FILE --signature-share FILE...
+             --out FILE
+  verify     --public-key-package FILE --message FILE --signature FILE";
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let command = args.next().unwrap_or_default();
+    let result = Options::parse(args).and_then(|options| match command.as_str() {
+        "keygen" => keygen(&options),
+        "commit" => commit(&options),
+        "package" => package(&options),
+        "sign" => sign(&options),
+        "aggregate" => aggregate(&options),
+        "verify" => verify(&options),
+        _ => Err(USAGE.to_string()),
+    });
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+/// The dealer splits a new group key into shares.
+fn keygen(options: &Options) -> Result<(), String> {
+    let num_signers = options.number("signers")?;
+    let threshold = options.number("threshold")?;
+    let out = PathBuf::from(options.one("out")?);
+
+    let (shares, pubkeys) = frost::keygen_with_dealer::<_, S>(num_signers, threshold, OsRng)
+        .map_err(|error| error.to_string())?;
+
+    fs::create_dir_all(&out).map_err(|error| error.to_string())?;
+    for share in &shares {
+        let message = new_message(
+            ParticipantId::Dealer,
+            participant_id(share.index),
+            Payload::SharePackage(SharePackage::from(share)),
+        )?;
+        write(
+            &out.join(format!("share-{}.msg", share.index)),
+            &message.to_bytes(),
+        )?;
+    }
+    write(
+        &out.join("public-key-package"),
+        encode_pubkeys(&pubkeys, threshold).as_bytes(),
+    )?;
+
+    println!(
+        "wrote {} shares and the public key package to {}",
+        shares.len(),
+        out.display()
+    );
+    Ok(())
+}
+
+/// A signer generates nonces and publishes the commitments to them.
+fn commit(options: &Options) -> Result<(), String> {
+    let share = read_share(options.one("share")?)?;
+
+    let (nonces, commitments) = frost::preprocess::<_, S>(1, share.index, &mut OsRng);
+    let mut nonce_bytes = share.index.to_le_bytes().to_vec();
+    nonce_bytes.extend_from_slice(&<[u8; 64]>::from(&nonces[0]));
+    write(Path::new(options.one("nonces")?), &nonce_bytes)?;
+
+    let message = new_message(
+        participant_id(share.index),
+        ParticipantId::Aggregator,
+        Payload::SigningCommitments(SigningCommitments::from(&commitments[0])),
+    )?;
+    write(Path::new(options.one("out")?), &message.to_bytes())
+}
+
+/// The aggregator collects commitments into a signing package for each signer.
+fn package(options: &Options) -> Result<(), String> {
+    let pubkeys = read_pubkeys(options.one("public-key-package")?)?;
+    let message = read(options.one("message")?)?;
+    let out = PathBuf::from(options.one("out")?);
+
+    let mut commitments = Vec::new();
+    for path in options.all("commitments") {
+        let message = read_message(path)?;
+        match message.payload() {
+            Payload::SigningCommitments(payload) => commitments.push(
+                frost::SigningCommitments::<S>::try_from((message.header().sender(), payload))
+                    .map_err(|error| format!("{}: {}", path, error))?,
+            ),
+            _ => return Err(format!("{}: not a signing commitments message", path)),
+        }
+    }
+
+    let session_id = frost::SessionId::new(&mut OsRng);
+    let signing_package = frost::SigningPackage::new(&pubkeys, session_id, commitments, message)
+        .map_err(|error| error.to_string())?;
+    let payload = SigningPackage::from(&signing_package);
+
+    fs::create_dir_all(&out).map_err(|error| error.to_string())?;
+    for receiver in payload.signing_commitments.keys() {
+        let message = new_message(
+            ParticipantId::Aggregator,
+            *receiver,
+            Payload::SigningPackage(payload.clone()),
+        )?;
+        write(
+            &out.join(format!("signing-package-{}.msg", u64::from(*receiver))),
+            &message.to_bytes(),
+        )?;
+    }
+
+    println!("session ID: {}", hex(&<[u8; 32]>::from(session_id)));
+    Ok(())
+}
+
+/// A signer signs the signing package with the nonces it committed to.
+fn sign(options: &Options) -> Result<(), String> {
+    let share = read_share(options.one("share")?)?;
+    let session_id = frost::SessionId::from(
+        <[u8; 32]>::try_from(unhex(options.one("session-id")?)?)
+            .map_err(|_| "the session ID must be 32 bytes".to_string())?,
+    );
+
+    let nonces_path = options.one("nonces")?;
+    let nonce_bytes = read(nonces_path)?;
+    if nonce_bytes.len() != 8 + 64 || nonce_bytes[..8] != share.index.to_le_bytes() {
+        return Err(format!("{}: not the nonces of this signer", nonces_path));
+    }
+    let nonces =
+        frost::SigningNonces::<S>::try_from(<[u8; 64]>::try_from(&nonce_bytes[8..]).unwrap())
+            .map_err(|error| format!("{}: {}", nonces_path, error))?;
+
+    let path = options.one("signing-package")?;
+    let message = read_message(path)?;
+    let signing_package: frost::SigningPackage<S> = match message.payload() {
+        Payload::SigningPackage(payload) => payload.clone().into(),
+        _ => return Err(format!("{}: not a signing package message", path)),
+    };
+
+    let signature_share = frost::sign(&signing_package, session_id, nonces, &share)
+        .map_err(|error| error.to_string())?;
+    // Nonces must never be used twice.
+    fs::remove_file(nonces_path).map_err(|error| format!("{}: {}", nonces_path, error))?;
+
+    let message = new_message(
+        participant_id(share.index),
+        ParticipantId::Aggregator,
+        Payload::SignatureShare(SignatureShare::from(signature_share)),
+    )?;
+    write(Path::new(options.one("out")?), &message.to_bytes())
+}
+
+/// The aggregator verifies the signature shares and combines them.
+fn aggregate(options: &Options) -> Result<(), String> {
+    let pubkeys = read_pubkeys(options.one("public-key-package")?)?;
+
+    let path = options.one("signing-package")?;
+    let message = read_message(path)?;
+    let payload = match message.payload() {
+        Payload::SigningPackage(payload) => payload.clone(),
+        _ => return Err(format!("{}: not a signing package message", path)),
+    };
+    let receiver = *payload
+        .signing_commitments
+        .keys()
+        .next()
+        .ok_or_else(|| format!("{}: no signers", path))?;
+    let signing_package: frost::SigningPackage<S> = payload.into();
+
+    let mut signature_shares = Vec::new();
+    for path in options.all("signature-share") {
+        let message = read_message(path)?;
+        match message.payload() {
+            Payload::SignatureShare(payload) => signature_shares.push(
+                frost::SignatureShare::<S>::try_from((message.header().sender(), payload))
+                    .map_err(|error| format!("{}: {}", path, error))?,
+            ),
+            _ => return Err(format!("{}: not a signature share message", path)),
+        }
+    }
+
+    let signature = frost::aggregate(&signing_package, &signature_shares, &pubkeys)
+        .map_err(|error| error.to_string())?;
+
+    let message = new_message(
+        ParticipantId::Aggregator,
+        receiver,
+        Payload::AggregateSignature(AggregateSignature::from(signature)),
+    )?;
+    write(Path::new(options.one("out")?), &message.to_bytes())
+}
+
+/// Anyone checks the signature against the group public key.
+fn verify(options: &Options) -> Result<(), String> {
+    let pubkeys = read_pubkeys(options.one("public-key-package")?)?;
+    let message = read(options.one("message")?)?;
+
+    let path = options.one("signature")?;
+    let signature: Signature<S> = match read_message(path)?.payload() {
+        Payload::AggregateSignature(payload) => payload.into(),
+        _ => return Err(format!("{}: not an aggregate signature message", path)),
+    };
+
+    pubkeys
+        .group_public
+        .verify(&message, &signature)
+        .map_err(|error| error.to_string())?;
+    println!("signature is valid");
+    Ok(())
+}
+
+/// Command-line options of the form `--name value`. Options that take several
+/// values are repeated.
+struct Options(HashMap<String, Vec<String>>);
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options: HashMap<String, Vec<String>> = HashMap::new();
+        while let Some(arg) = args.next() {
+            let name = arg
+                .strip_prefix("--")
+                .ok_or_else(|| format!("unexpected argument {}\n\n{}", arg, USAGE))?;
+            let value = args
+                .next()
+                .ok_or_else(|| format!("--{} needs a value", name))?;
+            options.entry(name.to_string()).or_default().push(value);
+        }
+        Ok(Options(options))
+    }
+
+    fn one(&self, name: &str) -> Result<&str, String> {
+        match self.0.get(name).map(Vec::as_slice) {
+            Some([value]) => Ok(value.as_str()),
+            Some(_) => Err(format!("--{} must be given once", name)),
+            None => Err(format!("missing --{}\n\n{}", name, USAGE)),
+        }
+    }
+
+    fn all(&self, name: &str) -> &[String] {
+        self.0.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn number(&self, name: &str) -> Result<u8, String> {
+        self.one(name)?
+            .parse()
+            .map_err(|_| format!("--{} must be a number from 1 to 255", name))
+    }
+}
+
+/// Returns the message participant ID of the signer with `frost` index
+/// `index`; participant IDs start at 0.
+fn participant_id(index: u64) -> ParticipantId {
+    ParticipantId::Signer(index - 1)
+}
+
+fn new_message(
+    sender: ParticipantId,
+    receiver: ParticipantId,
+    payload: Payload,
+) -> Result<Message, String> {
+    let header = Header::new(sender, receiver).map_err(|error| error.to_string())?;
+    Message::new(header, payload).map_err(|error| error.to_string())
+}
+
+fn read_message(path: &str) -> Result<Message, String> {
+    Message::from_bytes(&read(path)?).map_err(|error| format!("{}: {}", path, error))
+}
+
+fn read_share(path: &str) -> Result<frost::SharePackage<S>, String> {
+    let message = read_message(path)?;
+    match message.payload() {
+        Payload::SharePackage(payload) => {
+            frost::SharePackage::try_from((message.header().receiver(), payload))
+                .map_err(|error| format!("{}: {}", path, error))
+        }
+        _ => Err(format!("{}: not a share package message", path)),
+    }
+}
+
+/// Encodes a public key package as lines of text: the threshold, the group
+/// public key, and the index and public key of every signer.
+fn encode_pubkeys(pubkeys: &frost::PublicKeyPackage<S>, threshold: u8) -> String {
+    let mut signers: Vec<_> = pubkeys.signer_pubkeys().collect();
+    signers.sort_by_key(|(index, _)| *index);
+
+    let mut text = format!(
+        "threshold {}\ngroup {}\n",
+        threshold,
+        hex(&<[u8; 32]>::from(pubkeys.group_public))
+    );
+    for (index, public) in signers {
+        text += &format!("signer {} {}\n", index, hex(&<[u8; 32]>::from(*public)));
+    }
+    text
+}
+
+fn read_pubkeys(path: &str) -> Result<frost::PublicKeyPackage<S>, String> {
+    let text = String::from_utf8(read(path)?).map_err(|error| format!("{}: {}", path, error))?;
+    let malformed = || format!("{}: malformed public key package", path);
+    let key =
+        |hex: &str| -> Result<[u8; 32], String> { unhex(hex)?.try_into().map_err(|_| malformed()) };
+
+    let mut threshold = None;
+    let mut group_public = None;
+    let mut signer_pubkeys = BTreeMap::new();
+    for line in text.lines() {
+        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["threshold", value] => threshold = Some(value.parse().map_err(|_| malformed())?),
+            ["group", value] => {
+                group_public =
+                    Some(VerificationKey::<S>::try_from(key(value)?).map_err(|_| malformed())?)
+            }
+            ["signer", index, value] => {
+                let index: u64 = index.parse().map_err(|_| malformed())?;
+                let public = frost::Public::try_from(key(value)?).map_err(|_| malformed())?;
+                signer_pubkeys.insert(index, public);
+            }
+            [] => {}
+            _ => return Err(malformed()),
+        }
+    }
+
+    frost::PublicKeyPackage::new(
+        signer_pubkeys,
+        group_public.ok_or_else(malformed)?,
+        threshold.ok_or_else(malformed)?,
+    )
+    .map_err(|error| format!("{}: {}", path, error))
+}
+
+fn read(path: &str) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|error| format!("{}: {}", path, error))
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    fs::write(path, bytes).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(hex: &str) -> Result<Vec<u8>, String> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(format!("{} is not a hex string", hex));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| format!("{} is not a hex string", hex))
+        })
+        .collect()
+}
@@ -13,12 +13,34 @@ use core::fmt;
 /// An error related to RedDSA signatures.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Error {
-    /// The encoding of a signing key was malformed.
+    /// The encoding of a signing key was not a canonical scalar encoding.
     MalformedSigningKey,
-    /// The encoding of a verification key was malformed.
+    /// The encoding of a verification key was not a canonical point encoding.
     MalformedVerificationKey,
+    /// A signing key was zero.
+    ZeroSigningKey,
+    /// A verification key was the identity point.
+    IdentityVerificationKey,
+    /// The `R` component of a signature was not a canonical point encoding.
+    MalformedSignaturePoint,
+    /// The `s` component of a signature was not a canonical scalar encoding.
+    MalformedSignatureScalar,
     /// Signature verification failed.
     InvalidSignature,
+    /// The encoding of a batch verification item was malformed.
+    MalformedBatchItem,
+    /// A fixed-capacity batch verifier has no room for another item.
+    BatchFull,
+    /// A mnemonic phrase was not a valid BIP-39 mnemonic.
+    MalformedMnemonic,
+    /// A batch verification service stopped before verifying an item.
+    ServiceClosed,
+    /// The encoding of an adaptor secret was not a canonical scalar encoding.
+    MalformedAdaptorSecret,
+    /// The encoding of an adaptor point was not a canonical point encoding.
+    MalformedAdaptorPoint,
+    /// An adaptor point was the identity, or had a component of small order.
+    InvalidAdaptorPoint,
 }
 
 #[cfg(feature = "std")]
@@ -29,7 +51,23 @@ impl fmt::Display for Error {
         match self {
             Self::MalformedSigningKey => write!(f, "Malformed signing key encoding."),
             Self::MalformedVerificationKey => write!(f, "Malformed verification key encoding."),
+            Self::ZeroSigningKey => write!(f, "Signing key is zero."),
+            Self::IdentityVerificationKey => write!(f, "Verification key is the identity."),
+            Self::MalformedSignaturePoint => write!(f, "Malformed signature point encoding."),
+            Self::MalformedSignatureScalar => write!(f, "Malformed signature scalar encoding."),
             Self::InvalidSignature => write!(f, "Invalid signature."),
+            Self::MalformedBatchItem => write!(f, "Malformed batch item encoding."),
+            Self::BatchFull => write!(f, "Batch verifier is full."),
+            Self::MalformedMnemonic => write!(f, "Malformed mnemonic phrase."),
+            Self::ServiceClosed => write!(f, "Batch verification service has stopped."),
+            Self::MalformedAdaptorSecret => write!(f, "Malformed adaptor secret encoding."),
+            Self::MalformedAdaptorPoint => write!(f, "Malformed adaptor point encoding."),
+            Self::InvalidAdaptorPoint => {
+                write!(
+                    f,
+                    "Adaptor point is the identity or has a small-order component."
+                )
+            }
         }
     }
 }
@@ -22,11 +22,19 @@
 //! as specified in the FROST paper.
 //! Internally, keygen_with_dealer generates keys using Verifiable Secret
 //! Sharing,  where shares are generated using Shamir Secret Sharing.
+//!
+//! Signing is generic over the signature type, so a group can hold either a
+//! `SpendAuth` key, to produce `spendAuthSig`s, or a `Binding` key, to produce
+//! the `bindingSig` of a transaction built by several parties.
+//!
+//! With the `rayon` feature, the binding factors, the group commitment and the
+//! verification of signature shares are computed on all cores, which shortens
+//! [`aggregate`] for signer sets in the hundreds.
 
-use alloc::vec::Vec;
-use std::{
-    collections::HashMap,
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
     convert::{TryFrom, TryInto},
+    fmt,
     marker::PhantomData,
 };
 
@@ -36,18 +44,67 @@ use group::{
     Curve, Group, GroupEncoding,
 };
 use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
 use zeroize::DefaultIsZeroes;
 
-use crate::{private::SealedScalar, sapling, HStar, Signature, SpendAuth, VerificationKey};
+use crate::{
+    private::SealedScalar, sapling, signing_key::Fingerprint, HStar, SigType, Signature,
+    VerificationKey,
+};
+
+pub mod additive;
+#[cfg(feature = "frost-audit")]
+pub mod audit;
+#[cfg(feature = "frost-backup")]
+mod backup;
+pub mod echo;
+#[cfg(feature = "frost-share-encryption")]
+mod encryption;
+mod error;
+#[cfg(feature = "std")]
+pub mod legacy;
+#[cfg(feature = "frost-mnemonic")]
+pub mod mnemonic;
+pub mod policy;
+pub mod roast;
+#[cfg(feature = "gen-vectors")]
+pub mod vectors;
+pub mod weighted;
+
+#[cfg(feature = "frost-share-encryption")]
+pub use self::encryption::EncryptedSharePackage;
+pub use self::error::Error;
 
 /// A secret scalar value representing a single signer's secret key.
+///
+/// The `Debug` output only shows a fingerprint of the corresponding
+/// [`Public`] key.
 #[derive(Clone, Copy, Default, PartialEq)]
-pub struct Secret<S: SpendAuth>(pub(crate) S::Scalar);
+pub struct Secret<S: SigType>(pub(crate) S::Scalar);
+
+impl<S: SigType> fmt::Debug for Secret<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let public = S::basepoint_mul(&self.0).to_bytes();
+        f.debug_struct("Secret")
+            .field("fingerprint", &Fingerprint::new(&[public.as_ref()]))
+            .finish()
+    }
+}
 
 // Zeroizes `Secret` to be the `Default` value on drop (when it goes out of
 // scope).  Luckily the derived `Default` includes the `Default` impl of
 // jubjub::Fr/Scalar, which is four 0u64's under the hood.
-impl<S: SpendAuth> DefaultIsZeroes for Secret<S> {}
+#[cfg(feature = "zeroize")]
+impl<S: SigType> DefaultIsZeroes for Secret<S> {}
+
+impl<S: SigType> ConstantTimeEq for Secret<S> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
 
 impl From<jubjub::Scalar> for Secret<sapling::SpendAuth> {
     fn from(source: jubjub::Scalar) -> Secret<sapling::SpendAuth> {
@@ -57,7 +114,7 @@ impl From<jubjub::Scalar> for Secret<sapling::SpendAuth> {
 
 /// A public group element that represents a single signer's public key.
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Public<S: SpendAuth>(S::Point);
+pub struct Public<S: SigType>(pub(crate) S::Point);
 
 impl From<jubjub::ExtendedPoint> for Public<sapling::SpendAuth> {
     fn from(source: jubjub::ExtendedPoint) -> Public<sapling::SpendAuth> {
@@ -65,25 +122,55 @@ impl From<jubjub::ExtendedPoint> for Public<sapling::SpendAuth> {
     }
 }
 
+impl<S: SigType> From<Public<S>> for [u8; 32] {
+    fn from(public: Public<S>) -> [u8; 32] {
+        public.0.to_bytes().as_ref().try_into().unwrap()
+    }
+}
+
+impl<S: SigType> TryFrom<[u8; 32]> for Public<S> {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Self::Error> {
+        let mut repr = <S::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&bytes);
+        let maybe_point = S::Point::from_bytes(&repr);
+        if maybe_point.is_some().into() {
+            Ok(Public(maybe_point.unwrap()))
+        } else {
+            Err(Error::MalformedPublicKey)
+        }
+    }
+}
+
 /// A share generated by performing a (t-out-of-n) secret sharing scheme where
 /// n is the total number of shares and t is the threshold required to
 /// reconstruct the secret; in this case we use Shamir's secret sharing.
 #[derive(Clone)]
-pub struct Share<S: SpendAuth> {
-    receiver_index: u64,
+pub struct Share<S: SigType> {
+    pub(crate) receiver_index: u64,
     /// Secret Key.
     pub(crate) value: Secret<S>,
     /// The commitments to be distributed among signers.
     pub(crate) commitment: ShareCommitment<S>,
 }
 
+impl<S: SigType> fmt::Debug for Share<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("receiver_index", &self.receiver_index)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
 /// A Jubjub point that is a commitment to one coefficient of our secret
 /// polynomial.
 ///
 /// This is a (public) commitment to one coefficient of a secret polynomial used
 /// for performing verifiable secret sharing for a Shamir secret share.
 #[derive(Clone, PartialEq)]
-pub(crate) struct Commitment<S: SpendAuth>(pub(crate) <S::Point as CofactorCurve>::Affine);
+pub(crate) struct Commitment<S: SigType>(pub(crate) <S::Point as CofactorCurve>::Affine);
 
 /// Contains the commitments to the coefficients for our secret polynomial _f_,
 /// used to generate participants' key shares.
@@ -98,19 +185,19 @@ pub(crate) struct Commitment<S: SpendAuth>(pub(crate) <S::Point as CofactorCurve
 /// some agreed-upon public location for publication, where each participant can
 /// ensure that they received the correct (and same) value.
 #[derive(Clone)]
-pub struct ShareCommitment<S: SpendAuth>(pub(crate) Vec<Commitment<S>>);
+pub struct ShareCommitment<S: SigType>(pub(crate) Vec<Commitment<S>>);
 
 /// The product of all signers' individual commitments, published as part of the
 /// final signature.
 #[derive(PartialEq)]
-pub struct GroupCommitment<S: SpendAuth>(pub(crate) <S::Point as CofactorCurve>::Affine);
+pub struct GroupCommitment<S: SigType>(pub(crate) <S::Point as CofactorCurve>::Affine);
 
 /// Secret and public key material generated by a dealer performing
 /// [`keygen_with_dealer`].
 ///
 /// To derive a FROST keypair, the receiver of the [`SharePackage`] *must* call
 /// .into(), which under the hood also performs validation.
-pub struct SharePackage<S: SpendAuth> {
+pub struct SharePackage<S: SigType> {
     /// The public signing key that represents the entire group.
     pub(crate) group_public: VerificationKey<S>,
     /// Denotes the participant index each share is owned by.
@@ -121,8 +208,19 @@ pub struct SharePackage<S: SpendAuth> {
     pub(crate) share: Share<S>,
 }
 
-impl<S: SpendAuth> TryFrom<SharePackage<S>> for KeyPackage<S> {
-    type Error = &'static str;
+impl<S: SigType> fmt::Debug for SharePackage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharePackage")
+            .field("group_public", &self.group_public)
+            .field("index", &self.index)
+            .field("public", &self.public)
+            .field("share", &self.share)
+            .finish()
+    }
+}
+
+impl<S: SigType> TryFrom<SharePackage<S>> for KeyPackage<S> {
+    type Error = Error;
 
     /// Tries to verify a share and construct a [`KeyPackage`] from it.
     ///
@@ -132,7 +230,7 @@ impl<S: SpendAuth> TryFrom<SharePackage<S>> for KeyPackage<S> {
     /// every participant has the same view of the commitment issued by the
     /// dealer, but implementations *MUST* make sure that all participants have
     /// a consistent view of this commitment in practice.
-    fn try_from(sharepackage: SharePackage<S>) -> Result<Self, &'static str> {
+    fn try_from(sharepackage: SharePackage<S>) -> Result<Self, Error> {
         verify_share(&sharepackage.share)?;
 
         Ok(KeyPackage {
@@ -151,25 +249,99 @@ impl<S: SpendAuth> TryFrom<SharePackage<S>> for KeyPackage<S> {
 /// participants, who then perform verification, before deriving
 /// [`KeyPackage`]s, which they store to later use during signing.
 #[allow(dead_code)]
-pub struct KeyPackage<S: SpendAuth> {
+pub struct KeyPackage<S: SigType> {
     index: u64,
     secret_share: Secret<S>,
     public: Public<S>,
     group_public: VerificationKey<S>,
 }
 
+impl<S: SigType> fmt::Debug for KeyPackage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPackage")
+            .field("index", &self.index)
+            .field("secret_share", &self.secret_share)
+            .field("public", &self.public)
+            .field("group_public", &self.group_public)
+            .finish()
+    }
+}
+
 /// Public data that contains all the signer's public keys as well as the
 /// group public key.
 ///
 /// Used for verification purposes before publishing a signature.
-pub struct PublicKeyPackage<S: SpendAuth> {
+#[derive(Clone)]
+pub struct PublicKeyPackage<S: SigType> {
     /// When performing signing, the coordinator must ensure that they have the
     /// correct view of participant's public keys to perform verification before
     /// publishing a signature. signer_pubkeys represents all signers for a
     /// signing operation.
-    pub(crate) signer_pubkeys: HashMap<u64, Public<S>>,
+    pub(crate) signer_pubkeys: BTreeMap<u64, Public<S>>,
     /// group_public represents the joint public key for the entire group.
     pub group_public: VerificationKey<S>,
+    /// The number of signers needed to produce a signature.
+    pub(crate) threshold: u8,
+}
+
+impl<S: SigType> PublicKeyPackage<S> {
+    /// Creates a public key package from the public keys of all signers, the
+    /// group public key, and the number of signers needed to produce a
+    /// signature.
+    ///
+    /// Use this to restore a public key package that was published by the
+    /// dealer. Returns an error if the threshold is 0 or exceeds the number of
+    /// signers.
+    pub fn new(
+        signer_pubkeys: BTreeMap<u64, Public<S>>,
+        group_public: VerificationKey<S>,
+        threshold: u8,
+    ) -> Result<Self, Error> {
+        if threshold < 1 {
+            return Err(Error::ZeroThreshold);
+        }
+        if threshold as usize > signer_pubkeys.len() {
+            return Err(Error::ThresholdExceedsShares);
+        }
+
+        Ok(PublicKeyPackage {
+            signer_pubkeys,
+            group_public,
+            threshold,
+        })
+    }
+
+    /// The number of signers needed to produce a signature.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// The total number of signers in the group.
+    pub fn num_signers(&self) -> usize {
+        self.signer_pubkeys.len()
+    }
+
+    /// Returns the participant indices of all signers, in increasing order.
+    pub fn signer_indices(&self) -> Vec<u64> {
+        self.signer_pubkeys.keys().copied().collect()
+    }
+
+    /// Returns the public key of the signer with participant index `index`, if
+    /// that signer is part of the group.
+    ///
+    /// Coordinators can use this to authenticate messages from individual
+    /// signers, and to check their signature shares.
+    pub fn signer_pubkey(&self, index: u64) -> Option<&Public<S>> {
+        self.signer_pubkeys.get(&index)
+    }
+
+    /// Iterates over the participant indices and public keys of all signers,
+    /// in increasing order of index.
+    pub fn signer_pubkeys(&self) -> impl Iterator<Item = (u64, &Public<S>)> {
+        self.signer_pubkeys
+            .iter()
+            .map(|(index, public)| (*index, public))
+    }
 }
 
 /// Allows all participants' keys to be generated using a central, trusted
@@ -180,22 +352,87 @@ pub struct PublicKeyPackage<S: SpendAuth> {
 /// key. The output from this function is a set of shares along with one single
 /// commitment that participants use to verify the integrity of the share. The
 /// number of signers is limited to 255.
-pub fn keygen_with_dealer<R: RngCore + CryptoRng, S: SpendAuth>(
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(num_signers = num_signers, threshold = threshold)
+    )
+)]
+pub fn keygen_with_dealer<R: RngCore + CryptoRng, S: SigType>(
     num_signers: u8,
     threshold: u8,
     mut rng: R,
-) -> Result<(Vec<SharePackage<S>>, PublicKeyPackage<S>), &'static str> {
+) -> Result<(Vec<SharePackage<S>>, PublicKeyPackage<S>), Error> {
     let mut bytes = [0; 64];
     rng.fill_bytes(&mut bytes);
 
     let secret = Secret(S::Scalar::from_bytes_wide(&bytes));
-    let group_public = VerificationKey::from(&secret.0);
     let shares = generate_shares(&secret, num_signers, threshold, rng)?;
+
+    Ok(package_shares(&secret, shares, num_signers, threshold))
+}
+
+/// Like [`keygen_with_dealer`], but draws the randomness from the operating
+/// system's RNG.
+#[cfg(feature = "std")]
+pub fn keygen_with_dealer_default<S: SigType>(
+    num_signers: u8,
+    threshold: u8,
+) -> Result<(Vec<SharePackage<S>>, PublicKeyPackage<S>), Error> {
+    keygen_with_dealer(num_signers, threshold, rand_core::OsRng)
+}
+
+/// Like [`keygen_with_dealer`], but derives the group secret and all other
+/// polynomial coefficients from `seed` rather than from an RNG.
+///
+/// The same seed, number of signers and threshold always produce the same
+/// shares, so a key generation ceremony can be re-derived and checked from a
+/// recorded seed, and test vectors are reproducible. Anyone who learns the
+/// seed learns the group's signing key, so it must be generated and kept with
+/// the same care as the key itself.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(num_signers = num_signers, threshold = threshold)
+    )
+)]
+pub fn keygen_with_dealer_from_seed<S: SigType>(
+    seed: &[u8],
+    num_signers: u8,
+    threshold: u8,
+) -> Result<(Vec<SharePackage<S>>, PublicKeyPackage<S>), Error> {
+    let coefficient = |i: u8| {
+        HStar::<S>::default()
+            .update(b"FROST_DealerSeed")
+            .update([num_signers, threshold, i])
+            .update(seed)
+            .finalize()
+    };
+
+    let secret = Secret(coefficient(0));
+    let shares = generate_shares_with(&secret, num_signers, threshold, |i| coefficient(i + 1))?;
+
+    Ok(package_shares(&secret, shares, num_signers, threshold))
+}
+
+/// Bundles the shares created by the dealer into [`SharePackage`]s for each
+/// participant and a [`PublicKeyPackage`] for the group.
+fn package_shares<S: SigType>(
+    secret: &Secret<S>,
+    shares: Vec<Share<S>>,
+    num_signers: u8,
+    threshold: u8,
+) -> (Vec<SharePackage<S>>, PublicKeyPackage<S>) {
+    let group_public = VerificationKey::from(&secret.0);
     let mut sharepackages: Vec<SharePackage<S>> = Vec::with_capacity(num_signers as usize);
-    let mut signer_pubkeys: HashMap<u64, Public<S>> = HashMap::with_capacity(num_signers as usize);
+    let mut signer_pubkeys: BTreeMap<u64, Public<S>> = BTreeMap::new();
 
     for share in shares {
-        let signer_public = Public(S::basepoint() * share.value.0);
+        let signer_public = Public(S::basepoint_mul(&share.value.0));
         sharepackages.push(SharePackage {
             index: share.receiver_index,
             share: share.clone(),
@@ -206,13 +443,14 @@ pub fn keygen_with_dealer<R: RngCore + CryptoRng, S: SpendAuth>(
         signer_pubkeys.insert(share.receiver_index, signer_public);
     }
 
-    Ok((
+    (
         sharepackages,
         PublicKeyPackage {
             signer_pubkeys,
             group_public,
+            threshold,
         },
-    ))
+    )
 }
 
 /// Verifies that a share is consistent with a commitment.
@@ -221,10 +459,10 @@ pub fn keygen_with_dealer<R: RngCore + CryptoRng, S: SpendAuth>(
 /// mechanism as all other signing participants. Note that participants *MUST*
 /// ensure that they have the same view as all other participants of the
 /// commitment!
-fn verify_share<S: SpendAuth>(share: &Share<S>) -> Result<(), &'static str> {
+pub(crate) fn verify_share<S: SigType>(share: &Share<S>) -> Result<(), Error> {
     let f_result = S::basepoint() * share.value.0;
 
-    let x = S::Scalar::from(share.receiver_index as u64);
+    let x = S::Scalar::from(share.receiver_index);
 
     let (_, result) = share.commitment.0.iter().fold(
         (S::Scalar::one(), S::Point::identity()),
@@ -232,7 +470,12 @@ fn verify_share<S: SpendAuth>(share: &Share<S>) -> Result<(), &'static str> {
     );
 
     if !(f_result == result) {
-        return Err("Share is invalid.");
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            signer = share.receiver_index,
+            "share does not match the dealer's commitment"
+        );
+        return Err(Error::InvalidShare);
     }
 
     Ok(())
@@ -250,25 +493,40 @@ fn verify_share<S: SpendAuth>(share: &Share<S>) -> Result<(), &'static str> {
 ///
 /// More specifically, [`generate_shares`]:
 /// - Randomly samples of coefficients [a, b, c], this represents a secret
-/// polynomial f
+///   polynomial f
 /// - For each participant i, their secret share is f(i)
 /// - The commitment to the secret polynomial f is [g^a, g^b, g^c]
-fn generate_shares<R: RngCore + CryptoRng, S: SpendAuth>(
+fn generate_shares<R: RngCore + CryptoRng, S: SigType>(
     secret: &Secret<S>,
     numshares: u8,
     threshold: u8,
     mut rng: R,
-) -> Result<Vec<Share<S>>, &'static str> {
+) -> Result<Vec<Share<S>>, Error> {
+    generate_shares_with(secret, numshares, threshold, |_| {
+        let mut bytes = [0; 64];
+        rng.fill_bytes(&mut bytes);
+        S::Scalar::from_bytes_wide(&bytes)
+    })
+}
+
+/// Creates secret shares for a given secret, taking the `i`th non-constant
+/// coefficient of the secret polynomial from `coefficient(i)`.
+fn generate_shares_with<S: SigType>(
+    secret: &Secret<S>,
+    numshares: u8,
+    threshold: u8,
+    mut coefficient: impl FnMut(u8) -> S::Scalar,
+) -> Result<Vec<Share<S>>, Error> {
     if threshold < 1 {
-        return Err("Threshold cannot be 0");
+        return Err(Error::ZeroThreshold);
     }
 
     if numshares < 1 {
-        return Err("Number of shares cannot be 0");
+        return Err(Error::ZeroShares);
     }
 
     if threshold > numshares {
-        return Err("Threshold cannot exceed numshares");
+        return Err(Error::ThresholdExceedsShares);
     }
 
     let numcoeffs = threshold - 1;
@@ -280,22 +538,20 @@ fn generate_shares<R: RngCore + CryptoRng, S: SpendAuth>(
     let mut commitment: ShareCommitment<S> =
         ShareCommitment(Vec::with_capacity(threshold as usize));
 
-    for _ in 0..numcoeffs {
-        let mut bytes = [0; 64];
-        rng.fill_bytes(&mut bytes);
-        coefficients.push(S::Scalar::from_bytes_wide(&bytes));
+    for i in 0..numcoeffs {
+        coefficients.push(coefficient(i));
     }
 
     // Verifiable secret sharing, to make sure that participants can ensure their secret is consistent
     // with every other participant's.
     commitment
         .0
-        .push(Commitment((S::basepoint() * secret.0).to_affine()));
+        .push(Commitment(S::basepoint_mul(&secret.0).to_affine()));
 
     for c in &coefficients {
         commitment
             .0
-            .push(Commitment((S::basepoint() * c).to_affine()));
+            .push(Commitment(S::basepoint_mul(c).to_affine()));
     }
 
     // Evaluate the polynomial with `secret` as the constant term
@@ -322,13 +578,62 @@ fn generate_shares<R: RngCore + CryptoRng, S: SpendAuth>(
     Ok(shares)
 }
 
+/// Reconstructs the group's signing key from the share packages of at least
+/// `threshold` participants.
+///
+/// **This defeats the purpose of threshold custody**: whoever runs it holds the
+/// complete signing key, in one place, from then on. It exists only so that
+/// operators can deliberately exit threshold custody or recover a key in a
+/// disaster scenario, and is therefore only available with the
+/// `frost-reconstruct-dangerous` feature.
+///
+/// Each share is verified against its commitment, and the reconstructed key is
+/// checked against the group public key, so supplying fewer than `threshold`
+/// shares or shares from different groups results in an error rather than a
+/// wrong key.
+#[cfg(feature = "frost-reconstruct-dangerous")]
+pub fn reconstruct<S: SigType>(
+    sharepackages: &[SharePackage<S>],
+) -> Result<crate::SigningKey<S>, Error> {
+    let first = sharepackages.first().ok_or(Error::NoShares)?;
+
+    let mut indices = Vec::with_capacity(sharepackages.len());
+    for sharepackage in sharepackages {
+        if sharepackage.group_public != first.group_public {
+            return Err(Error::DifferentGroups);
+        }
+        if indices.contains(&sharepackage.index) {
+            return Err(Error::DuplicateShares);
+        }
+        verify_share(&sharepackage.share)?;
+        indices.push(sharepackage.index);
+    }
+
+    let mut secret = S::Scalar::zero();
+    for sharepackage in sharepackages {
+        let lambda_i = lagrange_coeff_at_zero::<S, _>(sharepackage.index, indices.iter().copied())?;
+        secret += lambda_i * sharepackage.share.value.0;
+    }
+
+    let signing_key =
+        crate::SigningKey::<S>::try_from(<[u8; 32]>::try_from(secret.to_repr().as_ref()).unwrap())
+            .map_err(|_| Error::ReconstructionMismatch)?;
+
+    let group_public: VerificationKey<S> = (&signing_key).into();
+    if group_public != first.group_public {
+        return Err(Error::ReconstructionMismatch);
+    }
+
+    Ok(signing_key)
+}
+
 /// Comprised of hiding and binding nonces.
 ///
 /// Note that [`SigningNonces`] must be used *only once* for a signing
 /// operation; re-using nonces will result in leakage of a signer's long-lived
 /// signing key.
 #[derive(Clone, Copy, Default)]
-pub struct SigningNonces<S: SpendAuth> {
+pub struct SigningNonces<S: SigType> {
     hiding: S::Scalar,
     binding: S::Scalar,
 }
@@ -336,9 +641,62 @@ pub struct SigningNonces<S: SpendAuth> {
 // Zeroizes `SigningNonces` to be the `Default` value on drop (when it goes out
 // of scope).  Luckily the derived `Default` includes the `Default` impl of the
 // `jubjub::Fr/Scalar`'s, which is four 0u64's under the hood.
-impl<S: SpendAuth> DefaultIsZeroes for SigningNonces<S> {}
+#[cfg(feature = "zeroize")]
+impl<S: SigType> DefaultIsZeroes for SigningNonces<S> {}
+
+/// Only shows a fingerprint of the commitments to the nonces.
+impl<S: SigType> fmt::Debug for SigningNonces<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hiding = S::basepoint_mul(&self.hiding).to_bytes();
+        let binding = S::basepoint_mul(&self.binding).to_bytes();
+        f.debug_struct("SigningNonces")
+            .field(
+                "fingerprint",
+                &Fingerprint::new(&[hiding.as_ref(), binding.as_ref()]),
+            )
+            .finish()
+    }
+}
 
-impl<S: SpendAuth> SigningNonces<S> {
+impl<S: SigType> From<&SigningNonces<S>> for [u8; 64] {
+    /// Encodes the nonces as the hiding nonce followed by the binding nonce.
+    ///
+    /// This is only meant for keeping nonces in secret storage between the two
+    /// rounds of signing; the encoding must never be shared.
+    fn from(nonces: &SigningNonces<S>) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(nonces.hiding.to_repr().as_ref());
+        bytes[32..].copy_from_slice(nonces.binding.to_repr().as_ref());
+        bytes
+    }
+}
+
+impl<S: SigType> TryFrom<[u8; 64]> for SigningNonces<S> {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 64]) -> Result<Self, Self::Error> {
+        let decode = |bytes: &[u8]| {
+            let mut repr = <S::Scalar as PrimeField>::Repr::default();
+            repr.as_mut().copy_from_slice(bytes);
+            Option::<S::Scalar>::from(S::Scalar::from_repr(repr))
+                .filter(|scalar| !bool::from(scalar.is_zero()))
+                .ok_or(Error::MalformedNonces)
+        };
+
+        Ok(SigningNonces {
+            hiding: decode(&bytes[..32])?,
+            binding: decode(&bytes[32..])?,
+        })
+    }
+}
+
+impl<S: SigType> ConstantTimeEq for SigningNonces<S> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.hiding.ct_eq(&other.hiding) & self.binding.ct_eq(&other.binding)
+    }
+}
+
+impl<S: SigType> SigningNonces<S> {
     /// Generates a new signing nonce.
     ///
     /// Each participant generates signing nonces before performing a signing
@@ -374,7 +732,7 @@ impl<S: SpendAuth> SigningNonces<S> {
 /// This step can be batched if desired by the implementation. Each
 /// SigningCommitment can be used for exactly *one* signature.
 #[derive(Copy, Clone)]
-pub struct SigningCommitments<S: SpendAuth> {
+pub struct SigningCommitments<S: SigType> {
     /// The participant index
     pub(crate) index: u64,
     /// The hiding point.
@@ -383,37 +741,210 @@ pub struct SigningCommitments<S: SpendAuth> {
     pub(crate) binding: S::Point,
 }
 
-impl<S: SpendAuth> From<(u64, &SigningNonces<S>)> for SigningCommitments<S> {
-    /// For SpendAuth signatures only, not Binding signatures, in RedDSA/Zcash.
+impl<S: SigType> SigningCommitments<S> {
+    /// The participant index of the signer that published these commitments.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+impl<S: SigType> From<(u64, &SigningNonces<S>)> for SigningCommitments<S> {
     fn from((index, nonces): (u64, &SigningNonces<S>)) -> Self {
         Self {
             index,
-            hiding: S::basepoint() * nonces.hiding,
-            binding: S::basepoint() * nonces.binding,
+            hiding: S::basepoint_mul(&nonces.hiding),
+            binding: S::basepoint_mul(&nonces.binding),
         }
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, S: SigType> arbitrary::Arbitrary<'a> for SigningCommitments<S> {
+    /// Generates commitments to arbitrary nonces, for an arbitrary participant.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            index: u.arbitrary()?,
+            hiding: S::basepoint() * arbitrary_scalar::<S>(u)?,
+            binding: S::basepoint() * arbitrary_scalar::<S>(u)?,
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_scalar<S: SigType>(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<S::Scalar> {
+    let mut bytes = [0; 64];
+    bytes.copy_from_slice(u.bytes(64)?);
+    Ok(S::Scalar::from_bytes_wide(&bytes))
+}
+
+/// Identifies a single signing session.
+///
+/// The coordinator picks a fresh session ID for every signing operation, and
+/// each signer must learn the ID of the session it agreed to take part in
+/// independently of the [`SigningPackage`], e.g. as part of the
+/// protocol-specific negotiation of the message to sign. The session ID is
+/// bound into every signer's binding factor, so commitments and signature
+/// shares from one session cannot be spliced into another, and [`sign`]
+/// rejects signing packages for a session other than the expected one.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SessionId([u8; 32]);
+
+impl SessionId {
+    /// Generates a new, random session ID.
+    pub fn new<R>(rng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut bytes = [0; 32];
+        rng.fill_bytes(&mut bytes);
+        SessionId(bytes)
+    }
+}
+
+impl From<[u8; 32]> for SessionId {
+    fn from(bytes: [u8; 32]) -> SessionId {
+        SessionId(bytes)
+    }
+}
+
+impl From<SessionId> for [u8; 32] {
+    fn from(session_id: SessionId) -> [u8; 32] {
+        session_id.0
+    }
+}
+
 /// Generated by the coordinator of the signing operation and distributed to
 /// each signing party.
-pub struct SigningPackage<S: SpendAuth> {
+///
+/// Signing packages are built with [`SigningPackage::new`], which checks the
+/// commitments against the group's [`PublicKeyPackage`].
+#[derive(Clone)]
+pub struct SigningPackage<S: SigType> {
+    /// The signing session this package belongs to.
+    pub(crate) session_id: SessionId,
     /// The set of commitments participants published in the first round of the
     /// protocol.
-    pub signing_commitments: Vec<SigningCommitments<S>>,
+    pub(crate) signing_commitments: Vec<SigningCommitments<S>>,
     /// Message which each participant will sign.
     ///
     /// Each signer should perform protocol-specific verification on the message.
-    pub message: Vec<u8>,
+    pub(crate) message: Vec<u8>,
+}
+
+impl<S: SigType> SigningPackage<S> {
+    /// Creates a signing package for `message` in the session `session_id`,
+    /// from one commitment per participating signer.
+    ///
+    /// Returns an error if two commitments belong to the same participant, if
+    /// a commitment belongs to a participant that is not part of the group
+    /// described by `pubkeys`, if a commitment is the identity, or if there are
+    /// fewer commitments than the group's threshold.
+    pub fn new(
+        pubkeys: &PublicKeyPackage<S>,
+        session_id: SessionId,
+        signing_commitments: Vec<SigningCommitments<S>>,
+        message: Vec<u8>,
+    ) -> Result<Self, Error> {
+        let identity = S::Point::identity();
+        for (i, commitment) in signing_commitments.iter().enumerate() {
+            if signing_commitments[..i]
+                .iter()
+                .any(|other| other.index == commitment.index)
+            {
+                return Err(Error::DuplicateCommitment);
+            }
+            if !pubkeys.signer_pubkeys.contains_key(&commitment.index) {
+                return Err(Error::UnknownSigner);
+            }
+            if commitment.hiding == identity || commitment.binding == identity {
+                return Err(Error::IdentityCommitment);
+            }
+        }
+
+        if signing_commitments.len() < pubkeys.threshold as usize {
+            return Err(Error::NotEnoughCommitments);
+        }
+
+        Ok(SigningPackage {
+            session_id,
+            signing_commitments,
+            message,
+        })
+    }
+
+    /// The signing session this package belongs to.
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    /// The message to be signed.
+    ///
+    /// Signers can show it, together with the
+    /// [`signer_indices`](Self::signer_indices), to whoever approves the
+    /// signature before calling [`sign`].
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The number of signers taking part in this signing operation.
+    pub fn num_signers(&self) -> usize {
+        self.signing_commitments.len()
+    }
+
+    /// Returns the participant indices of the signers taking part in this
+    /// signing operation, in the order their commitments were given.
+    pub fn signer_indices(&self) -> Vec<u64> {
+        self.signing_commitments
+            .iter()
+            .map(|commitment| commitment.index)
+            .collect()
+    }
+
+    /// Returns the commitments of the signer with participant index `index`,
+    /// if that signer takes part in this signing operation.
+    pub fn signing_commitment(&self, index: u64) -> Option<&SigningCommitments<S>> {
+        self.signing_commitments
+            .iter()
+            .find(|commitment| commitment.index == index)
+    }
+
+    /// Iterates over the commitments of all signers taking part in this
+    /// signing operation, in the order they were given.
+    pub fn signing_commitments(&self) -> impl Iterator<Item = &SigningCommitments<S>> {
+        self.signing_commitments.iter()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, S: SigType> arbitrary::Arbitrary<'a> for SigningPackage<S> {
+    /// Generates a signing package that, like those built with
+    /// [`SigningPackage::new`], has at most one commitment per participant, but
+    /// is otherwise unchecked.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut session_id = [0; 32];
+        session_id.copy_from_slice(u.bytes(32)?);
+        let mut signing_commitments: Vec<SigningCommitments<S>> = u.arbitrary()?;
+        signing_commitments.sort_by_key(|commitment| commitment.index);
+        signing_commitments.dedup_by_key(|commitment| commitment.index);
+
+        Ok(Self {
+            session_id: SessionId(session_id),
+            signing_commitments,
+            message: u.arbitrary()?,
+        })
+    }
 }
 
 /// A representation of a single signature used in FROST structures and messages.
 #[derive(Clone, Copy, Default, PartialEq)]
-pub struct SignatureResponse<S: SpendAuth>(pub(crate) S::Scalar);
+pub struct SignatureResponse<S: SigType>(pub(crate) S::Scalar);
 
 /// A participant's signature share, which the coordinator will use to aggregate
 /// with all other signer's shares into the joint signature.
 #[derive(Clone, Copy, Default)]
-pub struct SignatureShare<S: SpendAuth> {
+pub struct SignatureShare<S: SigType> {
     /// Represents the participant index.
     pub(crate) index: u64,
     /// This participant's signature over the message.
@@ -424,9 +955,26 @@ pub struct SignatureShare<S: SpendAuth> {
 // of scope).  Luckily the derived `Default` includes the `Default` impl of
 // jubjub::Fr/Scalar, which is four 0u64's under the hood, and u32, which is
 // 0u32.
-impl<S: SpendAuth> DefaultIsZeroes for SignatureShare<S> {}
+#[cfg(feature = "zeroize")]
+impl<S: SigType> DefaultIsZeroes for SignatureShare<S> {}
+
+impl<S: SigType> ConstantTimeEq for SignatureShare<S> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.index.ct_eq(&other.index) & self.signature.0.ct_eq(&other.signature.0)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, S: SigType> arbitrary::Arbitrary<'a> for SignatureShare<S> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            index: u.arbitrary()?,
+            signature: SignatureResponse(arbitrary_scalar::<S>(u)?),
+        })
+    }
+}
 
-impl<S: SpendAuth> SignatureShare<S> {
+impl<S: SigType> SignatureShare<S> {
     /// Tests if a signature share issued by a participant is valid before
     /// aggregating it into a final joint signature to publish.
     pub fn check_is_valid(
@@ -435,9 +983,9 @@ impl<S: SpendAuth> SignatureShare<S> {
         lambda_i: S::Scalar,
         commitment: S::Point,
         challenge: S::Scalar,
-    ) -> Result<(), &'static str> {
+    ) -> Result<(), Error> {
         if (S::basepoint() * self.signature.0) != (commitment + pubkey.0 * challenge * lambda_i) {
-            return Err("Invalid signature share");
+            return Err(Error::InvalidSignatureShare);
         }
         Ok(())
     }
@@ -450,11 +998,19 @@ impl<S: SpendAuth> SignatureShare<S> {
 /// perform the first round. Batching entails generating more than one
 /// nonce/commitment pair at a time.  Nonces should be stored in secret storage
 /// for later use, whereas the commitments are published.
-
+///
 /// The number of nonces is limited to 255. This limit can be increased if it
 /// turns out to be too conservative.
 // TODO: Make sure the above is a correct statement, fix if needed in:
 // https://github.com/ZcashFoundation/redjubjub/issues/111
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(num_nonces = num_nonces, signer = participant_index)
+    )
+)]
 pub fn preprocess<R, S>(
     num_nonces: u8,
     participant_index: u64,
@@ -462,7 +1018,7 @@ pub fn preprocess<R, S>(
 ) -> (Vec<SigningNonces<S>>, Vec<SigningCommitments<S>>)
 where
     R: CryptoRng + RngCore,
-    S: SpendAuth,
+    S: SigType,
 {
     let mut signing_nonces: Vec<SigningNonces<S>> = Vec::with_capacity(num_nonces as usize);
     let mut signing_commitments: Vec<SigningCommitments<S>> =
@@ -477,9 +1033,19 @@ where
     (signing_nonces, signing_commitments)
 }
 
+/// Like [`preprocess`], but draws the nonces from the operating system's RNG.
+#[cfg(feature = "std")]
+pub fn preprocess_default<S: SigType>(
+    num_nonces: u8,
+    participant_index: u64,
+) -> (Vec<SigningNonces<S>>, Vec<SigningCommitments<S>>) {
+    preprocess(num_nonces, participant_index, &mut rand_core::OsRng)
+}
+
 /// Generates the binding factor that ensures each signature share is strongly
-/// bound to a signing set, specific set of commitments, and a specific message.
-fn gen_rho_i<S: SpendAuth>(index: u64, signing_package: &SigningPackage<S>) -> S::Scalar {
+/// bound to a signing session, signing set, specific set of commitments, and a
+/// specific message.
+fn gen_rho_i<S: SigType>(index: u64, signing_package: &SigningPackage<S>) -> S::Scalar {
     // Hash signature message with HStar before deriving the binding factor.
     //
     // To avoid a collision with other inputs to the hash that generates the
@@ -493,6 +1059,7 @@ fn gen_rho_i<S: SpendAuth>(index: u64, signing_package: &SigningPackage<S>) -> S
     let mut hasher = HStar::<S>::default();
     hasher
         .update("FROST_rho".as_bytes())
+        .update(signing_package.session_id.0)
         .update(index.to_be_bytes())
         .update(message_hash.to_repr());
 
@@ -509,31 +1076,64 @@ fn gen_rho_i<S: SpendAuth>(index: u64, signing_package: &SigningPackage<S>) -> S
 
 /// Generates the group commitment which is published as part of the joint
 /// Schnorr signature.
-fn gen_group_commitment<S: SpendAuth>(
+fn gen_group_commitment<S: SigType>(
     signing_package: &SigningPackage<S>,
-    bindings: &HashMap<u64, S::Scalar>,
-) -> Result<GroupCommitment<S>, &'static str> {
-    let identity = S::Point::identity();
-    let mut accumulator = identity;
+    bindings: &BTreeMap<u64, S::Scalar>,
+) -> Result<GroupCommitment<S>, Error> {
+    #[cfg(not(feature = "rayon"))]
+    let terms = signing_package
+        .signing_commitments
+        .iter()
+        .map(|commitment| gen_commitment_term(commitment, bindings));
+    // The terms are collected in order, so that the first invalid commitment
+    // determines the error, as without `rayon`.
+    #[cfg(feature = "rayon")]
+    let terms = signing_package
+        .signing_commitments
+        .par_iter()
+        .map(|commitment| gen_commitment_term(commitment, bindings))
+        .collect::<Vec<_>>();
+
+    let mut accumulator = S::Point::identity();
+    for term in terms {
+        accumulator += term?;
+    }
 
-    for commitment in signing_package.signing_commitments.iter() {
-        // The following check prevents a party from accidentally revealing their share.
-        // Note that the '&&' operator would be sufficient.
-        if identity == commitment.binding || identity == commitment.hiding {
-            return Err("Commitment equals the identity.");
-        }
+    Ok(GroupCommitment(accumulator.to_affine()))
+}
 
-        let rho_i = bindings
-            .get(&commitment.index)
-            .ok_or("No matching commitment index")?;
-        accumulator += commitment.hiding + (commitment.binding * rho_i)
+/// Computes the term of a participant's commitment in the group commitment.
+fn gen_commitment_term<S: SigType>(
+    commitment: &SigningCommitments<S>,
+    bindings: &BTreeMap<u64, S::Scalar>,
+) -> Result<S::Point, Error> {
+    // The following check prevents a party from accidentally revealing their share.
+    // Note that the '&&' operator would be sufficient.
+    let identity = S::Point::identity();
+    if identity == commitment.binding || identity == commitment.hiding {
+        return Err(Error::IdentityCommitment);
     }
 
-    Ok(GroupCommitment(accumulator.to_affine()))
+    let rho_i = bindings
+        .get(&commitment.index)
+        .ok_or(Error::MissingBinding)?;
+    Ok(commitment.hiding + (commitment.binding * rho_i))
+}
+
+/// Computes the binding factor of every participant in the signing package.
+fn gen_bindings<S: SigType>(signing_package: &SigningPackage<S>) -> BTreeMap<u64, S::Scalar> {
+    #[cfg(not(feature = "rayon"))]
+    let commitments = signing_package.signing_commitments.iter();
+    #[cfg(feature = "rayon")]
+    let commitments = signing_package.signing_commitments.par_iter();
+
+    commitments
+        .map(|comm| (comm.index, gen_rho_i(comm.index, signing_package)))
+        .collect()
 }
 
 /// Generates the challenge as is required for Schnorr signatures.
-fn gen_challenge<S: SpendAuth>(
+fn gen_challenge<S: SigType>(
     signing_package: &SigningPackage<S>,
     group_commitment: &GroupCommitment<S>,
     group_public: &VerificationKey<S>,
@@ -548,22 +1148,38 @@ fn gen_challenge<S: SpendAuth>(
 }
 
 /// Generates the lagrange coefficient for the i'th participant.
-fn gen_lagrange_coeff<S: SpendAuth>(
+fn gen_lagrange_coeff<S: SigType>(
     signer_index: u64,
     signing_package: &SigningPackage<S>,
-) -> Result<S::Scalar, &'static str> {
+) -> Result<S::Scalar, Error> {
+    lagrange_coeff_at_zero::<S, _>(
+        signer_index,
+        signing_package
+            .signing_commitments
+            .iter()
+            .map(|commitment| commitment.index),
+    )
+}
+
+/// Computes the lagrange coefficient of the participant with index
+/// `signer_index` when interpolating at zero over the participants with indices
+/// `signer_indices`.
+fn lagrange_coeff_at_zero<S: SigType, I: IntoIterator<Item = u64>>(
+    signer_index: u64,
+    signer_indices: I,
+) -> Result<S::Scalar, Error> {
     let mut num = S::Scalar::one();
     let mut den = S::Scalar::one();
-    for commitment in signing_package.signing_commitments.iter() {
-        if commitment.index == signer_index {
+    for index in signer_indices {
+        if index == signer_index {
             continue;
         }
-        num *= S::Scalar::from(commitment.index as u64);
-        den *= S::Scalar::from(commitment.index as u64) - S::Scalar::from(signer_index as u64);
+        num *= S::Scalar::from(index);
+        den *= S::Scalar::from(index) - S::Scalar::from(signer_index);
     }
 
     if den == S::Scalar::zero() {
-        return Err("Duplicate shares provided");
+        return Err(Error::DuplicateShares);
     }
 
     // TODO: handle this unwrap better like other CtOption's
@@ -572,6 +1188,31 @@ fn gen_lagrange_coeff<S: SpendAuth>(
     Ok(lagrange_coeff)
 }
 
+/// Computes the Lagrange coefficient that [`sign`] and [`aggregate`] apply to
+/// the share of the participant with index `signer_index`, when the
+/// participants with indices `signer_indices` sign together.
+///
+/// This lets auditors and alternative coordinators recompute the interpolation
+/// weights of a signing session independently. Returns an error if
+/// `signer_index` is not one of `signer_indices`, or if `signer_indices`
+/// contains duplicates.
+pub fn derive_lagrange_coefficient<S: SigType>(
+    signer_index: u64,
+    signer_indices: &[u64],
+) -> Result<S::Scalar, Error> {
+    if !signer_indices.contains(&signer_index) {
+        return Err(Error::UnknownSigner);
+    }
+
+    for (i, index) in signer_indices.iter().enumerate() {
+        if signer_indices[..i].contains(index) {
+            return Err(Error::DuplicateShares);
+        }
+    }
+
+    lagrange_coeff_at_zero::<S, _>(signer_index, signer_indices.iter().copied())
+}
+
 /// Performed once by each participant selected for the signing operation.
 ///
 /// Receives the message to be signed and a set of signing commitments and a set
@@ -580,32 +1221,44 @@ fn gen_lagrange_coeff<S: SpendAuth>(
 ///
 /// Assumes the participant has already determined which nonce corresponds with
 /// the commitment that was assigned by the coordinator in the SigningPackage.
-pub fn sign<S: SpendAuth>(
+///
+/// `session_id` is the [`SessionId`] of the session the participant agreed to
+/// take part in; signing packages for any other session are rejected with
+/// [`Error::SessionMismatch`].
+///
+/// Branches and memory accesses only depend on the public signing package,
+/// never on the participant's nonces or share.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(signer = share_package.index))
+)]
+pub fn sign<S: SigType>(
     signing_package: &SigningPackage<S>,
+    session_id: SessionId,
     participant_nonces: SigningNonces<S>,
     share_package: &SharePackage<S>,
-) -> Result<SignatureShare<S>, &'static str> {
-    let mut bindings: HashMap<u64, S::Scalar> =
-        HashMap::with_capacity(signing_package.signing_commitments.len());
-
-    for comm in signing_package.signing_commitments.iter() {
-        let rho_i = gen_rho_i(comm.index, &signing_package);
-        bindings.insert(comm.index, rho_i);
+) -> Result<SignatureShare<S>, Error> {
+    if signing_package.session_id != session_id {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("signing package is for a different session");
+        return Err(Error::SessionMismatch);
     }
 
-    let lambda_i = gen_lagrange_coeff(share_package.index, &signing_package)?;
+    let bindings = gen_bindings(signing_package);
+
+    let lambda_i = gen_lagrange_coeff(share_package.index, signing_package)?;
 
-    let group_commitment = gen_group_commitment(&signing_package, &bindings)?;
+    let group_commitment = gen_group_commitment(signing_package, &bindings)?;
 
     let challenge = gen_challenge(
-        &signing_package,
+        signing_package,
         &group_commitment,
         &share_package.group_public,
     );
 
     let participant_rho_i = bindings
         .get(&share_package.index)
-        .ok_or("No matching binding!")?;
+        .ok_or(Error::MissingBinding)?;
 
     // The Schnorr signature share
     let signature: S::Scalar = participant_nonces.hiding
@@ -618,11 +1271,88 @@ pub fn sign<S: SpendAuth>(
     })
 }
 
+/// Verifies a single participant's signature share against the signing
+/// package, given the binding factors and challenge for that package.
+fn verify_signature_share<S: SigType>(
+    signing_package: &SigningPackage<S>,
+    bindings: &BTreeMap<u64, S::Scalar>,
+    challenge: S::Scalar,
+    signing_share: &SignatureShare<S>,
+    pubkeys: &PublicKeyPackage<S>,
+) -> Result<(), Error> {
+    let signer_pubkey = pubkeys
+        .signer_pubkeys
+        .get(&signing_share.index)
+        .ok_or(Error::UnknownSigner)?;
+    let lambda_i = gen_lagrange_coeff(signing_share.index, signing_package)?;
+    let signer_commitment = signing_package
+        .signing_commitments
+        .iter()
+        .find(|comm| comm.index == signing_share.index)
+        .ok_or(Error::MissingCommitment)?;
+    let rho_i = bindings
+        .get(&signing_share.index)
+        .ok_or(Error::MissingBinding)?;
+
+    let commitment_i = signer_commitment.hiding + (signer_commitment.binding * rho_i);
+
+    signing_share.check_is_valid(signer_pubkey, lambda_i, commitment_i, challenge)
+}
+
+/// Verifies every participant's signature share, and returns the indices of
+/// the participants whose shares are invalid, in the order of
+/// `signing_shares`.
+///
+/// [`aggregate`] stops at the first invalid share, while this finds all of
+/// them at once. It does not aggregate a signature from the remaining valid
+/// shares, and no function of this module does: even if there are at least as
+/// many valid shares as the threshold, every share was computed with the group
+/// commitment, challenge and Lagrange coefficient of the full set of
+/// participants in the signing package, and the group commitment includes the
+/// nonce commitments of the participants being excluded. After excluding the
+/// participants returned here, the coordinator must run a new signing session
+/// with fresh commitments, as [`roast`] does automatically.
+pub fn find_invalid_shares<S: SigType>(
+    signing_package: &SigningPackage<S>,
+    signing_shares: &[SignatureShare<S>],
+    pubkeys: &PublicKeyPackage<S>,
+) -> Result<Vec<u64>, Error> {
+    let bindings = gen_bindings(signing_package);
+
+    let group_commitment = gen_group_commitment(signing_package, &bindings)?;
+
+    let challenge = gen_challenge(signing_package, &group_commitment, &pubkeys.group_public);
+
+    #[cfg(not(feature = "rayon"))]
+    let signing_shares = signing_shares.iter();
+    #[cfg(feature = "rayon")]
+    let signing_shares = signing_shares.par_iter();
+
+    Ok(signing_shares
+        .filter(|signing_share| {
+            verify_signature_share(
+                signing_package,
+                &bindings,
+                challenge,
+                signing_share,
+                pubkeys,
+            )
+            .is_err()
+        })
+        .map(|signing_share| signing_share.index)
+        .collect())
+}
+
 /// Verifies each participant's signature share, and if all are valid,
 /// aggregates the shares into a signature to publish.
 ///
-/// Resulting signature is compatible with verification of a plain SpendAuth
-/// signature.
+/// Resulting signature is compatible with verification of a plain signature
+/// of the same type. It is verified against the group public key before it is
+/// returned, so it does not need to be verified again before it is published.
+///
+/// Every participant in the signing package must provide a valid share. To
+/// find out which participants to exclude from a new signing session when this
+/// fails, use [`find_invalid_shares`].
 ///
 /// This operation is performed by a coordinator that can communicate with all
 /// the signing participants before publishing the final signature. The
@@ -633,36 +1363,47 @@ pub fn sign<S: SpendAuth>(
 /// signature, if the coordinator themselves is a signer and misbehaves, they
 /// can avoid that step. However, at worst, this results in a denial of
 /// service attack due to publishing an invalid signature.
-pub fn aggregate<S: SpendAuth>(
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(num_shares = signing_shares.len()))
+)]
+pub fn aggregate<S: SigType>(
     signing_package: &SigningPackage<S>,
     signing_shares: &[SignatureShare<S>],
     pubkeys: &PublicKeyPackage<S>,
-) -> Result<Signature<S>, &'static str> {
-    let mut bindings: HashMap<u64, S::Scalar> =
-        HashMap::with_capacity(signing_package.signing_commitments.len());
-
-    for comm in signing_package.signing_commitments.iter() {
-        let rho_i = gen_rho_i(comm.index, &signing_package);
-        bindings.insert(comm.index, rho_i);
-    }
-
-    let group_commitment = gen_group_commitment(&signing_package, &bindings)?;
-
-    let challenge = gen_challenge(&signing_package, &group_commitment, &pubkeys.group_public);
-
-    for signing_share in signing_shares {
-        let signer_pubkey = pubkeys.signer_pubkeys[&signing_share.index];
-        let lambda_i = gen_lagrange_coeff(signing_share.index, &signing_package)?;
-        let signer_commitment = signing_package
-            .signing_commitments
-            .iter()
-            .find(|comm| comm.index == signing_share.index)
-            .ok_or("No matching signing commitment for signer")?;
-
-        let commitment_i =
-            signer_commitment.hiding + (signer_commitment.binding * bindings[&signing_share.index]);
-
-        signing_share.check_is_valid(&signer_pubkey, lambda_i, commitment_i, challenge)?;
+) -> Result<Signature<S>, Error> {
+    let bindings = gen_bindings(signing_package);
+
+    let group_commitment = gen_group_commitment(signing_package, &bindings)?;
+
+    let challenge = gen_challenge(signing_package, &group_commitment, &pubkeys.group_public);
+
+    // Report the first invalid share, whether or not the shares are verified
+    // in parallel.
+    let verify_share = |signing_share: &SignatureShare<S>| {
+        verify_signature_share(
+            signing_package,
+            &bindings,
+            challenge,
+            signing_share,
+            pubkeys,
+        )
+        .err()
+        .map(|error| (signing_share.index, error))
+    };
+    #[cfg(not(feature = "rayon"))]
+    let invalid = signing_shares.iter().find_map(verify_share);
+    #[cfg(feature = "rayon")]
+    let invalid = signing_shares.par_iter().find_map_first(verify_share);
+
+    if let Some((_signer, error)) = invalid {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            signer = _signer,
+            %error,
+            "signature share failed verification"
+        );
+        return Err(error);
     }
 
     // The aggregation of the signature shares by summing them up, resulting in
@@ -672,11 +1413,20 @@ pub fn aggregate<S: SpendAuth>(
         z += signature_share.signature.0;
     }
 
-    Ok(Signature {
+    let signature = Signature {
         r_bytes: group_commitment.0.to_bytes().as_ref().try_into().unwrap(),
         s_bytes: z.to_repr().as_ref().try_into().unwrap(),
         _marker: PhantomData,
-    })
+    };
+
+    // The shares are valid, but the signature is still invalid if shares are
+    // missing for some of the participants in the signing package.
+    pubkeys
+        .group_public
+        .verify(&signing_package.message, &signature)
+        .map_err(|_| Error::InvalidSignature)?;
+
+    Ok(signature)
 }
 
 #[cfg(test)]
@@ -686,14 +1436,14 @@ mod tests {
     use jubjub::Scalar;
     use rand::thread_rng;
 
-    fn reconstruct_secret<S: SpendAuth>(shares: Vec<Share<S>>) -> Result<S::Scalar, &'static str> {
+    fn reconstruct_secret<S: SigType>(shares: Vec<Share<S>>) -> Result<S::Scalar, &'static str> {
         let numshares = shares.len();
 
         if numshares < 1 {
             return Err("No shares provided");
         }
 
-        let mut lagrange_coeffs: Vec<S::Scalar> = Vec::with_capacity(numshares as usize);
+        let mut lagrange_coeffs: Vec<S::Scalar> = Vec::with_capacity(numshares);
 
         for i in 0..numshares {
             let mut num = S::Scalar::one();
@@ -702,9 +1452,9 @@ mod tests {
                 if j == i {
                     continue;
                 }
-                num *= S::Scalar::from(shares[j].receiver_index as u64);
-                den *= S::Scalar::from(shares[j].receiver_index as u64)
-                    - S::Scalar::from(shares[i].receiver_index as u64);
+                num *= S::Scalar::from(shares[j].receiver_index);
+                den *= S::Scalar::from(shares[j].receiver_index)
+                    - S::Scalar::from(shares[i].receiver_index);
             }
             if den == S::Scalar::zero() {
                 return Err("Duplicate shares provided");
@@ -736,9 +1486,26 @@ mod tests {
         let shares = generate_shares::<_, sapling::SpendAuth>(&secret, 5, 3, rng).unwrap();
 
         for share in shares.iter() {
-            assert_eq!(verify_share(&share), Ok(()));
+            assert_eq!(verify_share(share), Ok(()));
         }
 
         assert_eq!(reconstruct_secret(shares).unwrap(), secret.0)
     }
+
+    #[test]
+    fn check_signing_package_rejects_identity_commitments() {
+        let mut rng = thread_rng();
+        let (_, pubkeys) = keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+        let (_, commitments1) = preprocess(1, 1, &mut rng);
+        let (_, mut commitments2) = preprocess(1, 2, &mut rng);
+        commitments2[0].binding = jubjub::ExtendedPoint::identity();
+
+        let result = SigningPackage::new(
+            &pubkeys,
+            SessionId::default(),
+            vec![commitments1[0], commitments2[0]],
+            b"message".to_vec(),
+        );
+        assert_eq!(result.err(), Some(Error::IdentityCommitment));
+    }
 }
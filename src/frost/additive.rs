@@ -0,0 +1,204 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! n-of-n signing with additive shares.
+//!
+//! When every member of the group must sign, as in 2-of-2 custody, the signing
+//! key can be split into shares that simply add up to it. Key generation draws
+//! one random share per participant, with no polynomial or commitment to
+//! verify, and signing applies no Lagrange coefficients.
+//!
+//! The rounds are the same as for threshold signing: participants publish
+//! commitments made with [`preprocess`](super::preprocess), the coordinator
+//! builds a [`SigningPackage`] with the commitments of *every* participant, and
+//! participants [`sign`] and the coordinator [`aggregate`]s with the functions
+//! of this module.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{convert::TryInto, fmt, marker::PhantomData};
+
+use group::{
+    ff::{Field, PrimeField},
+    GroupEncoding,
+};
+use rand_core::{CryptoRng, RngCore};
+
+use super::{
+    gen_bindings, gen_challenge, gen_group_commitment, Error, Public, PublicKeyPackage, Secret,
+    SessionId, SignatureResponse, SignatureShare, SigningNonces, SigningPackage,
+};
+use crate::{private::SealedScalar, SigType, Signature, VerificationKey};
+
+/// An additive share of the group's signing key, generated by
+/// [`keygen_with_dealer`].
+pub struct AdditiveSharePackage<S: SigType> {
+    /// Denotes the participant index each share is owned by.
+    pub index: u64,
+    /// This participant's share of the signing key.
+    pub(crate) secret: Secret<S>,
+    /// This participant's public key.
+    pub(crate) public: Public<S>,
+    /// The public signing key that represents the entire group.
+    pub(crate) group_public: VerificationKey<S>,
+}
+
+impl<S: SigType> fmt::Debug for AdditiveSharePackage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdditiveSharePackage")
+            .field("index", &self.index)
+            .field("secret", &self.secret)
+            .field("public", &self.public)
+            .field("group_public", &self.group_public)
+            .finish()
+    }
+}
+
+impl<S: SigType> AdditiveSharePackage<S> {
+    /// This participant's public key.
+    pub fn public(&self) -> &Public<S> {
+        &self.public
+    }
+
+    /// The public signing key that represents the entire group.
+    pub fn group_public(&self) -> &VerificationKey<S> {
+        &self.group_public
+    }
+}
+
+/// Allows all participants' keys to be generated using a central, trusted
+/// dealer, as random shares that add up to the group's signing key.
+///
+/// The threshold of the returned [`PublicKeyPackage`] is `num_signers`. The
+/// number of signers is limited to 255.
+pub fn keygen_with_dealer<R: RngCore + CryptoRng, S: SigType>(
+    num_signers: u8,
+    mut rng: R,
+) -> Result<(Vec<AdditiveSharePackage<S>>, PublicKeyPackage<S>), Error> {
+    if num_signers == 0 {
+        return Err(Error::ZeroShares);
+    }
+
+    let secrets: Vec<Secret<S>> = (0..num_signers)
+        .map(|_| {
+            let mut bytes = [0; 64];
+            rng.fill_bytes(&mut bytes);
+            Secret(S::Scalar::from_bytes_wide(&bytes))
+        })
+        .collect();
+    let group_secret = secrets
+        .iter()
+        .fold(S::Scalar::zero(), |sum, secret| sum + secret.0);
+    let group_public = VerificationKey::from(&group_secret);
+
+    let mut signer_pubkeys = BTreeMap::new();
+    let sharepackages = secrets
+        .into_iter()
+        .zip(1..)
+        .map(|(secret, index)| {
+            let public = Public(S::basepoint_mul(&secret.0));
+            signer_pubkeys.insert(index, public);
+            AdditiveSharePackage {
+                index,
+                secret,
+                public,
+                group_public,
+            }
+        })
+        .collect();
+
+    Ok((
+        sharepackages,
+        PublicKeyPackage {
+            signer_pubkeys,
+            group_public,
+            threshold: num_signers,
+        },
+    ))
+}
+
+/// Performed by each participant, to produce their signature share.
+///
+/// This is [`frost::sign`](super::sign) for additive shares. `session_id` is
+/// the [`SessionId`] of the session the participant agreed to take part in.
+pub fn sign<S: SigType>(
+    signing_package: &SigningPackage<S>,
+    session_id: SessionId,
+    participant_nonces: SigningNonces<S>,
+    share_package: &AdditiveSharePackage<S>,
+) -> Result<SignatureShare<S>, Error> {
+    if signing_package.session_id != session_id {
+        return Err(Error::SessionMismatch);
+    }
+
+    let bindings = gen_bindings(signing_package);
+    let group_commitment = gen_group_commitment(signing_package, &bindings)?;
+    let challenge = gen_challenge(
+        signing_package,
+        &group_commitment,
+        &share_package.group_public,
+    );
+    let rho_i = bindings
+        .get(&share_package.index)
+        .ok_or(Error::MissingBinding)?;
+
+    let signature = participant_nonces.hiding
+        + (participant_nonces.binding * rho_i)
+        + (share_package.secret.0 * challenge);
+
+    Ok(SignatureShare {
+        index: share_package.index,
+        signature: SignatureResponse(signature),
+    })
+}
+
+/// Verifies each participant's signature share, and if all are valid,
+/// aggregates the shares into a signature to publish.
+///
+/// This is [`frost::aggregate`](super::aggregate) for additive shares: the
+/// signature is verified against the group public key before it is returned.
+pub fn aggregate<S: SigType>(
+    signing_package: &SigningPackage<S>,
+    signing_shares: &[SignatureShare<S>],
+    pubkeys: &PublicKeyPackage<S>,
+) -> Result<Signature<S>, Error> {
+    let bindings = gen_bindings(signing_package);
+    let group_commitment = gen_group_commitment(signing_package, &bindings)?;
+    let challenge = gen_challenge(signing_package, &group_commitment, &pubkeys.group_public);
+
+    let mut z = S::Scalar::zero();
+    for signing_share in signing_shares {
+        let signer_pubkey = pubkeys
+            .signer_pubkeys
+            .get(&signing_share.index)
+            .ok_or(Error::UnknownSigner)?;
+        let signer_commitment = signing_package
+            .signing_commitments
+            .iter()
+            .find(|comm| comm.index == signing_share.index)
+            .ok_or(Error::MissingCommitment)?;
+        let rho_i = bindings
+            .get(&signing_share.index)
+            .ok_or(Error::MissingBinding)?;
+
+        let commitment_i = signer_commitment.hiding + (signer_commitment.binding * rho_i);
+        signing_share.check_is_valid(signer_pubkey, S::Scalar::one(), commitment_i, challenge)?;
+
+        z += signing_share.signature.0;
+    }
+
+    let signature = Signature {
+        r_bytes: group_commitment.0.to_bytes().as_ref().try_into().unwrap(),
+        s_bytes: z.to_repr().as_ref().try_into().unwrap(),
+        _marker: PhantomData,
+    };
+
+    pubkeys
+        .group_public
+        .verify(&signing_package.message, &signature)
+        .map_err(|_| Error::InvalidSignature)?;
+
+    Ok(signature)
+}
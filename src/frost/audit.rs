@@ -0,0 +1,311 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! An append-only, hash-chained audit log of signing sessions.
+//!
+//! Threshold custody operators need a record of what the group signed, with
+//! whom, and how each session ended, which cannot be rewritten after the fact.
+//! An [`AuditLog`] records one [`Entry`] per signing session, with:
+//!
+//! - the BLAKE2b-256 hash of the message, personalized with
+//!   `"reddsa_AuditMsg_"`;
+//! - the group commitment \\(R\\), the randomness of the signature, which the
+//!   commitments of the participants determine;
+//! - the participant indices of the signers;
+//! - the [`Outcome`] of the session.
+//!
+//! Every entry also holds the hash of the entry before it, and its own hash
+//! covers that, so changing, removing or reordering entries breaks the chain.
+//! The hash of the last entry, [`AuditLog::head`], commits to the whole log
+//! and can be published or countersigned periodically.
+//!
+//! [`AuditLog::export`] encodes the log for review, and [`AuditLog::import`]
+//! decodes an exported log and checks its chain. An entry is encoded as:
+//!
+//! | Bytes    | Contents                                            |
+//! |----------|-----------------------------------------------------|
+//! | 8        | Sequence number, little-endian                      |
+//! | 32       | Session ID                                          |
+//! | 32       | Message hash                                        |
+//! | 32       | Group commitment                                    |
+//! | 8 + 8n   | Number of signers, and each participant index       |
+//! | 1 + ...  | Outcome tag, and the outcome's data                 |
+//! | 32       | Hash of the previous entry, or zeros for the first  |
+//! | 32       | Hash of this entry                                  |
+//!
+//! The outcome tag is `0` for [`Outcome::Signed`], followed by the 64-byte
+//! signature; `1` for [`Outcome::Failed`], followed by the number and indices
+//! of the misbehaving signers like the signers above; `2` for
+//! [`Outcome::Expired`]; and `3` for [`Outcome::Cancelled`]. An exported log is
+//! the concatenation of its entries, and the hash of an entry is the
+//! BLAKE2b-256 hash, personalized with `"reddsa_FROSTAudt"`, of its encoding
+//! up to and including the hash of the previous entry.
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use group::GroupEncoding;
+
+use super::{gen_bindings, gen_group_commitment, Error, SessionId, SigningPackage};
+use crate::{SigType, Signature};
+
+/// How a signing session ended.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// The session produced this signature.
+    Signed([u8; 64]),
+    /// The session failed, because of the signers with these participant
+    /// indices.
+    Failed(Vec<u64>),
+    /// The session expired before every signer answered.
+    Expired,
+    /// The session was cancelled.
+    Cancelled,
+}
+
+impl<S: SigType> From<Signature<S>> for Outcome {
+    fn from(signature: Signature<S>) -> Outcome {
+        Outcome::Signed(signature.into())
+    }
+}
+
+/// The record of one signing session in an [`AuditLog`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    sequence: u64,
+    session_id: SessionId,
+    message_hash: [u8; 32],
+    group_commitment: [u8; 32],
+    signers: Vec<u64>,
+    outcome: Outcome,
+    previous_hash: [u8; 32],
+    hash: [u8; 32],
+}
+
+impl Entry {
+    /// The position of this entry in the log, counted from 0.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// The ID of the signing session.
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    /// The hash of the message that was to be signed.
+    pub fn message_hash(&self) -> [u8; 32] {
+        self.message_hash
+    }
+
+    /// The encoding of the group commitment of the session.
+    pub fn group_commitment(&self) -> [u8; 32] {
+        self.group_commitment
+    }
+
+    /// The participant indices of the signers of the session, in the order of
+    /// their commitments.
+    pub fn signers(&self) -> &[u64] {
+        &self.signers
+    }
+
+    /// How the session ended.
+    pub fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+
+    /// The hash of the previous entry, or zeros for the first entry.
+    pub fn previous_hash(&self) -> [u8; 32] {
+        self.previous_hash
+    }
+
+    /// The hash of this entry.
+    pub fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// Encodes every field but the hash of this entry.
+    fn encode_body(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.sequence.to_le_bytes());
+        bytes.extend_from_slice(&<[u8; 32]>::from(self.session_id));
+        bytes.extend_from_slice(&self.message_hash);
+        bytes.extend_from_slice(&self.group_commitment);
+        encode_indices(&self.signers, bytes);
+        match &self.outcome {
+            Outcome::Signed(signature) => {
+                bytes.push(0);
+                bytes.extend_from_slice(signature);
+            }
+            Outcome::Failed(signers) => {
+                bytes.push(1);
+                encode_indices(signers, bytes);
+            }
+            Outcome::Expired => bytes.push(2),
+            Outcome::Cancelled => bytes.push(3),
+        }
+        bytes.extend_from_slice(&self.previous_hash);
+    }
+
+    fn compute_hash(&self) -> [u8; 32] {
+        let mut body = Vec::new();
+        self.encode_body(&mut body);
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"reddsa_FROSTAudt")
+            .hash(&body);
+        hash.as_bytes().try_into().unwrap()
+    }
+}
+
+fn encode_indices(indices: &[u64], bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&(indices.len() as u64).to_le_bytes());
+    for index in indices {
+        bytes.extend_from_slice(&index.to_le_bytes());
+    }
+}
+
+/// Reads encoded entries from the front of a byte slice.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::MalformedAuditLog);
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn indices(&mut self) -> Result<Vec<u64>, Error> {
+        let len = self.u64()?;
+        // Check the length before allocating, so that a crafted length cannot
+        // exhaust memory.
+        if len > (self.0.len() / 8) as u64 {
+            return Err(Error::MalformedAuditLog);
+        }
+        (0..len).map(|_| self.u64()).collect()
+    }
+
+    fn entry(&mut self) -> Result<Entry, Error> {
+        Ok(Entry {
+            sequence: self.u64()?,
+            session_id: SessionId::from(self.array::<32>()?),
+            message_hash: self.array()?,
+            group_commitment: self.array()?,
+            signers: self.indices()?,
+            outcome: match self.array::<1>()?[0] {
+                0 => Outcome::Signed(self.array()?),
+                1 => Outcome::Failed(self.indices()?),
+                2 => Outcome::Expired,
+                3 => Outcome::Cancelled,
+                _ => return Err(Error::MalformedAuditLog),
+            },
+            previous_hash: self.array()?,
+            hash: self.array()?,
+        })
+    }
+}
+
+/// An append-only, hash-chained log of signing sessions.
+#[derive(Clone, Debug, Default)]
+pub struct AuditLog {
+    entries: Vec<Entry>,
+}
+
+impl AuditLog {
+    /// Creates an empty audit log.
+    pub fn new() -> AuditLog {
+        AuditLog::default()
+    }
+
+    /// Appends an entry recording that the session of `signing_package`
+    /// ended with `outcome`, and returns it.
+    ///
+    /// Returns an error if the group commitment of the package cannot be
+    /// computed, which only happens for packages that no signer would sign.
+    pub fn record<S: SigType>(
+        &mut self,
+        signing_package: &SigningPackage<S>,
+        outcome: Outcome,
+    ) -> Result<&Entry, Error> {
+        let bindings = gen_bindings(signing_package);
+        let group_commitment = gen_group_commitment(signing_package, &bindings)?;
+
+        let message_hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"reddsa_AuditMsg_")
+            .hash(&signing_package.message);
+
+        let mut entry = Entry {
+            sequence: self.entries.len() as u64,
+            session_id: signing_package.session_id,
+            message_hash: message_hash.as_bytes().try_into().unwrap(),
+            group_commitment: group_commitment.0.to_bytes().as_ref().try_into().unwrap(),
+            signers: signing_package.signer_indices(),
+            outcome,
+            previous_hash: self.head(),
+            hash: [0; 32],
+        };
+        entry.hash = entry.compute_hash();
+        self.entries.push(entry);
+
+        Ok(self.entries.last().unwrap())
+    }
+
+    /// The entries of the log, oldest first.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The hash of the last entry, which commits to the whole log, or zeros if
+    /// the log is empty.
+    pub fn head(&self) -> [u8; 32] {
+        self.entries.last().map_or([0; 32], |entry| entry.hash)
+    }
+
+    /// Encodes the log for export, in the format described in the
+    /// [module documentation](self).
+    pub fn export(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for entry in self.entries.iter() {
+            entry.encode_body(&mut bytes);
+            bytes.extend_from_slice(&entry.hash);
+        }
+        bytes
+    }
+
+    /// Decodes an exported log, and checks that its entries are numbered in
+    /// order and chained by their hashes.
+    ///
+    /// Returns [`Error::MalformedAuditLog`] if the bytes are not an encoded
+    /// log, and [`Error::BrokenAuditChain`] if an entry was changed, removed
+    /// or reordered. Check [`AuditLog::head`] against a trusted copy to also
+    /// detect entries removed from the end.
+    pub fn import(bytes: &[u8]) -> Result<AuditLog, Error> {
+        let mut reader = Reader(bytes);
+        let mut log = AuditLog::new();
+        while !reader.0.is_empty() {
+            let entry = reader.entry()?;
+            if entry.sequence != log.entries.len() as u64
+                || entry.previous_hash != log.head()
+                || entry.hash != entry.compute_hash()
+            {
+                return Err(Error::BrokenAuditChain);
+            }
+            log.entries.push(entry);
+        }
+        Ok(log)
+    }
+}
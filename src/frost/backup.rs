@@ -0,0 +1,246 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! A versioned, passphrase-encrypted at-rest format for [`SharePackage`]s.
+//!
+//! The passphrase is stretched into a key with Argon2id, and the share is
+//! sealed with ChaCha20-Poly1305. An encrypted backup is laid out as:
+//!
+//! | Bytes    | Contents                                     |
+//! |----------|----------------------------------------------|
+//! | 1        | Format version, currently `1`                |
+//! | 4        | Argon2id memory cost in KiB, little-endian   |
+//! | 4        | Argon2id number of passes, little-endian     |
+//! | 4        | Argon2id degree of parallelism, little-endian|
+//! | 16       | Argon2id salt                                |
+//! | 12       | ChaCha20-Poly1305 nonce                      |
+//! | the rest | Ciphertext and 16-byte authentication tag    |
+//!
+//! The header (everything before the ciphertext) is authenticated as
+//! associated data, together with the signature scheme's hash
+//! personalization, so a backup cannot be imported with tampered KDF
+//! parameters or for a different curve.
+//!
+//! The plaintext is the participant index (8 bytes, little-endian), the group
+//! public key (32 bytes), the secret share (32 bytes), the number of dealer
+//! commitments (1 byte), and the commitments themselves (32 bytes each).
+
+use alloc::vec::Vec;
+use std::convert::{TryFrom, TryInto};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use group::{ff::PrimeField, Curve, GroupEncoding};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use super::{
+    verify_share, Commitment, Error, Public, Secret, Share, ShareCommitment, SharePackage,
+};
+use crate::{SigType, VerificationKey};
+
+/// The current version of the encrypted backup format.
+const VERSION: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = 1 + 4 + 4 + 4 + SALT_LEN + NONCE_LEN;
+
+// Argon2id parameters used for new backups: 64 MiB, three passes, one lane.
+const M_COST: u32 = 64 * 1024;
+const T_COST: u32 = 3;
+const P_COST: u32 = 1;
+
+// The largest costs accepted when importing, so that a crafted header cannot
+// make the importer allocate unbounded memory or run for an unbounded time.
+const MAX_M_COST: u32 = 1024 * 1024;
+const MAX_T_COST: u32 = 16;
+const MAX_P_COST: u32 = 16;
+
+impl<S: SigType> SharePackage<S> {
+    /// Encrypts this share package under `passphrase`, for storing it at
+    /// rest.
+    ///
+    /// The result can be turned back into a [`SharePackage`] with
+    /// [`SharePackage::import_encrypted`], which also verifies the share
+    /// against the dealer's commitment.
+    pub fn export_encrypted<R: RngCore + CryptoRng>(
+        &self,
+        passphrase: &[u8],
+        mut rng: R,
+    ) -> Result<Vec<u8>, Error> {
+        let mut header = [0u8; HEADER_LEN];
+        header[0] = VERSION;
+        header[1..5].copy_from_slice(&M_COST.to_le_bytes());
+        header[5..9].copy_from_slice(&T_COST.to_le_bytes());
+        header[9..13].copy_from_slice(&P_COST.to_le_bytes());
+        rng.fill_bytes(&mut header[13..HEADER_LEN]);
+
+        let mut plaintext = self.to_backup_bytes();
+        let result = seal::<S>(&header, passphrase, &plaintext);
+        plaintext.zeroize();
+
+        let ciphertext = result?;
+        let mut bytes = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        bytes.extend_from_slice(&header);
+        bytes.extend_from_slice(&ciphertext);
+        Ok(bytes)
+    }
+
+    /// Decrypts a share package produced by
+    /// [`SharePackage::export_encrypted`].
+    ///
+    /// Returns [`Error::UnsupportedBackupVersion`] for backups written in an
+    /// unknown format version, [`Error::BackupDecryptionFailed`] if the
+    /// passphrase is wrong or the backup was modified, and
+    /// [`Error::InvalidShare`] if the decrypted share does not match the
+    /// dealer's commitment.
+    pub fn import_encrypted(bytes: &[u8], passphrase: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LEN {
+            return Err(Error::MalformedBackup);
+        }
+        let (header, ciphertext) = bytes.split_at(HEADER_LEN);
+        if header[0] != VERSION {
+            return Err(Error::UnsupportedBackupVersion);
+        }
+
+        let mut plaintext = open::<S>(header, passphrase, ciphertext)?;
+        let result = Self::from_backup_bytes(&plaintext);
+        plaintext.zeroize();
+        result
+    }
+
+    fn to_backup_bytes(&self) -> Vec<u8> {
+        let commitments = &self.share.commitment.0;
+        let mut bytes = Vec::with_capacity(8 + 32 + 32 + 1 + 32 * commitments.len());
+        bytes.extend_from_slice(&self.index.to_le_bytes());
+        bytes.extend_from_slice(&<[u8; 32]>::from(self.group_public));
+        bytes.extend_from_slice(self.share.value.0.to_repr().as_ref());
+        bytes.push(commitments.len() as u8);
+        for commitment in commitments {
+            bytes.extend_from_slice(commitment.0.to_bytes().as_ref());
+        }
+        bytes
+    }
+
+    fn from_backup_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 8 + 32 + 32 + 1 {
+            return Err(Error::MalformedBackup);
+        }
+        let index = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let group_public = VerificationKey::try_from(read_32(&bytes[8..40]))
+            .map_err(|_| Error::MalformedPublicKey)?;
+
+        let mut repr = <S::Scalar as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(&bytes[40..72]);
+        let maybe_scalar = S::Scalar::from_repr(repr);
+        repr.as_mut().zeroize();
+        if maybe_scalar.is_none().into() {
+            return Err(Error::MalformedBackup);
+        }
+        let value = Secret(maybe_scalar.unwrap());
+
+        let num_commitments = bytes[72] as usize;
+        let commitment_bytes = &bytes[73..];
+        if commitment_bytes.len() != 32 * num_commitments {
+            return Err(Error::MalformedBackup);
+        }
+        let mut commitments = Vec::with_capacity(num_commitments);
+        for chunk in commitment_bytes.chunks(32) {
+            let mut repr = <S::Point as GroupEncoding>::Repr::default();
+            repr.as_mut().copy_from_slice(chunk);
+            let maybe_point = S::Point::from_bytes(&repr);
+            if maybe_point.is_none().into() {
+                return Err(Error::MalformedBackup);
+            }
+            commitments.push(Commitment(maybe_point.unwrap().to_affine()));
+        }
+
+        let share = Share {
+            receiver_index: index,
+            value,
+            commitment: ShareCommitment(commitments),
+        };
+        verify_share(&share)?;
+
+        Ok(SharePackage {
+            group_public,
+            index,
+            public: Public(S::basepoint() * share.value.0),
+            share,
+        })
+    }
+}
+
+fn read_32(bytes: &[u8]) -> [u8; 32] {
+    bytes.try_into().unwrap()
+}
+
+/// Derives the encryption key for a backup from the passphrase and the KDF
+/// parameters and salt in `header`.
+fn derive_key(header: &[u8], passphrase: &[u8]) -> Result<[u8; 32], Error> {
+    let m_cost = u32::from_le_bytes(header[1..5].try_into().unwrap());
+    let t_cost = u32::from_le_bytes(header[5..9].try_into().unwrap());
+    let p_cost = u32::from_le_bytes(header[9..13].try_into().unwrap());
+    let salt = &header[13..13 + SALT_LEN];
+    if m_cost > MAX_M_COST || t_cost > MAX_T_COST || p_cost > MAX_P_COST {
+        return Err(Error::MalformedBackup);
+    }
+
+    let params =
+        Params::new(m_cost, t_cost, p_cost, Some(32)).map_err(|_| Error::MalformedBackup)?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|_| Error::MalformedBackup)?;
+    Ok(key)
+}
+
+fn associated_data<S: SigType>(header: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(16 + header.len());
+    aad.extend_from_slice(S::H_STAR_PERSONALIZATION);
+    aad.extend_from_slice(header);
+    aad
+}
+
+fn seal<S: SigType>(header: &[u8], passphrase: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut key = derive_key(header, passphrase)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    key.zeroize();
+
+    let nonce = Nonce::from_slice(&header[HEADER_LEN - NONCE_LEN..]);
+    let aad = associated_data::<S>(header);
+    cipher
+        .encrypt(
+            nonce,
+            Payload {
+                msg: plaintext,
+                aad: &aad,
+            },
+        )
+        .map_err(|_| Error::MalformedBackup)
+}
+
+fn open<S: SigType>(header: &[u8], passphrase: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut key = derive_key(header, passphrase)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    key.zeroize();
+
+    let nonce = Nonce::from_slice(&header[HEADER_LEN - NONCE_LEN..]);
+    let aad = associated_data::<S>(header);
+    cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: &aad,
+            },
+        )
+        .map_err(|_| Error::BackupDecryptionFailed)
+}
@@ -0,0 +1,205 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Echo broadcast, to check that every participant received the same messages
+//! in a broadcast round.
+//!
+//! Key generation is only secure if every participant sees the same
+//! commitments: a participant that sends different commitments to different
+//! peers (that *equivocates*) can leave the group with inconsistent key
+//! material. Without a reliable broadcast channel, participants can detect
+//! this by echoing what they received. After a broadcast round, each
+//! participant feeds the messages it received, and its own, into an
+//! [`EchoBroadcast`], sends the resulting [`Echo`] of message digests to every
+//! peer, and passes the echoes it receives back in. Once every echo is in,
+//! [`EchoBroadcast::finalize`] either returns a digest of the transcript of
+//! the round that every honest participant agrees on, or reports the senders
+//! whose messages were seen differently.
+//!
+//! Echoes are not signed, so a dishonest participant can make an honest
+//! sender appear to equivocate by echoing wrong digests. Either way the round
+//! must be aborted before any key material is derived from it; the reported
+//! senders tell the operators where to look.
+//!
+//! The digest of a message is the BLAKE2b-256 hash, personalized with
+//! `"reddsa_FROSTEcMs"`, of the session ID, the sender's participant index,
+//! and the message. The transcript digest is the BLAKE2b-256 hash,
+//! personalized with `"reddsa_FROSTEcho"`, of the session ID and every
+//! sender's participant index and message digest, in order of participant
+//! index.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::convert::TryInto;
+
+use super::{Error, SessionId};
+
+/// The digests of the messages a participant received in a broadcast round,
+/// sent to every peer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Echo {
+    /// The participant index of the participant that sends this echo.
+    pub sender: u64,
+    /// The digest of the message received from every participant, keyed by
+    /// participant index.
+    pub digests: BTreeMap<u64, [u8; 32]>,
+}
+
+/// One participant's state for checking the consistency of a broadcast round.
+pub struct EchoBroadcast {
+    session_id: SessionId,
+    index: u64,
+    participants: BTreeSet<u64>,
+    received: BTreeMap<u64, [u8; 32]>,
+    echoes: BTreeMap<u64, BTreeMap<u64, [u8; 32]>>,
+    equivocators: BTreeSet<u64>,
+}
+
+impl EchoBroadcast {
+    /// Creates the state of the participant `index` for a broadcast round of
+    /// the session `session_id` among `participants`, which must include
+    /// `index`.
+    ///
+    /// Returns [`Error::UnknownSigner`] if `participants` does not include
+    /// `index`.
+    pub fn new<I: IntoIterator<Item = u64>>(
+        session_id: SessionId,
+        index: u64,
+        participants: I,
+    ) -> Result<EchoBroadcast, Error> {
+        let participants: BTreeSet<u64> = participants.into_iter().collect();
+        if !participants.contains(&index) {
+            return Err(Error::UnknownSigner);
+        }
+
+        Ok(EchoBroadcast {
+            session_id,
+            index,
+            participants,
+            received: BTreeMap::new(),
+            echoes: BTreeMap::new(),
+            equivocators: BTreeSet::new(),
+        })
+    }
+
+    /// Records the broadcast message of `sender`, which is this participant
+    /// itself for its own message.
+    ///
+    /// Returns [`Error::UnknownSigner`] if `sender` is not a participant. If
+    /// `sender` already sent a different message, it is reported as an
+    /// equivocator.
+    pub fn receive(&mut self, sender: u64, message: &[u8]) -> Result<(), Error> {
+        if !self.participants.contains(&sender) {
+            return Err(Error::UnknownSigner);
+        }
+
+        let digest = self.message_digest(sender, message);
+        if *self.received.entry(sender).or_insert(digest) != digest {
+            self.equivocators.insert(sender);
+        }
+        Ok(())
+    }
+
+    /// The echo of the messages received so far, to be sent to every peer once
+    /// the message of every participant has been received.
+    ///
+    /// Returns [`Error::IncompleteBroadcast`] if a message is still missing.
+    pub fn echo(&self) -> Result<Echo, Error> {
+        if self.received.len() != self.participants.len() {
+            return Err(Error::IncompleteBroadcast);
+        }
+
+        Ok(Echo {
+            sender: self.index,
+            digests: self.received.clone(),
+        })
+    }
+
+    /// Records the echo of a peer. Every sender whose message the peer saw
+    /// differently than this participant is reported as an equivocator.
+    ///
+    /// Returns [`Error::UnknownSigner`] if the echo is not from a participant,
+    /// and [`Error::IncompleteBroadcast`] if it does not hold a digest for
+    /// exactly every participant. A second echo from the same peer that
+    /// differs from its first one reports the peer itself.
+    pub fn receive_echo(&mut self, echo: Echo) -> Result<(), Error> {
+        if !self.participants.contains(&echo.sender) {
+            return Err(Error::UnknownSigner);
+        }
+        if !echo.digests.keys().eq(self.participants.iter()) {
+            return Err(Error::IncompleteBroadcast);
+        }
+
+        let first = self
+            .echoes
+            .entry(echo.sender)
+            .or_insert_with(|| echo.digests.clone());
+        if *first != echo.digests {
+            self.equivocators.insert(echo.sender);
+        }
+        Ok(())
+    }
+
+    /// The senders reported as equivocators so far, in order of participant
+    /// index.
+    pub fn equivocators(&self) -> Vec<u64> {
+        let mut equivocators = self.equivocators.clone();
+        for digests in self.echoes.values() {
+            for (sender, digest) in digests.iter() {
+                if self.received.get(sender).map_or(false, |own| own != digest) {
+                    equivocators.insert(*sender);
+                }
+            }
+        }
+        equivocators.into_iter().collect()
+    }
+
+    /// Checks that every participant received the same messages, and returns
+    /// the digest of the transcript of the round.
+    ///
+    /// Returns [`Error::IncompleteBroadcast`] if a message or an echo from a
+    /// peer is still missing, and [`Error::EquivocationDetected`] if any
+    /// sender was seen to equivocate, which
+    /// [`equivocators`](Self::equivocators) then lists.
+    pub fn finalize(&self) -> Result<[u8; 32], Error> {
+        if !self.equivocators().is_empty() {
+            return Err(Error::EquivocationDetected);
+        }
+        if self.received.len() != self.participants.len()
+            || self
+                .participants
+                .iter()
+                .any(|index| *index != self.index && !self.echoes.contains_key(index))
+        {
+            return Err(Error::IncompleteBroadcast);
+        }
+
+        let mut state = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"reddsa_FROSTEcho")
+            .to_state();
+        state.update(&<[u8; 32]>::from(self.session_id));
+        for (sender, digest) in self.received.iter() {
+            state.update(&sender.to_le_bytes());
+            state.update(digest);
+        }
+        Ok(state.finalize().as_bytes().try_into().unwrap())
+    }
+
+    fn message_digest(&self, sender: u64, message: &[u8]) -> [u8; 32] {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"reddsa_FROSTEcMs")
+            .to_state()
+            .update(&<[u8; 32]>::from(self.session_id))
+            .update(&sender.to_le_bytes())
+            .update(message)
+            .finalize();
+        hash.as_bytes().try_into().unwrap()
+    }
+}
@@ -0,0 +1,372 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Encryption of dealer-generated [`SharePackage`]s to their recipients, for
+//! distributing them over an untrusted broadcast channel.
+//!
+//! The dealer encrypts each share to the recipient's long-lived encryption
+//! key, a [`VerificationKey`] on the curve of the signature type, with an
+//! ephemeral Diffie-Hellman key exchange and ChaCha20-Poly1305. The dealer's
+//! commitment to the sharing polynomial is sent in the clear next to the
+//! ciphertext and is authenticated as associated data, so a share cannot be
+//! moved to another commitment without the recipient noticing.
+//!
+//! Anyone can check with [`EncryptedSharePackage::verify`] that the
+//! commitment of an encrypted package is the one behind the group's
+//! [`PublicKeyPackage`]. Since the commitment is fixed by the public keys of
+//! the signers, every package that passes this check carries the same
+//! commitment. The recipient then checks, in
+//! [`EncryptedSharePackage::decrypt`], that the decrypted share matches it.
+//! The ciphertext itself does not prove to third parties that it contains a
+//! valid share: a recipient whose share fails to decrypt or verify must raise
+//! that through the ceremony.
+//!
+//! An encrypted package is laid out as:
+//!
+//! | Bytes    | Contents                                          |
+//! |----------|---------------------------------------------------|
+//! | 8        | Participant index, little-endian                  |
+//! | 32       | Group public key                                  |
+//! | 1        | Number of dealer commitments                      |
+//! | 32 each  | Dealer commitments                                |
+//! | 32       | The ephemeral point \\(e \cdot B\\)               |
+//! | 48       | Encrypted secret share and authentication tag     |
+//!
+//! The key is the BLAKE2b-256 hash, personalized with `"reddsa_FROSTShrE"`,
+//! of the signature scheme's hash personalization, the ephemeral point and
+//! the shared secret \\(e \cdot PK_{recipient}\\). Every package has its own
+//! key, so the nonce is always zero. The associated data is the signature
+//! scheme's hash personalization, the recipient's encryption key, and every
+//! field before the ciphertext.
+
+use alloc::vec::Vec;
+use std::convert::{TryFrom, TryInto};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use group::{
+    cofactor::CofactorGroup,
+    ff::{Field, PrimeField},
+    Curve, Group, GroupEncoding,
+};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use super::{
+    verify_share, Commitment, Error, Public, PublicKeyPackage, Secret, Share, ShareCommitment,
+    SharePackage,
+};
+use crate::{
+    private::SealedScalar, signing_key::SecretScalar, SigningKey, SpendAuth, VerificationKey,
+};
+
+/// The length of the encrypted secret share and its authentication tag.
+const CIPHERTEXT_LEN: usize = 32 + 16;
+
+/// A [`SharePackage`] encrypted to its recipient by the dealer.
+#[derive(Clone)]
+pub struct EncryptedSharePackage<S: SpendAuth> {
+    index: u64,
+    group_public: VerificationKey<S>,
+    commitment: ShareCommitment<S>,
+    ephemeral_point: [u8; 32],
+    ciphertext: [u8; CIPHERTEXT_LEN],
+}
+
+impl<S: SpendAuth> SharePackage<S> {
+    /// Encrypts this share package to `recipient`, the encryption key of the
+    /// participant it belongs to.
+    ///
+    /// The recipient's key must be authenticated out of band, for example
+    /// when the participants enrol in the ceremony. Returns
+    /// [`Error::InvalidEncryptionKey`] if it is the identity or of small order,
+    /// which would make the shared secret predictable.
+    pub fn encrypt_to<R: RngCore + CryptoRng>(
+        &self,
+        recipient: &VerificationKey<S>,
+        mut rng: R,
+    ) -> Result<EncryptedSharePackage<S>, Error> {
+        if recipient.point.is_small_order().into() {
+            return Err(Error::InvalidEncryptionKey);
+        }
+
+        let mut bytes = [0; 64];
+        rng.fill_bytes(&mut bytes);
+        let mut ephemeral = SecretScalar(S::Scalar::from_bytes_wide(&bytes));
+        bytes.zeroize();
+        let ephemeral_point = S::basepoint_mul(&ephemeral.0)
+            .to_bytes()
+            .as_ref()
+            .try_into()
+            .unwrap();
+        let shared_secret = recipient.point * ephemeral.0;
+        ephemeral.zeroize();
+
+        let mut encrypted = EncryptedSharePackage {
+            index: self.index,
+            group_public: self.group_public,
+            commitment: self.share.commitment.clone(),
+            ephemeral_point,
+            ciphertext: [0; CIPHERTEXT_LEN],
+        };
+
+        let cipher = encrypted.cipher(shared_secret);
+        let mut plaintext = self.share.value.0.to_repr();
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&[0; 12]),
+                Payload {
+                    msg: plaintext.as_ref(),
+                    aad: &encrypted.associated_data(recipient),
+                },
+            )
+            .expect("encrypting into a Vec never fails");
+        plaintext.as_mut().zeroize();
+        encrypted.ciphertext.copy_from_slice(&ciphertext);
+
+        Ok(encrypted)
+    }
+}
+
+impl<S: SpendAuth> EncryptedSharePackage<S> {
+    /// The participant index of the recipient.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Checks that the dealer's commitment in this package is the one behind
+    /// `pubkeys`: that it commits to the group public key, and to the public
+    /// key of every signer.
+    ///
+    /// This only uses public values, so anyone observing the broadcast
+    /// channel can run it. Returns [`Error::UnknownSigner`] if the recipient
+    /// is not part of the group, [`Error::DifferentGroups`] if the package is
+    /// for another group key, and [`Error::InvalidShare`] if the commitment
+    /// does not match the public keys.
+    pub fn verify(&self, pubkeys: &PublicKeyPackage<S>) -> Result<(), Error> {
+        if !pubkeys.signer_pubkeys.contains_key(&self.index) {
+            return Err(Error::UnknownSigner);
+        }
+        if self.group_public != pubkeys.group_public {
+            return Err(Error::DifferentGroups);
+        }
+
+        let commitments = &self.commitment.0;
+        if commitments.len() != pubkeys.threshold as usize
+            || commitments[0].0 != pubkeys.group_public.to_affine()
+        {
+            return Err(Error::InvalidShare);
+        }
+        for (index, public) in pubkeys.signer_pubkeys.iter() {
+            let x = S::Scalar::from(*index);
+            let (_, result) = commitments.iter().fold(
+                (S::Scalar::one(), S::Point::identity()),
+                |(x_to_the_i, sum_so_far), comm_i| {
+                    (x_to_the_i * x, sum_so_far + comm_i.0 * x_to_the_i)
+                },
+            );
+            if result != public.0 {
+                return Err(Error::InvalidShare);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decrypts this package with `identity`, the recipient's encryption key,
+    /// and checks the share against the dealer's commitment.
+    ///
+    /// Returns [`Error::ShareDecryptionFailed`] if the package was not
+    /// encrypted to `identity` or was modified, and [`Error::InvalidShare`] if
+    /// the share does not match the dealer's commitment.
+    ///
+    /// An ephemeral point outside of the prime-order subgroup is rejected:
+    /// otherwise a crafted package would decrypt or not depending on the
+    /// secret key modulo the cofactor, which leaks those bits of the key.
+    pub fn decrypt(&self, identity: &SigningKey<S>) -> Result<SharePackage<S>, Error> {
+        let mut repr = <S::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&self.ephemeral_point);
+        let ephemeral_point: S::Point = Option::from(S::Point::from_bytes(&repr))
+            .filter(|point: &S::Point| {
+                !bool::from(point.is_identity()) && bool::from(point.is_torsion_free())
+            })
+            .ok_or(Error::ShareDecryptionFailed)?;
+
+        let cipher = self.cipher(ephemeral_point * identity.sk.0);
+        let mut plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&[0; 12]),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &self.associated_data(&identity.pk),
+                },
+            )
+            .map_err(|_| Error::ShareDecryptionFailed)?;
+
+        let mut repr = <S::Scalar as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(&plaintext);
+        plaintext.zeroize();
+        let maybe_scalar = S::Scalar::from_repr(repr);
+        repr.as_mut().zeroize();
+        if maybe_scalar.is_none().into() {
+            return Err(Error::InvalidShare);
+        }
+        let share = Share {
+            receiver_index: self.index,
+            value: Secret(maybe_scalar.unwrap()),
+            commitment: self.commitment.clone(),
+        };
+        verify_share(&share)?;
+
+        Ok(SharePackage {
+            group_public: self.group_public,
+            index: self.index,
+            public: Public(S::basepoint() * share.value.0),
+            share,
+        })
+    }
+
+    /// Encodes this package for sending it over the broadcast channel.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header();
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Decodes a package encoded with [`EncryptedSharePackage::to_bytes`].
+    ///
+    /// Returns [`Error::MalformedEncryptedShare`] if the bytes are not an
+    /// encoded package.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 8 + 32 + 1 + 32 + CIPHERTEXT_LEN {
+            return Err(Error::MalformedEncryptedShare);
+        }
+        let index = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let group_public = VerificationKey::try_from(read_32(&bytes[8..40]))
+            .map_err(|_| Error::MalformedEncryptedShare)?;
+
+        let num_commitments = bytes[40] as usize;
+        let rest = &bytes[41..];
+        if num_commitments == 0 || rest.len() != 32 * num_commitments + 32 + CIPHERTEXT_LEN {
+            return Err(Error::MalformedEncryptedShare);
+        }
+        let (commitment_bytes, rest) = rest.split_at(32 * num_commitments);
+        let mut commitments = Vec::with_capacity(num_commitments);
+        for chunk in commitment_bytes.chunks(32) {
+            let mut repr = <S::Point as GroupEncoding>::Repr::default();
+            repr.as_mut().copy_from_slice(chunk);
+            let maybe_point = S::Point::from_bytes(&repr);
+            if maybe_point.is_none().into() {
+                return Err(Error::MalformedEncryptedShare);
+            }
+            commitments.push(Commitment(maybe_point.unwrap().to_affine()));
+        }
+
+        let (ephemeral_point, ciphertext) = rest.split_at(32);
+        Ok(EncryptedSharePackage {
+            index,
+            group_public,
+            commitment: ShareCommitment(commitments),
+            ephemeral_point: read_32(ephemeral_point),
+            ciphertext: ciphertext.try_into().unwrap(),
+        })
+    }
+
+    /// Encodes every field before the ciphertext.
+    fn header(&self) -> Vec<u8> {
+        let commitments = &self.commitment.0;
+        let mut bytes = Vec::with_capacity(8 + 32 + 1 + 32 * commitments.len() + 32);
+        bytes.extend_from_slice(&self.index.to_le_bytes());
+        bytes.extend_from_slice(&<[u8; 32]>::from(self.group_public));
+        bytes.push(commitments.len() as u8);
+        for commitment in commitments {
+            bytes.extend_from_slice(commitment.0.to_bytes().as_ref());
+        }
+        bytes.extend_from_slice(&self.ephemeral_point);
+        bytes
+    }
+
+    fn associated_data(&self, recipient: &VerificationKey<S>) -> Vec<u8> {
+        let mut aad = Vec::new();
+        aad.extend_from_slice(S::H_STAR_PERSONALIZATION);
+        aad.extend_from_slice(&<[u8; 32]>::from(*recipient));
+        aad.extend(self.header());
+        aad
+    }
+
+    /// Derives the cipher for this package from the shared secret
+    /// \\(e \cdot PK_{recipient}\\).
+    fn cipher(&self, shared_secret: S::Point) -> ChaCha20Poly1305 {
+        let mut shared_secret = shared_secret.to_bytes();
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"reddsa_FROSTShrE")
+            .to_state()
+            .update(S::H_STAR_PERSONALIZATION)
+            .update(&self.ephemeral_point)
+            .update(shared_secret.as_ref())
+            .finalize();
+        shared_secret.as_mut().zeroize();
+
+        let mut key: [u8; 32] = hash.as_bytes().try_into().unwrap();
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        key.zeroize();
+        cipher
+    }
+}
+
+fn read_32(bytes: &[u8]) -> [u8; 32] {
+    bytes.try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use jubjub::{AffinePoint, ExtendedPoint, Fq};
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{frost::keygen_with_dealer, sapling};
+
+    #[test]
+    fn ephemeral_points_with_torsion_are_rejected() {
+        let mut rng = thread_rng();
+        let (shares, _) = keygen_with_dealer::<_, sapling::SpendAuth>(2, 2, &mut rng).unwrap();
+
+        // A recipient key that is a multiple of 4 maps the points of order 4
+        // to the identity, so adding one to the ephemeral point does not
+        // change the shared secret.
+        let identity = SigningKey::<sapling::SpendAuth>::from_scalar(jubjub::Scalar::from(4 * 7));
+        let recipient = identity.pk;
+        let mut encrypted = shares[0].encrypt_to(&recipient, &mut rng).unwrap();
+        let ephemeral_point = ExtendedPoint::from_bytes(&encrypted.ephemeral_point).unwrap();
+        // The encoding of (1, 0) decodes to a point of order 4, with y = 0.
+        let order4 = AffinePoint::from_raw_unchecked(Fq::one(), Fq::zero()).to_bytes();
+        let order4 = ExtendedPoint::from_bytes(&order4).unwrap();
+        encrypted.ephemeral_point = (ephemeral_point + order4).to_bytes();
+
+        // Encrypt the share again under the key the modified package derives,
+        // as a dealer probing the recipient's key would.
+        let cipher = encrypted.cipher(ephemeral_point * identity.sk.0);
+        let plaintext = shares[0].share.value.0.to_repr();
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&[0; 12]),
+                Payload {
+                    msg: plaintext.as_ref(),
+                    aad: &encrypted.associated_data(&recipient),
+                },
+            )
+            .unwrap();
+        encrypted.ciphertext.copy_from_slice(&ciphertext);
+
+        assert_eq!(
+            encrypted.decrypt(&identity).err(),
+            Some(Error::ShareDecryptionFailed)
+        );
+    }
+}
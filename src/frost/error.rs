@@ -0,0 +1,189 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+use core::fmt;
+
+/// An error related to FROST key generation or signing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Error {
+    /// The encoding of a public key was malformed.
+    MalformedPublicKey,
+    /// A secret share does not match the dealer's commitment to it.
+    InvalidShare,
+    /// The threshold was 0.
+    ZeroThreshold,
+    /// The number of shares was 0.
+    ZeroShares,
+    /// The threshold was greater than the number of shares.
+    ThresholdExceedsShares,
+    /// No shares were provided.
+    NoShares,
+    /// The shares were generated for different groups.
+    DifferentGroups,
+    /// The same share, or participant index, was provided more than once.
+    DuplicateShares,
+    /// The reconstructed signing key does not match the group public key.
+    ReconstructionMismatch,
+    /// A signing commitment was the identity element.
+    IdentityCommitment,
+    /// The signing package contains more than one commitment for a
+    /// participant.
+    DuplicateCommitment,
+    /// The signing package contains fewer commitments than the threshold.
+    NotEnoughCommitments,
+    /// The signing package has no commitment for a participant.
+    MissingCommitment,
+    /// No binding factor was computed for a participant.
+    MissingBinding,
+    /// The participant is not part of the group.
+    UnknownSigner,
+    /// A signature share failed verification.
+    InvalidSignatureShare,
+    /// The signing package belongs to a different signing session than the
+    /// one the signer expected.
+    SessionMismatch,
+    /// The signing package does not include the signer, or not with the
+    /// signer's current commitments.
+    CommitmentMismatch,
+    /// The number of nonces does not match the number of shares.
+    NonceCountMismatch,
+    /// A participant's weight was 0.
+    ZeroWeight,
+    /// The total weight of all participants exceeds 255.
+    TotalWeightTooLarge,
+    /// The signer was previously identified as malicious.
+    MaliciousSigner,
+    /// The signer is already waiting for a signing session.
+    SignerAlreadyWaiting,
+    /// Too many signers were identified as malicious to produce a signature.
+    TooManyMaliciousSigners,
+    /// A signature share arrived after its signing session expired.
+    SessionExpired,
+    /// A signature share arrived for a signing session that was cancelled.
+    SessionCancelled,
+    /// The coordinator did not start a signing session with this ID.
+    UnknownSession,
+    /// The signer's signing policy refused to sign the request.
+    PolicyRejected,
+    /// An encrypted share package was malformed.
+    MalformedEncryptedShare,
+    /// An encrypted share package could not be decrypted, because it was
+    /// encrypted to another key or was modified.
+    ShareDecryptionFailed,
+    /// An encryption key was the identity or a point of small order.
+    InvalidEncryptionKey,
+    /// A message or echo of a broadcast round is missing, or an echo does
+    /// not cover every participant.
+    IncompleteBroadcast,
+    /// A participant sent different messages to different peers in a
+    /// broadcast round.
+    EquivocationDetected,
+    /// A snapshot of a coordinator or signer was malformed.
+    MalformedSnapshot,
+    /// An exported audit log was malformed.
+    MalformedAuditLog,
+    /// An entry of an audit log does not follow from the entries before it.
+    BrokenAuditChain,
+    /// An encrypted share backup was malformed.
+    MalformedBackup,
+    /// An encrypted share backup uses an unknown format version.
+    UnsupportedBackupVersion,
+    /// An encrypted share backup could not be decrypted, because the
+    /// passphrase was wrong or the backup was modified.
+    BackupDecryptionFailed,
+    /// A share mnemonic had the wrong number of words, or a word outside the
+    /// wordlist.
+    MalformedMnemonic,
+    /// The checksum of a share mnemonic did not match.
+    MnemonicChecksumMismatch,
+    /// A share mnemonic uses an unknown format version.
+    UnsupportedMnemonicVersion,
+    /// The encoding of signing nonces was malformed, or a nonce was 0.
+    MalformedNonces,
+    /// A share package or public key package in the legacy `redjubjub`
+    /// encoding was malformed.
+    MalformedLegacyEncoding,
+    /// The signers' public keys do not interpolate to the group public key.
+    InconsistentPublicKeys,
+    /// The aggregated signature failed verification against the group public
+    /// key.
+    InvalidSignature,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedPublicKey => write!(f, "Malformed public key."),
+            Self::InvalidShare => write!(f, "Share is invalid."),
+            Self::ZeroThreshold => write!(f, "Threshold cannot be 0."),
+            Self::ZeroShares => write!(f, "Number of shares cannot be 0."),
+            Self::ThresholdExceedsShares => write!(f, "Threshold cannot exceed numshares."),
+            Self::NoShares => write!(f, "No shares provided."),
+            Self::DifferentGroups => write!(f, "Shares belong to different groups."),
+            Self::DuplicateShares => write!(f, "Duplicate shares provided."),
+            Self::ReconstructionMismatch => {
+                write!(f, "Reconstructed key does not match the group public key.")
+            }
+            Self::IdentityCommitment => write!(f, "Commitment equals the identity."),
+            Self::DuplicateCommitment => write!(f, "Duplicate signing commitments provided."),
+            Self::NotEnoughCommitments => {
+                write!(f, "Fewer signing commitments than the threshold.")
+            }
+            Self::MissingCommitment => write!(f, "No matching signing commitment for signer."),
+            Self::MissingBinding => write!(f, "No matching binding."),
+            Self::UnknownSigner => write!(f, "Unknown signer."),
+            Self::InvalidSignatureShare => write!(f, "Invalid signature share."),
+            Self::SessionMismatch => write!(f, "Signing package is for a different session."),
+            Self::CommitmentMismatch => {
+                write!(
+                    f,
+                    "Signing package does not match this signer's commitments."
+                )
+            }
+            Self::NonceCountMismatch => write!(f, "Expected one set of nonces per share."),
+            Self::ZeroWeight => write!(f, "Participant weight cannot be 0."),
+            Self::TotalWeightTooLarge => write!(f, "Total weight cannot exceed 255."),
+            Self::MaliciousSigner => {
+                write!(f, "Signer was previously identified as malicious.")
+            }
+            Self::SignerAlreadyWaiting => {
+                write!(f, "Signer is already waiting for a signing session.")
+            }
+            Self::TooManyMaliciousSigners => {
+                write!(f, "Too many malicious signers to produce a signature.")
+            }
+            Self::SessionExpired => write!(f, "Signing session has expired."),
+            Self::SessionCancelled => write!(f, "Signing session was cancelled."),
+            Self::UnknownSession => write!(f, "Unknown signing session."),
+            Self::PolicyRejected => write!(f, "Signing policy refused the request."),
+            Self::MalformedEncryptedShare => write!(f, "Malformed encrypted share package."),
+            Self::ShareDecryptionFailed => write!(f, "Could not decrypt share package."),
+            Self::InvalidEncryptionKey => write!(f, "Encryption key is of small order."),
+            Self::IncompleteBroadcast => write!(f, "Broadcast round is incomplete."),
+            Self::EquivocationDetected => write!(f, "Participant equivocated in broadcast round."),
+            Self::MalformedSnapshot => write!(f, "Malformed state snapshot."),
+            Self::MalformedAuditLog => write!(f, "Malformed audit log."),
+            Self::BrokenAuditChain => write!(f, "Audit log hash chain is broken."),
+            Self::MalformedBackup => write!(f, "Malformed share backup."),
+            Self::UnsupportedBackupVersion => write!(f, "Unsupported share backup version."),
+            Self::BackupDecryptionFailed => write!(f, "Could not decrypt share backup."),
+            Self::MalformedMnemonic => write!(f, "Malformed share mnemonic."),
+            Self::MnemonicChecksumMismatch => write!(f, "Share mnemonic checksum mismatch."),
+            Self::UnsupportedMnemonicVersion => {
+                write!(f, "Unsupported share mnemonic version.")
+            }
+            Self::MalformedNonces => write!(f, "Malformed signing nonces."),
+            Self::MalformedLegacyEncoding => write!(f, "Malformed legacy redjubjub encoding."),
+            Self::InconsistentPublicKeys => {
+                write!(f, "Signer public keys do not match the group public key.")
+            }
+            Self::InvalidSignature => write!(f, "Aggregated signature is invalid."),
+        }
+    }
+}
@@ -0,0 +1,347 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Decoders for the FROST key material of deployments that used the FROST
+//! implementation of the `redjubjub` crate, before it moved to this crate.
+//!
+//! Those deployments stored [`SharePackage`]s and [`PublicKeyPackage`]s as the
+//! `bincode` encoding (fixed-width little-endian integers) of the fields of the
+//! `redjubjub` types, in declaration order:
+//!
+//! | Share package            | Encoding                                  |
+//! |--------------------------|-------------------------------------------|
+//! | `group_public`           | 32 bytes                                  |
+//! | `index`                  | `u64`                                     |
+//! | `public`                 | 32 bytes                                  |
+//! | `share.receiver_index`   | `u64`                                     |
+//! | `share.value`            | 32 bytes                                  |
+//! | `share.commitment`       | `u64` length, then 32 bytes per commitment|
+//!
+//! | Public key package       | Encoding                                  |
+//! |--------------------------|-------------------------------------------|
+//! | `signer_pubkeys`         | `u64` length, then a `u64` index and 32 bytes per signer |
+//! | `group_public`           | 32 bytes                                  |
+//!
+//! All of the keys are RedJubjub `SpendAuth` keys. The decoded packages are
+//! checked against their group public key before they are returned.
+
+use alloc::vec::Vec;
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use bincode::Options;
+use serde::Deserialize;
+
+use super::{
+    lagrange_coeff_at_zero, verify_share, Commitment, Error, Public, PublicKeyPackage, Secret,
+    Share, ShareCommitment, SharePackage,
+};
+use crate::{private::Sealed, sapling, VerificationKey};
+
+#[derive(Deserialize)]
+struct LegacyShare {
+    receiver_index: u64,
+    value: [u8; 32],
+    commitment: Vec<[u8; 32]>,
+}
+
+#[derive(Deserialize)]
+struct LegacySharePackage {
+    group_public: [u8; 32],
+    index: u64,
+    public: [u8; 32],
+    share: LegacyShare,
+}
+
+#[derive(Deserialize)]
+struct LegacyPublicKeyPackage {
+    signer_pubkeys: BTreeMap<u64, [u8; 32]>,
+    group_public: [u8; 32],
+}
+
+fn deserialize<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, Error> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+        .map_err(|_| Error::MalformedLegacyEncoding)
+}
+
+fn group_public(bytes: [u8; 32]) -> Result<VerificationKey<sapling::SpendAuth>, Error> {
+    VerificationKey::try_from(bytes).map_err(|_| Error::MalformedPublicKey)
+}
+
+/// Decodes a share package written by the `redjubjub` crate's FROST
+/// implementation.
+///
+/// The share is verified against the dealer's commitment, the commitment to
+/// the constant term must be the group public key, and the participant's
+/// public key must match the share.
+pub fn share_package_from_legacy(bytes: &[u8]) -> Result<SharePackage<sapling::SpendAuth>, Error> {
+    let legacy: LegacySharePackage = deserialize(bytes)?;
+
+    let group_public = group_public(legacy.group_public)?;
+    let public = Public::try_from(legacy.public)?;
+    let value = Option::from(jubjub::Scalar::from_bytes(&legacy.share.value))
+        .ok_or(Error::MalformedLegacyEncoding)?;
+    let commitments = legacy
+        .share
+        .commitment
+        .iter()
+        .map(|bytes| {
+            Option::from(jubjub::AffinePoint::from_bytes(*bytes))
+                .map(Commitment::<sapling::SpendAuth>)
+                .ok_or(Error::MalformedPublicKey)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if legacy.index != legacy.share.receiver_index {
+        return Err(Error::InvalidShare);
+    }
+    // The commitment to the constant term of the dealer's polynomial is the
+    // group public key.
+    if commitments.first().map(|commitment| commitment.0)
+        != Some(jubjub::AffinePoint::from(group_public.point))
+    {
+        return Err(Error::InvalidShare);
+    }
+
+    let share = Share {
+        receiver_index: legacy.index,
+        value: Secret(value),
+        commitment: ShareCommitment(commitments),
+    };
+    verify_share(&share)?;
+    if public != Public(sapling::SpendAuth::basepoint_mul(&value)) {
+        return Err(Error::InvalidShare);
+    }
+
+    Ok(SharePackage {
+        group_public,
+        index: legacy.index,
+        public,
+        share,
+    })
+}
+
+/// Decodes a public key package written by the `redjubjub` crate's FROST
+/// implementation, which did not record the group's threshold.
+///
+/// The public keys of every `threshold` consecutive signers, ordered by index,
+/// must interpolate to the group public key. This ensures that all of the
+/// public keys lie on a single polynomial of degree `threshold - 1` whose
+/// constant term is the group public key.
+pub fn public_key_package_from_legacy(
+    bytes: &[u8],
+    threshold: u8,
+) -> Result<PublicKeyPackage<sapling::SpendAuth>, Error> {
+    let legacy: LegacyPublicKeyPackage = deserialize(bytes)?;
+
+    let group_public = group_public(legacy.group_public)?;
+    let mut signers = Vec::with_capacity(legacy.signer_pubkeys.len());
+    for (index, public) in legacy.signer_pubkeys {
+        if index == 0 {
+            return Err(Error::MalformedLegacyEncoding);
+        }
+        signers.push((index, Public::try_from(public)?));
+    }
+
+    if threshold >= 1 {
+        for window in signers.windows(threshold as usize) {
+            let indices = window.iter().map(|(index, _)| *index);
+            let mut interpolated = jubjub::ExtendedPoint::identity();
+            for (index, public) in window {
+                let lambda =
+                    lagrange_coeff_at_zero::<sapling::SpendAuth, _>(*index, indices.clone())?;
+                interpolated += public.0 * lambda;
+            }
+            if interpolated != group_public.point {
+                return Err(Error::InconsistentPublicKeys);
+            }
+        }
+    }
+
+    let signer_pubkeys: BTreeMap<u64, Public<sapling::SpendAuth>> = signers.into_iter().collect();
+    PublicKeyPackage::new(signer_pubkeys, group_public, threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{ff::PrimeField, Group};
+    use rand::thread_rng;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::frost::keygen_with_dealer;
+
+    /// The share package of participant 2 of a 2-of-3 group, in the
+    /// `redjubjub` layout documented above, computed independently of this
+    /// crate from a fixed secret and polynomial coefficient.
+    const SHARE_PACKAGE: [u8; 184] = [
+        0x45, 0x63, 0x82, 0x63, 0x76, 0x9f, 0x50, 0x9b, 0x92, 0x8c, 0x28, 0x89, 0xaa, 0x5c, 0x93,
+        0xd9, 0xf7, 0xb0, 0x4f, 0x60, 0x0d, 0xbd, 0xa8, 0x34, 0x19, 0x87, 0x51, 0xc5, 0xfa, 0x46,
+        0xf0, 0x0b, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xd2, 0x3d, 0x40, 0xa2, 0x96,
+        0x56, 0xc6, 0xa4, 0xb5, 0x4d, 0x46, 0x0b, 0xad, 0xe9, 0x61, 0x1a, 0x2d, 0x42, 0xb7, 0x0f,
+        0x65, 0xcd, 0x49, 0xee, 0x45, 0xc0, 0xce, 0x23, 0x00, 0x73, 0x08, 0x81, 0x02, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0xc6, 0x55, 0x02, 0x7c, 0xff, 0x70, 0xfc, 0xa9, 0x5a, 0xdb,
+        0xe5, 0x44, 0xc4, 0x21, 0x3e, 0x27, 0xc0, 0xe9, 0x17, 0xf8, 0x6d, 0x8b, 0x67, 0xe0, 0x20,
+        0xde, 0xc9, 0x57, 0x6e, 0xa8, 0xf9, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x45, 0x63, 0x82, 0x63, 0x76, 0x9f, 0x50, 0x9b, 0x92, 0x8c, 0x28, 0x89, 0xaa, 0x5c, 0x93,
+        0xd9, 0xf7, 0xb0, 0x4f, 0x60, 0x0d, 0xbd, 0xa8, 0x34, 0x19, 0x87, 0x51, 0xc5, 0xfa, 0x46,
+        0xf0, 0x0b, 0x1a, 0x73, 0xfc, 0x8f, 0x6d, 0x0c, 0x2a, 0x81, 0x4c, 0xb4, 0xa5, 0x7c, 0x67,
+        0xc9, 0xe1, 0xf4, 0xf4, 0x03, 0x86, 0x15, 0xc1, 0x09, 0x0e, 0x8d, 0x15, 0x85, 0xe1, 0x3e,
+        0xae, 0xe4, 0x18, 0xc8,
+    ];
+
+    /// The public key package of the same group.
+    const PUBLIC_KEY_PACKAGE: [u8; 160] = [
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x15, 0xf8, 0x3f, 0x39, 0x63, 0xcf, 0xdc, 0xe7, 0x84, 0x56, 0x6d, 0xf4, 0xdb, 0xfa,
+        0x70, 0x0a, 0xbc, 0xf7, 0x3d, 0x6d, 0x1c, 0x1d, 0xd8, 0xa4, 0x85, 0x8c, 0x25, 0xda, 0x57,
+        0xcd, 0xd8, 0x2c, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xd2, 0x3d, 0x40, 0xa2,
+        0x96, 0x56, 0xc6, 0xa4, 0xb5, 0x4d, 0x46, 0x0b, 0xad, 0xe9, 0x61, 0x1a, 0x2d, 0x42, 0xb7,
+        0x0f, 0x65, 0xcd, 0x49, 0xee, 0x45, 0xc0, 0xce, 0x23, 0x00, 0x73, 0x08, 0x81, 0x03, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xd4, 0xad, 0x9f, 0xf2, 0x39, 0x0e, 0x4a, 0x8a, 0x81,
+        0x20, 0x18, 0xee, 0x42, 0x72, 0xa5, 0x55, 0xc5, 0xf0, 0x74, 0x28, 0x24, 0xdb, 0x83, 0x2b,
+        0xcc, 0x6f, 0xbe, 0x1a, 0x32, 0x90, 0xb7, 0x3a, 0x45, 0x63, 0x82, 0x63, 0x76, 0x9f, 0x50,
+        0x9b, 0x92, 0x8c, 0x28, 0x89, 0xaa, 0x5c, 0x93, 0xd9, 0xf7, 0xb0, 0x4f, 0x60, 0x0d, 0xbd,
+        0xa8, 0x34, 0x19, 0x87, 0x51, 0xc5, 0xfa, 0x46, 0xf0, 0x0b,
+    ];
+
+    /// The group public key, the dealer's commitment to the secret.
+    const GROUP_PUBLIC: [u8; 32] = [
+        0x45, 0x63, 0x82, 0x63, 0x76, 0x9f, 0x50, 0x9b, 0x92, 0x8c, 0x28, 0x89, 0xaa, 0x5c, 0x93,
+        0xd9, 0xf7, 0xb0, 0x4f, 0x60, 0x0d, 0xbd, 0xa8, 0x34, 0x19, 0x87, 0x51, 0xc5, 0xfa, 0x46,
+        0xf0, 0x0b,
+    ];
+
+    /// The public key of participant 2.
+    const PUBLIC_2: [u8; 32] = [
+        0xd2, 0x3d, 0x40, 0xa2, 0x96, 0x56, 0xc6, 0xa4, 0xb5, 0x4d, 0x46, 0x0b, 0xad, 0xe9, 0x61,
+        0x1a, 0x2d, 0x42, 0xb7, 0x0f, 0x65, 0xcd, 0x49, 0xee, 0x45, 0xc0, 0xce, 0x23, 0x00, 0x73,
+        0x08, 0x81,
+    ];
+
+    #[derive(Serialize)]
+    struct EncodeShare<'a> {
+        group_public: [u8; 32],
+        index: u64,
+        public: [u8; 32],
+        receiver_index: u64,
+        value: [u8; 32],
+        commitment: &'a [[u8; 32]],
+    }
+
+    #[derive(Serialize)]
+    struct EncodePublicKeys {
+        signer_pubkeys: BTreeMap<u64, [u8; 32]>,
+        group_public: [u8; 32],
+    }
+
+    fn encode_share(share_package: &SharePackage<sapling::SpendAuth>, value: [u8; 32]) -> Vec<u8> {
+        let commitment: Vec<[u8; 32]> = share_package
+            .share
+            .commitment
+            .0
+            .iter()
+            .map(|commitment| commitment.0.to_bytes())
+            .collect();
+        bincode::serialize(&EncodeShare {
+            group_public: share_package.group_public.into(),
+            index: share_package.index,
+            public: share_package.public.into(),
+            receiver_index: share_package.share.receiver_index,
+            value,
+            commitment: &commitment,
+        })
+        .unwrap()
+    }
+
+    fn encode_public_keys(package: &PublicKeyPackage<sapling::SpendAuth>) -> Vec<u8> {
+        bincode::serialize(&EncodePublicKeys {
+            signer_pubkeys: package
+                .signer_pubkeys()
+                .map(|(index, public)| (index, (*public).into()))
+                .collect(),
+            group_public: package.group_public.into(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_legacy_packages() {
+        let (shares, pubkeys) = keygen_with_dealer(5, 3, thread_rng()).unwrap();
+
+        for share_package in shares.iter() {
+            let bytes = encode_share(share_package, share_package.share.value.0.to_repr());
+            let decoded = share_package_from_legacy(&bytes).unwrap();
+            assert_eq!(decoded.index, share_package.index);
+            assert_eq!(decoded.public, share_package.public);
+            assert_eq!(decoded.group_public, share_package.group_public);
+        }
+
+        let decoded = public_key_package_from_legacy(&encode_public_keys(&pubkeys), 3).unwrap();
+        assert_eq!(decoded.group_public, pubkeys.group_public);
+        for (index, public) in pubkeys.signer_pubkeys() {
+            assert_eq!(decoded.signer_pubkey(index), Some(public));
+        }
+    }
+
+    #[test]
+    fn decode_legacy_fixture() {
+        let share_package = share_package_from_legacy(&SHARE_PACKAGE).unwrap();
+        assert_eq!(share_package.index, 2);
+        assert_eq!(<[u8; 32]>::from(share_package.group_public), GROUP_PUBLIC);
+        assert_eq!(<[u8; 32]>::from(share_package.public), PUBLIC_2);
+        assert_eq!(share_package.share.commitment.0.len(), 2);
+        assert_eq!(
+            share_package.share.commitment.0[0].0.to_bytes(),
+            GROUP_PUBLIC
+        );
+
+        let pubkeys = public_key_package_from_legacy(&PUBLIC_KEY_PACKAGE, 2).unwrap();
+        assert_eq!(<[u8; 32]>::from(pubkeys.group_public), GROUP_PUBLIC);
+        assert_eq!(pubkeys.signer_pubkeys().count(), 3);
+        assert_eq!(pubkeys.signer_pubkey(2), Some(&share_package.public));
+        for (index, public) in pubkeys.signer_pubkeys() {
+            let offset = 16 + 40 * (index as usize - 1);
+            assert_eq!(
+                <[u8; 32]>::from(*public)[..],
+                PUBLIC_KEY_PACKAGE[offset..offset + 32]
+            );
+        }
+    }
+
+    #[test]
+    fn reject_inconsistent_legacy_packages() {
+        let (shares, pubkeys) = keygen_with_dealer(5, 3, thread_rng()).unwrap();
+        let (other_shares, _) =
+            keygen_with_dealer::<_, sapling::SpendAuth>(5, 3, thread_rng()).unwrap();
+
+        // A share from a different group.
+        let bytes = encode_share(&shares[0], other_shares[0].share.value.0.to_repr());
+        assert_eq!(
+            share_package_from_legacy(&bytes).err(),
+            Some(Error::InvalidShare)
+        );
+
+        // A signer public key that is not on the group's polynomial.
+        let mut pubkeys = pubkeys;
+        pubkeys
+            .signer_pubkeys
+            .insert(2, Public(jubjub::ExtendedPoint::generator()));
+        assert_eq!(
+            public_key_package_from_legacy(&encode_public_keys(&pubkeys), 3).err(),
+            Some(Error::InconsistentPublicKeys)
+        );
+
+        let mut bytes = encode_share(&shares[0], shares[0].share.value.0.to_repr());
+        bytes.push(0);
+        assert_eq!(
+            share_package_from_legacy(&bytes).err(),
+            Some(Error::MalformedLegacyEncoding)
+        );
+    }
+}
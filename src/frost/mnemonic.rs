@@ -0,0 +1,228 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Encoding of secret shares as mnemonics, for writing them down on paper or
+//! steel.
+//!
+//! In the spirit of [SLIP-39], a share mnemonic carries the participant's
+//! secret share together with enough metadata to catch mistakes when it is
+//! restored: the participant index, the signing threshold, and a short
+//! identifier of the group public key. Each mnemonic is 32 words from the
+//! [BIP-39] English wordlist, each word encoding 11 bits of:
+//!
+//! | Bytes | Contents                                            |
+//! |-------|-----------------------------------------------------|
+//! | 1     | Format version, currently `1`                       |
+//! | 1     | Signing threshold                                   |
+//! | 1     | Participant index                                   |
+//! | 4     | Group identifier, derived from the group public key |
+//! | 32    | Secret share                                        |
+//! | 5     | Checksum over all of the above                      |
+//!
+//! A mnemonic only covers the secret part of a share. To sign with a restored
+//! share, it is combined with the group's [`PublicKeyPackage`] into a
+//! [`KeyPackage`] using [`MnemonicShare::into_key_package`].
+//!
+//! [SLIP-39]: https://github.com/satoshilabs/slips/blob/master/slip-0039.md
+//! [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+
+use alloc::{string::String, vec::Vec};
+use std::convert::TryFrom;
+
+use bip39::Language;
+use group::ff::PrimeField;
+use zeroize::Zeroize;
+
+use super::{Error, KeyPackage, Public, PublicKeyPackage, Secret, SharePackage};
+use crate::{SigType, VerificationKey};
+
+/// The current version of the share mnemonic format.
+const VERSION: u8 = 1;
+
+const PAYLOAD_LEN: usize = 1 + 1 + 1 + 4 + 32;
+const CHECKSUM_LEN: usize = 5;
+const ENCODED_LEN: usize = PAYLOAD_LEN + CHECKSUM_LEN;
+
+/// The number of words in a share mnemonic.
+pub const MNEMONIC_WORDS: usize = ENCODED_LEN * 8 / 11;
+
+/// A secret share restored from a mnemonic, along with its metadata.
+pub struct MnemonicShare<S: SigType> {
+    index: u64,
+    threshold: u8,
+    group_id: [u8; 4],
+    secret: Secret<S>,
+}
+
+impl<S: SigType> MnemonicShare<S> {
+    /// The participant index of this share.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// The number of signers needed to produce a signature.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Combines this share with the group's public key package into a
+    /// [`KeyPackage`] for signing.
+    ///
+    /// Returns [`Error::DifferentGroups`] if the mnemonic was made for a
+    /// different group, or with a different threshold, and
+    /// [`Error::InvalidShare`] if the share does not match the participant's
+    /// public key.
+    pub fn into_key_package(self, pubkeys: &PublicKeyPackage<S>) -> Result<KeyPackage<S>, Error> {
+        if self.group_id != group_id(&pubkeys.group_public) || self.threshold != pubkeys.threshold {
+            return Err(Error::DifferentGroups);
+        }
+        let public = pubkeys
+            .signer_pubkey(self.index)
+            .ok_or(Error::UnknownSigner)?;
+        if *public != Public(S::basepoint() * self.secret.0) {
+            return Err(Error::InvalidShare);
+        }
+
+        Ok(KeyPackage {
+            index: self.index,
+            secret_share: self.secret,
+            public: *public,
+            group_public: pubkeys.group_public,
+        })
+    }
+}
+
+impl<S: SigType> SharePackage<S> {
+    /// Encodes the secret share of this package as a mnemonic of
+    /// [`MNEMONIC_WORDS`] space-separated words.
+    ///
+    /// The dealer's commitment is verified first, and
+    /// [`Error::InvalidShare`] is returned if it does not match the share.
+    pub fn to_mnemonic(&self) -> Result<String, Error> {
+        super::verify_share(&self.share)?;
+        let index = u8::try_from(self.index).map_err(|_| Error::UnknownSigner)?;
+
+        let mut encoded = [0u8; ENCODED_LEN];
+        encoded[0] = VERSION;
+        encoded[1] = self.share.commitment.0.len() as u8;
+        encoded[2] = index;
+        encoded[3..7].copy_from_slice(&group_id(&self.group_public));
+        encoded[7..PAYLOAD_LEN].copy_from_slice(self.share.value.0.to_repr().as_ref());
+        let checksum = checksum(&encoded[..PAYLOAD_LEN]);
+        encoded[PAYLOAD_LEN..].copy_from_slice(&checksum);
+
+        let words = Language::English.word_list();
+        let mnemonic = (0..MNEMONIC_WORDS)
+            .map(|i| words[read_bits(&encoded, i * 11) as usize])
+            .collect::<Vec<_>>()
+            .join(" ");
+        encoded.zeroize();
+
+        Ok(mnemonic)
+    }
+}
+
+/// Decodes a share mnemonic produced by [`SharePackage::to_mnemonic`].
+///
+/// Returns [`Error::MalformedMnemonic`] if the mnemonic has the wrong number
+/// of words or contains a word outside the wordlist,
+/// [`Error::MnemonicChecksumMismatch`] if it was written down or typed in
+/// incorrectly, and [`Error::UnsupportedMnemonicVersion`] for mnemonics in an
+/// unknown format version.
+pub fn from_mnemonic<S: SigType>(mnemonic: &str) -> Result<MnemonicShare<S>, Error> {
+    let mut encoded = [0u8; ENCODED_LEN];
+    let mut count = 0;
+    for word in mnemonic.split_whitespace() {
+        if count == MNEMONIC_WORDS {
+            return Err(Error::MalformedMnemonic);
+        }
+        let value = Language::English
+            .find_word(&word.to_lowercase())
+            .ok_or(Error::MalformedMnemonic)?;
+        write_bits(&mut encoded, count * 11, value);
+        count += 1;
+    }
+    if count != MNEMONIC_WORDS {
+        return Err(Error::MalformedMnemonic);
+    }
+
+    let result = decode(&encoded);
+    encoded.zeroize();
+    result
+}
+
+fn decode<S: SigType>(encoded: &[u8; ENCODED_LEN]) -> Result<MnemonicShare<S>, Error> {
+    if encoded[PAYLOAD_LEN..] != checksum(&encoded[..PAYLOAD_LEN]) {
+        return Err(Error::MnemonicChecksumMismatch);
+    }
+    if encoded[0] != VERSION {
+        return Err(Error::UnsupportedMnemonicVersion);
+    }
+
+    let mut repr = <S::Scalar as PrimeField>::Repr::default();
+    repr.as_mut().copy_from_slice(&encoded[7..PAYLOAD_LEN]);
+    let maybe_scalar = S::Scalar::from_repr(repr);
+    repr.as_mut().zeroize();
+    if maybe_scalar.is_none().into() {
+        return Err(Error::MalformedMnemonic);
+    }
+
+    let mut group_id = [0u8; 4];
+    group_id.copy_from_slice(&encoded[3..7]);
+    Ok(MnemonicShare {
+        index: encoded[2] as u64,
+        threshold: encoded[1],
+        group_id,
+        secret: Secret(maybe_scalar.unwrap()),
+    })
+}
+
+/// Identifies the group a share belongs to, so that shares of different
+/// groups are not mixed up when they are restored.
+fn group_id<S: SigType>(group_public: &VerificationKey<S>) -> [u8; 4] {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(4)
+        .personal(b"FROST_MnemonicID")
+        .to_state()
+        .update(S::H_STAR_PERSONALIZATION)
+        .update(&<[u8; 32]>::from(*group_public))
+        .finalize();
+
+    let mut id = [0u8; 4];
+    id.copy_from_slice(hash.as_bytes());
+    id
+}
+
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(CHECKSUM_LEN)
+        .personal(b"FROST_MnemonicCk")
+        .hash(payload);
+
+    let mut checksum = [0u8; CHECKSUM_LEN];
+    checksum.copy_from_slice(hash.as_bytes());
+    checksum
+}
+
+/// Reads the 11 bits starting at bit `offset` of `bytes`, most significant bit
+/// first.
+fn read_bits(bytes: &[u8], offset: usize) -> u16 {
+    (0..11).fold(0, |value, i| {
+        let bit = offset + i;
+        (value << 1) | u16::from((bytes[bit / 8] >> (7 - bit % 8)) & 1)
+    })
+}
+
+/// Writes the low 11 bits of `value` starting at bit `offset` of `bytes`, most
+/// significant bit first.
+fn write_bits(bytes: &mut [u8], offset: usize, value: u16) {
+    for i in 0..11 {
+        let bit = offset + i;
+        if (value >> (10 - i)) & 1 == 1 {
+            bytes[bit / 8] |= 1 << (7 - bit % 8);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Policies that a signer consults before producing a signature share.
+//!
+//! Integrators often need to decide, per request, whether a key may sign at
+//! all: to rate-limit a key, to only sign certain kinds of transactions, or to
+//! wait for a human to approve. A [`SigningPolicy`] is asked to approve every
+//! [`SigningRequest`] before the signer signs it:
+//!
+//! - a ROAST [`Signer`](super::roast::Signer) consults the policy set with
+//!   [`set_policy`](super::roast::Signer::set_policy), after it has checked
+//!   that the request is for its current commitments;
+//! - plain FROST signers call [`policy::sign`](sign) in place of
+//!   [`frost::sign`](super::sign).
+//!
+//! If the policy refuses, [`Error::PolicyRejected`] is returned and no
+//! signature share is produced. The nonces committed to in the refused
+//! signing package are discarded either way, so that commitments which
+//! appeared in a refused package are never signed with later.
+//!
+//! Closures of the form `FnMut(&SigningRequest<S>) -> bool` are policies, so
+//! simple checks need no new type.
+
+use super::{Error, SessionId, SharePackage, SignatureShare, SigningNonces, SigningPackage};
+use crate::SigType;
+
+/// Running counts of the requests a signer has handled.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SigningCounters {
+    /// The number of signature shares the signer has produced.
+    pub signed: u64,
+    /// The number of requests the signer's policy has refused.
+    pub refused: u64,
+}
+
+/// A request to a signer to sign, as seen by its [`SigningPolicy`].
+pub struct SigningRequest<'a, S: SigType> {
+    /// The ID of the ROAST run the signer takes part in, or `None` for plain
+    /// FROST signing.
+    pub run_id: Option<SessionId>,
+    /// The coordinator-local number of the ROAST signing session, or `None`
+    /// for plain FROST signing.
+    pub session_index: Option<usize>,
+    /// The ID of the signing session.
+    pub session_id: SessionId,
+    /// The signing package the signer is asked to sign, with the message and
+    /// the commitments of every selected signer.
+    pub signing_package: &'a SigningPackage<S>,
+    /// The counts of the requests the signer handled before this one.
+    pub counters: SigningCounters,
+}
+
+impl<'a, S: SigType> SigningRequest<'a, S> {
+    /// The message to be signed.
+    pub fn message(&self) -> &'a [u8] {
+        &self.signing_package.message
+    }
+}
+
+/// Decides whether a signer may sign a request.
+pub trait SigningPolicy<S: SigType> {
+    /// Returns whether the signer may produce a signature share for
+    /// `request`.
+    fn approve(&mut self, request: &SigningRequest<'_, S>) -> bool;
+}
+
+impl<S: SigType, F: FnMut(&SigningRequest<'_, S>) -> bool> SigningPolicy<S> for F {
+    fn approve(&mut self, request: &SigningRequest<'_, S>) -> bool {
+        self(request)
+    }
+}
+
+/// Asks `policy` to approve `request`, and counts a refusal in `counters`.
+pub(super) fn approve<S: SigType, P: SigningPolicy<S> + ?Sized>(
+    policy: &mut P,
+    request: &SigningRequest<'_, S>,
+    counters: &mut SigningCounters,
+) -> bool {
+    let approved = policy.approve(request);
+    if !approved {
+        counters.refused += 1;
+    }
+    approved
+}
+
+/// Performed once by each participant selected for the signing operation, as
+/// [`frost::sign`](super::sign), but only if `policy` approves the request.
+///
+/// `counters` holds the counts of the requests the participant handled so far,
+/// which the policy sees and which are updated for this request. If the policy
+/// refuses, [`Error::PolicyRejected`] is returned; `participant_nonces` are
+/// consumed either way, and the participant must send fresh commitments
+/// before it can take part in another signing operation.
+pub fn sign<S: SigType, P: SigningPolicy<S> + ?Sized>(
+    policy: &mut P,
+    counters: &mut SigningCounters,
+    signing_package: &SigningPackage<S>,
+    session_id: SessionId,
+    participant_nonces: SigningNonces<S>,
+    share_package: &SharePackage<S>,
+) -> Result<SignatureShare<S>, Error> {
+    if signing_package.session_id != session_id {
+        return Err(Error::SessionMismatch);
+    }
+
+    let request = SigningRequest {
+        run_id: None,
+        session_index: None,
+        session_id,
+        signing_package,
+        counters: *counters,
+    };
+    if !approve(policy, &request, counters) {
+        return Err(Error::PolicyRejected);
+    }
+
+    let signature_share = super::sign(
+        signing_package,
+        session_id,
+        participant_nonces,
+        share_package,
+    )?;
+    counters.signed += 1;
+
+    Ok(signature_share)
+}
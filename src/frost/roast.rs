@@ -0,0 +1,571 @@
+//! A [ROAST] (Robust Asynchronous Schnorr Threshold) wrapper around FROST
+//! signing.
+//!
+//! Plain FROST only succeeds if every participant selected for a signing
+//! session responds with a valid signature share. ROAST layers a scheduler on
+//! top of it: the [`Coordinator`] starts a new signing session whenever
+//! `threshold` signers are ready, and every signer answers a session request
+//! with its signature share together with a fresh commitment for the next
+//! session it may be asked to join. Signers that submit invalid shares are
+//! excluded, and signers that stall simply never become ready again, so as
+//! long as `threshold` honest signers are responsive a signature is always
+//! produced.
+//!
+//! Every run of ROAST is identified by a [`SessionId`] that the coordinator and
+//! the signers agree on together with the message, and each signing session of
+//! the run gets its own ID derived from it with [`session_id`].
+//!
+//! Sessions can be given a deadline with [`Coordinator::set_session_timeout`],
+//! or be cancelled with [`Coordinator::cancel`]. The coordinator has no clock
+//! of its own, so the caller reports the passing of time with
+//! [`Coordinator::advance_time`]. Shares that arrive for an expired or
+//! cancelled session are rejected with an error, without marking their signer
+//! as malicious, and the signer can rejoin with its next commitments.
+//!
+//! A [`Signer`] can be given a [`SigningPolicy`] that must approve every
+//! request before it signs, to enforce rate limits or human approval. A signer
+//! whose policy refuses a request reports it with [`Coordinator::decline`],
+//! which takes it out of its session without marking it as malicious.
+//!
+//! With the `std` feature, coordinators and signers can be checkpointed with
+//! `snapshot` and resumed with `restore`, so that a restart does not abort
+//! the run.
+//!
+//! [ROAST]: https://eprint.iacr.org/2022/550
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::{convert::TryFrom, time::Duration};
+
+use group::Group;
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(feature = "std")]
+mod snapshot;
+
+use super::{
+    aggregate, gen_challenge, gen_group_commitment, gen_rho_i,
+    policy::{self, SigningCounters, SigningPolicy, SigningRequest},
+    sign, verify_signature_share, Error, PublicKeyPackage, SessionId, SharePackage, SignatureShare,
+    SigningCommitments, SigningNonces, SigningPackage,
+};
+use crate::{SigType, Signature};
+
+/// Derives the [`SessionId`] of the signing session with index
+/// `session_index` in the ROAST run identified by `run_id`.
+pub fn session_id(run_id: SessionId, session_index: usize) -> SessionId {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(32)
+        .personal(b"ROAST_SessionId_")
+        .to_state()
+        .update(&<[u8; 32]>::from(run_id))
+        .update(&(session_index as u64).to_le_bytes())
+        .finalize();
+
+    SessionId::from(<[u8; 32]>::try_from(hash.as_bytes()).unwrap())
+}
+
+/// A request from the [`Coordinator`] to a set of signers to take part in a
+/// new FROST signing session.
+pub struct SessionRequest<S: SigType> {
+    /// The coordinator-local number of this session.
+    pub session_index: usize,
+    /// The participant indices of the signers selected for this session.
+    pub signers: Vec<u64>,
+    /// The signing package that must be sent to every selected signer.
+    pub signing_package: SigningPackage<S>,
+}
+
+/// The result of the [`Coordinator`] processing a message from a signer.
+pub enum Output<S: SigType> {
+    /// Not enough signers are ready yet to start a new session.
+    Pending,
+    /// A new signing session was started.
+    NewSession(SessionRequest<S>),
+    /// A session completed, producing the final signature.
+    Signature(Signature<S>),
+}
+
+/// The state of a single FROST signing session run by the [`Coordinator`].
+struct Session<S: SigType> {
+    signing_package: SigningPackage<S>,
+    /// The binding factors of every signer, cached so that each incoming
+    /// signature share can be checked without re-hashing the package.
+    bindings: BTreeMap<u64, S::Scalar>,
+    challenge: S::Scalar,
+    signature_shares: Vec<SignatureShare<S>>,
+    /// The time after which shares for this session are rejected, if any.
+    deadline: Option<Duration>,
+    cancelled: bool,
+}
+
+impl<S: SigType> Session<S> {
+    fn new(
+        signing_package: SigningPackage<S>,
+        pubkeys: &PublicKeyPackage<S>,
+        deadline: Option<Duration>,
+    ) -> Result<Self, Error> {
+        let bindings: BTreeMap<u64, S::Scalar> = signing_package
+            .signing_commitments
+            .iter()
+            .map(|comm| (comm.index, gen_rho_i(comm.index, &signing_package)))
+            .collect();
+        let group_commitment = gen_group_commitment(&signing_package, &bindings)?;
+        let challenge = gen_challenge(&signing_package, &group_commitment, &pubkeys.group_public);
+
+        Ok(Session {
+            signing_package,
+            bindings,
+            challenge,
+            signature_shares: Vec::new(),
+            deadline,
+            cancelled: false,
+        })
+    }
+
+    /// Whether only one signature share is missing from this session.
+    fn awaits_last_share(&self) -> bool {
+        self.signature_shares.len() + 1 == self.signing_package.signing_commitments.len()
+    }
+
+    fn is_expired(&self, now: Duration) -> bool {
+        self.deadline.map_or(false, |deadline| deadline <= now)
+    }
+
+    /// Returns the error for a share that arrives at `now`, if the session no
+    /// longer accepts shares.
+    fn closed_error(&self, now: Duration) -> Option<Error> {
+        if self.cancelled {
+            Some(Error::SessionCancelled)
+        } else if self.is_expired(now) {
+            Some(Error::SessionExpired)
+        } else {
+            None
+        }
+    }
+}
+
+/// The coordinator side of ROAST.
+///
+/// The coordinator is fed every message it receives from the signers through
+/// [`Coordinator::receive`], and forwards the [`SessionRequest`]s it returns to
+/// the signers they name. Signers are never waited on: a session that stalls
+/// is simply superseded by the next one.
+pub struct Coordinator<S: SigType> {
+    pubkeys: PublicKeyPackage<S>,
+    threshold: usize,
+    message: Vec<u8>,
+    run_id: SessionId,
+    /// Signers that sent a fresh commitment and are not part of any session.
+    ready: Vec<u64>,
+    /// Signers that sent an invalid message; they are ignored from then on.
+    malicious: BTreeSet<u64>,
+    /// The most recent commitment received from each signer.
+    latest_commitments: BTreeMap<u64, SigningCommitments<S>>,
+    /// The session each signer was last asked to sign in.
+    assigned_sessions: BTreeMap<u64, usize>,
+    sessions: Vec<Session<S>>,
+    signature: Option<Signature<S>>,
+    /// How long each new session accepts shares, if it expires at all.
+    session_timeout: Option<Duration>,
+    /// The latest time reported with `advance_time`.
+    now: Duration,
+}
+
+impl<S: SigType> Coordinator<S> {
+    /// Creates a coordinator that will obtain a `threshold` signature over
+    /// `message` from the signers in `pubkeys`, in the ROAST run identified by
+    /// `run_id`.
+    ///
+    /// `threshold` cannot be lower than the threshold the group's keys were
+    /// generated with, since smaller sessions cannot produce a signature.
+    pub fn new(
+        pubkeys: PublicKeyPackage<S>,
+        threshold: u8,
+        message: Vec<u8>,
+        run_id: SessionId,
+    ) -> Result<Self, Error> {
+        if threshold < 1 {
+            return Err(Error::ZeroThreshold);
+        }
+
+        if threshold as usize > pubkeys.signer_pubkeys.len() {
+            return Err(Error::ThresholdExceedsShares);
+        }
+
+        if threshold < pubkeys.threshold {
+            return Err(Error::NotEnoughCommitments);
+        }
+
+        Ok(Coordinator {
+            pubkeys,
+            threshold: threshold as usize,
+            message,
+            run_id,
+            ready: Vec::new(),
+            malicious: BTreeSet::new(),
+            latest_commitments: BTreeMap::new(),
+            assigned_sessions: BTreeMap::new(),
+            sessions: Vec::new(),
+            signature: None,
+            session_timeout: None,
+            now: Duration::ZERO,
+        })
+    }
+
+    /// Makes every session started from now on expire `timeout` after it
+    /// starts.
+    ///
+    /// Sessions never expire unless a timeout is set.
+    pub fn set_session_timeout(&mut self, timeout: Duration) {
+        self.session_timeout = Some(timeout);
+    }
+
+    /// Reports the current time, measured from any fixed starting point, such
+    /// as the creation of the coordinator.
+    ///
+    /// Returns the indices of the sessions that expired since the previous
+    /// call, whose signers can be told to stop. Times earlier than a time
+    /// already reported are ignored.
+    pub fn advance_time(&mut self, now: Duration) -> Vec<usize> {
+        let previous = self.now;
+        self.now = self.now.max(now);
+
+        self.sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, session)| {
+                !session.cancelled && !session.is_expired(previous) && session.is_expired(self.now)
+            })
+            .map(|(session_index, _)| session_index)
+            .collect()
+    }
+
+    /// Cancels the session with ID `session_id`, so that the commitments its
+    /// signers used for it can no longer be signed with.
+    ///
+    /// Shares for the session that arrive later are rejected with
+    /// [`Error::SessionCancelled`]. Returns [`Error::UnknownSession`] if the
+    /// coordinator did not start a session with this ID.
+    pub fn cancel(&mut self, session_id: SessionId) -> Result<(), Error> {
+        let session = self
+            .sessions
+            .iter_mut()
+            .find(|session| session.signing_package.session_id == session_id)
+            .ok_or(Error::UnknownSession)?;
+        session.cancelled = true;
+        Ok(())
+    }
+
+    /// Processes a message from the signer with participant index `index`.
+    ///
+    /// The first message of every signer carries no signature share, only the
+    /// commitment it will use in its first session. Every later message must
+    /// carry the signer's share for the session it was last assigned to,
+    /// together with a fresh commitment for its next session.
+    ///
+    /// Signers that send a message that does not follow these rules, or an
+    /// invalid signature share, are marked as malicious and ignored from then
+    /// on. An error is returned if this leaves fewer than `threshold`
+    /// non-malicious signers, since no signature can be produced anymore.
+    ///
+    /// A share for a session that has expired or was cancelled is rejected
+    /// with [`Error::SessionExpired`] or [`Error::SessionCancelled`]. The
+    /// signer is then no longer part of any session, and can rejoin by
+    /// sending its next commitments without a share.
+    pub fn receive(
+        &mut self,
+        index: u64,
+        signature_share: Option<SignatureShare<S>>,
+        next_commitments: SigningCommitments<S>,
+    ) -> Result<Output<S>, Error> {
+        if let Some(signature) = self.signature {
+            return Ok(Output::Signature(signature));
+        }
+
+        if !self.pubkeys.signer_pubkeys.contains_key(&index) {
+            return Err(Error::UnknownSigner);
+        }
+
+        if self.malicious.contains(&index) {
+            return Err(Error::MaliciousSigner);
+        }
+
+        if self.ready.contains(&index) {
+            return Err(Error::SignerAlreadyWaiting);
+        }
+
+        let identity = S::Point::identity();
+        if next_commitments.index != index
+            || next_commitments.hiding == identity
+            || next_commitments.binding == identity
+        {
+            return self.mark_malicious(index);
+        }
+
+        let mut assigned_session = self.assigned_sessions.remove(&index);
+        if let Some(session_index) = assigned_session {
+            if let Some(error) = self.sessions[session_index].closed_error(self.now) {
+                if signature_share.is_some() {
+                    return Err(error);
+                }
+                // The signer gave up on the closed session, and rejoins.
+                assigned_session = None;
+            }
+        }
+
+        match (assigned_session, signature_share) {
+            // The initial message of a signer.
+            (None, None) => {}
+            (Some(session_index), Some(signature_share)) => {
+                let session = &mut self.sessions[session_index];
+
+                if signature_share.index != index
+                    || verify_signature_share(
+                        &session.signing_package,
+                        &session.bindings,
+                        session.challenge,
+                        &signature_share,
+                        &self.pubkeys,
+                    )
+                    .is_err()
+                {
+                    return self.mark_malicious(index);
+                }
+
+                if session.awaits_last_share() {
+                    // Only record the share once the signature aggregates, so
+                    // that an error leaves the session and the signer's
+                    // assignment to it as they were.
+                    let mut signature_shares = session.signature_shares.clone();
+                    signature_shares.push(signature_share);
+                    let aggregated =
+                        aggregate(&session.signing_package, &signature_shares, &self.pubkeys);
+                    let signature = match aggregated {
+                        Ok(signature) => signature,
+                        Err(error) => {
+                            self.assigned_sessions.insert(index, session_index);
+                            return Err(error);
+                        }
+                    };
+                    session.signature_shares = signature_shares;
+                    self.signature = Some(signature);
+                    return Ok(Output::Signature(signature));
+                }
+
+                session.signature_shares.push(signature_share);
+            }
+            // Either a share without being asked for one, or no share while
+            // being part of a session.
+            _ => return self.mark_malicious(index),
+        }
+
+        self.latest_commitments.insert(index, next_commitments);
+        self.ready.push(index);
+
+        if self.ready.len() < self.threshold {
+            return Ok(Output::Pending);
+        }
+
+        let signers = core::mem::take(&mut self.ready);
+        let session_index = self.sessions.len();
+        let signing_package = SigningPackage::new(
+            &self.pubkeys,
+            session_id(self.run_id, session_index),
+            signers
+                .iter()
+                .map(|signer| self.latest_commitments[signer])
+                .collect(),
+            self.message.clone(),
+        )?;
+
+        let deadline = self.session_timeout.map(|timeout| self.now + timeout);
+        self.sessions.push(Session::new(
+            signing_package.clone(),
+            &self.pubkeys,
+            deadline,
+        )?);
+        for signer in signers.iter() {
+            self.assigned_sessions.insert(*signer, session_index);
+        }
+
+        Ok(Output::NewSession(SessionRequest {
+            session_index,
+            signers,
+            signing_package,
+        }))
+    }
+
+    /// Processes a message from the signer with participant index `index`
+    /// declining to sign in the session with index `session_index`, because
+    /// its [`SigningPolicy`] refused the request.
+    ///
+    /// Declining is not misbehavior: the signer is taken out of the session
+    /// without being marked as malicious, and is not made ready for another
+    /// session. It can rejoin the run by sending its next commitments without
+    /// a share to [`Coordinator::receive`], once it is willing to sign. The
+    /// session it declined can no longer complete, and is superseded like a
+    /// stalled one.
+    ///
+    /// Returns [`Error::UnknownSession`] if the signer is not part of the
+    /// session `session_index`.
+    pub fn decline(&mut self, index: u64, session_index: usize) -> Result<(), Error> {
+        if !self.pubkeys.signer_pubkeys.contains_key(&index) {
+            return Err(Error::UnknownSigner);
+        }
+
+        if self.malicious.contains(&index) {
+            return Err(Error::MaliciousSigner);
+        }
+
+        if self.assigned_sessions.get(&index) != Some(&session_index) {
+            return Err(Error::UnknownSession);
+        }
+
+        self.assigned_sessions.remove(&index);
+        Ok(())
+    }
+
+    /// Returns the participant indices of the signers identified as malicious
+    /// so far, in ascending order.
+    pub fn malicious_signers(&self) -> Vec<u64> {
+        let mut malicious: Vec<u64> = self.malicious.iter().copied().collect();
+        malicious.sort_unstable();
+        malicious
+    }
+
+    fn mark_malicious(&mut self, index: u64) -> Result<Output<S>, Error> {
+        self.malicious.insert(index);
+        self.assigned_sessions.remove(&index);
+
+        if self.pubkeys.signer_pubkeys.len() - self.malicious.len() < self.threshold {
+            return Err(Error::TooManyMaliciousSigners);
+        }
+
+        Ok(Output::Pending)
+    }
+}
+
+/// The signer side of ROAST.
+///
+/// A signer always holds exactly one set of unused nonces, whose commitments
+/// it has sent to the coordinator. Signing with them consumes them and
+/// generates the set for the next session.
+pub struct Signer<S: SigType> {
+    share_package: SharePackage<S>,
+    run_id: SessionId,
+    nonces: SigningNonces<S>,
+    commitments: SigningCommitments<S>,
+    policy: Option<Box<dyn SigningPolicy<S>>>,
+    counters: SigningCounters,
+}
+
+impl<S: SigType> Signer<S> {
+    /// Creates a signer for the ROAST run identified by `run_id` from its share
+    /// package, generating the nonces for its first session.
+    pub fn new<R>(share_package: SharePackage<S>, run_id: SessionId, rng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let nonces = SigningNonces::new(rng);
+        let commitments = SigningCommitments::from((share_package.index, &nonces));
+
+        Signer {
+            share_package,
+            run_id,
+            nonces,
+            commitments,
+            policy: None,
+            counters: SigningCounters::default(),
+        }
+    }
+
+    /// Sets the policy that must approve every request before this signer
+    /// signs it, replacing any previous policy.
+    pub fn set_policy<P: SigningPolicy<S> + 'static>(&mut self, policy: P) {
+        self.policy = Some(Box::new(policy));
+    }
+
+    /// The counts of the requests this signer has handled.
+    pub fn counters(&self) -> SigningCounters {
+        self.counters
+    }
+
+    /// The commitments to this signer's current nonces, which must be sent to
+    /// the coordinator with the signer's first message.
+    pub fn commitments(&self) -> SigningCommitments<S> {
+        self.commitments
+    }
+
+    /// Signs a [`SessionRequest`]'s signing package with the current nonces,
+    /// returning the signature share and the commitments for the next session,
+    /// which must both be sent to the coordinator.
+    ///
+    /// The signing package must belong to the session with index
+    /// `session_index` of this signer's ROAST run.
+    ///
+    /// Each signer should perform protocol-specific verification on the
+    /// message before calling this, or in its [`SigningPolicy`]. If the policy
+    /// refuses the request, [`Error::PolicyRejected`] is returned, and the
+    /// signer should report it with [`Coordinator::decline`]. The current
+    /// nonces are then discarded and replaced, so that the commitments that
+    /// appeared in the refused signing package are never signed with. The
+    /// signer rejoins the run by sending its new
+    /// [`commitments`](Self::commitments) without a share.
+    pub fn sign<R>(
+        &mut self,
+        session_index: usize,
+        signing_package: &SigningPackage<S>,
+        rng: &mut R,
+    ) -> Result<(SignatureShare<S>, SigningCommitments<S>), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let own_commitments = signing_package
+            .signing_commitments
+            .iter()
+            .find(|comm| comm.index == self.share_package.index)
+            .ok_or(Error::CommitmentMismatch)?;
+
+        if own_commitments.hiding != self.commitments.hiding
+            || own_commitments.binding != self.commitments.binding
+        {
+            return Err(Error::CommitmentMismatch);
+        }
+
+        let request = SigningRequest {
+            run_id: Some(self.run_id),
+            session_index: Some(session_index),
+            session_id: session_id(self.run_id, session_index),
+            signing_package,
+            counters: self.counters,
+        };
+        let approved = match self.policy.as_mut() {
+            Some(signing_policy) => {
+                policy::approve(&mut **signing_policy, &request, &mut self.counters)
+            }
+            None => true,
+        };
+        if !approved {
+            self.nonces = SigningNonces::new(rng);
+            self.commitments = SigningCommitments::from((self.share_package.index, &self.nonces));
+            return Err(Error::PolicyRejected);
+        }
+
+        let signature_share = sign(
+            signing_package,
+            session_id(self.run_id, session_index),
+            self.nonces,
+            &self.share_package,
+        )?;
+
+        self.nonces = SigningNonces::new(rng);
+        self.commitments = SigningCommitments::from((self.share_package.index, &self.nonces));
+        self.counters.signed += 1;
+
+        Ok((signature_share, self.commitments))
+    }
+}
@@ -0,0 +1,419 @@
+//! Snapshots of the ROAST state machines, so that a coordinator or signer can
+//! be checkpointed in the middle of a run and resumed after a restart.
+//!
+//! Snapshots are the `bincode` encoding (fixed-width little-endian integers)
+//! of a versioned description of the state, with points and scalars in their
+//! canonical 32-byte encodings. Values that can be recomputed, like the
+//! binding factors of a session, are left out and recomputed on restore.
+//!
+//! Snapshots only contain public values. A signer snapshot contains neither
+//! the signer's share package, which must be kept in secret storage
+//! separately and passed back on restore, nor its nonces: restoring a snapshot
+//! twice, or an outdated one, must never make a signer sign with the same
+//! nonces again, so a restored signer generates new ones.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::{convert::TryFrom, time::Duration};
+
+use bincode::Options;
+use group::{ff::PrimeField, GroupEncoding};
+use rand_core::{CryptoRng, RngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{session_id, Coordinator, Session, Signer, SigningCounters};
+use crate::{
+    frost::{
+        Error, Public, PublicKeyPackage, SessionId, SharePackage, SignatureResponse,
+        SignatureShare, SigningCommitments, SigningPackage,
+    },
+    SigType, Signature, VerificationKey,
+};
+
+/// The current version of the snapshot format.
+const VERSION: u8 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct CommitmentsSnapshot {
+    index: u64,
+    hiding: [u8; 32],
+    binding: [u8; 32],
+}
+
+#[derive(Serialize, Deserialize)]
+struct SessionSnapshot {
+    signing_commitments: Vec<CommitmentsSnapshot>,
+    signature_shares: Vec<(u64, [u8; 32])>,
+    deadline: Option<Duration>,
+    cancelled: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CoordinatorSnapshot {
+    version: u8,
+    signer_pubkeys: BTreeMap<u64, [u8; 32]>,
+    group_public: [u8; 32],
+    key_threshold: u8,
+    threshold: u8,
+    message: Vec<u8>,
+    run_id: [u8; 32],
+    ready: Vec<u64>,
+    malicious: Vec<u64>,
+    latest_commitments: Vec<CommitmentsSnapshot>,
+    assigned_sessions: BTreeMap<u64, u64>,
+    sessions: Vec<SessionSnapshot>,
+    signature: Option<([u8; 32], [u8; 32])>,
+    session_timeout: Option<Duration>,
+    now: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignerSnapshot {
+    version: u8,
+    index: u64,
+    run_id: [u8; 32],
+    counters: (u64, u64),
+}
+
+fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .serialize(value)
+        .expect("serializing into a Vec never fails")
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+        .map_err(|_| Error::MalformedSnapshot)
+}
+
+fn point_to_bytes<S: SigType>(point: &S::Point) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes.copy_from_slice(point.to_bytes().as_ref());
+    bytes
+}
+
+fn point_from_bytes<S: SigType>(bytes: [u8; 32]) -> Result<S::Point, Error> {
+    let mut repr = <S::Point as GroupEncoding>::Repr::default();
+    repr.as_mut().copy_from_slice(&bytes);
+    Option::from(S::Point::from_bytes(&repr)).ok_or(Error::MalformedSnapshot)
+}
+
+fn scalar_from_bytes<S: SigType>(bytes: [u8; 32]) -> Result<S::Scalar, Error> {
+    let mut repr = <S::Scalar as PrimeField>::Repr::default();
+    repr.as_mut().copy_from_slice(&bytes);
+    Option::from(S::Scalar::from_repr(repr)).ok_or(Error::MalformedSnapshot)
+}
+
+impl CommitmentsSnapshot {
+    fn new<S: SigType>(commitments: &SigningCommitments<S>) -> Self {
+        CommitmentsSnapshot {
+            index: commitments.index,
+            hiding: point_to_bytes::<S>(&commitments.hiding),
+            binding: point_to_bytes::<S>(&commitments.binding),
+        }
+    }
+
+    fn restore<S: SigType>(&self) -> Result<SigningCommitments<S>, Error> {
+        Ok(SigningCommitments {
+            index: self.index,
+            hiding: point_from_bytes::<S>(self.hiding)?,
+            binding: point_from_bytes::<S>(self.binding)?,
+        })
+    }
+}
+
+impl<S: SigType> Coordinator<S> {
+    /// Encodes the state of this coordinator, so that it can be resumed with
+    /// [`Coordinator::restore`].
+    ///
+    /// The snapshot only contains public values.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let sessions = self
+            .sessions
+            .iter()
+            .map(|session| SessionSnapshot {
+                signing_commitments: session
+                    .signing_package
+                    .signing_commitments
+                    .iter()
+                    .map(CommitmentsSnapshot::new)
+                    .collect(),
+                signature_shares: session
+                    .signature_shares
+                    .iter()
+                    .map(|share| {
+                        let mut bytes = [0; 32];
+                        bytes.copy_from_slice(share.signature.0.to_repr().as_ref());
+                        (share.index, bytes)
+                    })
+                    .collect(),
+                deadline: session.deadline,
+                cancelled: session.cancelled,
+            })
+            .collect();
+
+        serialize(&CoordinatorSnapshot {
+            version: VERSION,
+            signer_pubkeys: self
+                .pubkeys
+                .signer_pubkeys
+                .iter()
+                .map(|(index, public)| (*index, <[u8; 32]>::from(*public)))
+                .collect(),
+            group_public: self.pubkeys.group_public.into(),
+            key_threshold: self.pubkeys.threshold,
+            threshold: self.threshold as u8,
+            message: self.message.clone(),
+            run_id: self.run_id.into(),
+            ready: self.ready.clone(),
+            malicious: self.malicious.iter().copied().collect(),
+            latest_commitments: self
+                .latest_commitments
+                .values()
+                .map(CommitmentsSnapshot::new)
+                .collect(),
+            assigned_sessions: self
+                .assigned_sessions
+                .iter()
+                .map(|(index, session_index)| (*index, *session_index as u64))
+                .collect(),
+            sessions,
+            signature: self.signature.map(|signature| {
+                let bytes = <[u8; 64]>::from(signature);
+                let (mut r, mut s) = ([0; 32], [0; 32]);
+                r.copy_from_slice(&bytes[..32]);
+                s.copy_from_slice(&bytes[32..]);
+                (r, s)
+            }),
+            session_timeout: self.session_timeout,
+            now: self.now,
+        })
+    }
+
+    /// Resumes a coordinator from a snapshot taken with
+    /// [`Coordinator::snapshot`].
+    ///
+    /// Returns [`Error::MalformedSnapshot`] if the snapshot is not a valid
+    /// encoding, or describes an inconsistent state.
+    pub fn restore(bytes: &[u8]) -> Result<Self, Error> {
+        let snapshot: CoordinatorSnapshot = deserialize(bytes)?;
+        if snapshot.version != VERSION {
+            return Err(Error::MalformedSnapshot);
+        }
+
+        let signer_pubkeys = snapshot
+            .signer_pubkeys
+            .into_iter()
+            .map(|(index, bytes)| Ok((index, Public::try_from(bytes)?)))
+            .collect::<Result<BTreeMap<_, _>, Error>>()?;
+        let group_public = VerificationKey::try_from(snapshot.group_public)
+            .map_err(|_| Error::MalformedSnapshot)?;
+        let pubkeys = PublicKeyPackage::new(signer_pubkeys, group_public, snapshot.key_threshold)?;
+        let run_id = SessionId::from(snapshot.run_id);
+        let mut coordinator =
+            Coordinator::new(pubkeys, snapshot.threshold, snapshot.message, run_id)?;
+
+        let is_signer = |index: &u64| coordinator.pubkeys.signer_pubkeys.contains_key(index);
+        if !snapshot.ready.iter().all(is_signer)
+            || !snapshot.malicious.iter().all(is_signer)
+            || !snapshot.assigned_sessions.keys().all(is_signer)
+            || snapshot
+                .assigned_sessions
+                .values()
+                .any(|session_index| *session_index >= snapshot.sessions.len() as u64)
+        {
+            return Err(Error::MalformedSnapshot);
+        }
+
+        for (session_index, session) in snapshot.sessions.into_iter().enumerate() {
+            let signing_package = SigningPackage::new(
+                &coordinator.pubkeys,
+                session_id(run_id, session_index),
+                session
+                    .signing_commitments
+                    .iter()
+                    .map(CommitmentsSnapshot::restore)
+                    .collect::<Result<_, _>>()?,
+                coordinator.message.clone(),
+            )?;
+            let mut restored =
+                Session::new(signing_package, &coordinator.pubkeys, session.deadline)?;
+            restored.cancelled = session.cancelled;
+            for (index, bytes) in session.signature_shares {
+                if !restored.bindings.contains_key(&index) {
+                    return Err(Error::MalformedSnapshot);
+                }
+                restored.signature_shares.push(SignatureShare {
+                    index,
+                    signature: SignatureResponse(scalar_from_bytes::<S>(bytes)?),
+                });
+            }
+            coordinator.sessions.push(restored);
+        }
+
+        for commitments in snapshot.latest_commitments.iter() {
+            let commitments = commitments.restore::<S>()?;
+            if !coordinator
+                .pubkeys
+                .signer_pubkeys
+                .contains_key(&commitments.index)
+            {
+                return Err(Error::MalformedSnapshot);
+            }
+            coordinator
+                .latest_commitments
+                .insert(commitments.index, commitments);
+        }
+
+        // Each ready signer waits once, with the commitments it sent last, and
+        // is not part of a session; otherwise the next session would be
+        // started with missing or duplicate commitments.
+        let mut ready = BTreeSet::new();
+        for index in snapshot.ready.iter() {
+            if !ready.insert(*index)
+                || !coordinator.latest_commitments.contains_key(index)
+                || snapshot.assigned_sessions.contains_key(index)
+            {
+                return Err(Error::MalformedSnapshot);
+            }
+        }
+
+        coordinator.ready = snapshot.ready;
+        coordinator.malicious = snapshot.malicious.into_iter().collect();
+        coordinator.assigned_sessions = snapshot
+            .assigned_sessions
+            .into_iter()
+            .map(|(index, session_index)| (index, session_index as usize))
+            .collect();
+        coordinator.signature = snapshot
+            .signature
+            .map(|(r, s)| {
+                let mut bytes = [0; 64];
+                bytes[..32].copy_from_slice(&r);
+                bytes[32..].copy_from_slice(&s);
+                Signature::try_from(bytes).map_err(|_| Error::MalformedSnapshot)
+            })
+            .transpose()?;
+        coordinator.session_timeout = snapshot.session_timeout;
+        coordinator.now = snapshot.now;
+
+        Ok(coordinator)
+    }
+}
+
+impl<S: SigType> Signer<S> {
+    /// Encodes the state of this signer, except for its share package and its
+    /// nonces, so that it can be resumed with [`Signer::restore`].
+    ///
+    /// The snapshot only contains public values.
+    pub fn snapshot(&self) -> Vec<u8> {
+        serialize(&SignerSnapshot {
+            version: VERSION,
+            index: self.share_package.index,
+            run_id: self.run_id.into(),
+            counters: (self.counters.signed, self.counters.refused),
+        })
+    }
+
+    /// Resumes a signer from its share package and a snapshot taken with
+    /// [`Signer::snapshot`], generating new nonces.
+    ///
+    /// The restored signer cannot sign with the commitments the signer sent
+    /// before the snapshot. Like a signer that crashed, it sends its new
+    /// [`commitments`](Signer::commitments) to the coordinator without a share
+    /// once the session those were assigned to has expired or was cancelled.
+    ///
+    /// The snapshot keeps the signer's [`counters`](Signer::counters) as of
+    /// the snapshot, so restoring an older snapshot rolls them back: a policy
+    /// that enforces limits must keep its own count in durable storage. The
+    /// policy itself is not kept, and must be set again with
+    /// [`Signer::set_policy`].
+    ///
+    /// Returns [`Error::MalformedSnapshot`] if the snapshot is not a valid
+    /// encoding, or was taken by a signer with another participant index.
+    pub fn restore<R>(
+        share_package: SharePackage<S>,
+        bytes: &[u8],
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let snapshot: SignerSnapshot = deserialize(bytes)?;
+        if snapshot.version != VERSION || snapshot.index != share_package.index {
+            return Err(Error::MalformedSnapshot);
+        }
+
+        let mut signer = Signer::new(share_package, SessionId::from(snapshot.run_id), rng);
+        signer.counters = SigningCounters {
+            signed: snapshot.counters.0,
+            refused: snapshot.counters.1,
+        };
+        Ok(signer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{frost::keygen_with_dealer, sapling};
+
+    /// Returns a snapshot of a coordinator that started a session with
+    /// signers 1 and 2, and has signer 3 ready.
+    fn snapshot_with_ready_signer() -> CoordinatorSnapshot {
+        let mut rng = thread_rng();
+        let (shares, pubkeys) =
+            keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+        let run_id = SessionId::new(&mut rng);
+        let mut coordinator = Coordinator::new(pubkeys, 2, b"message".to_vec(), run_id).unwrap();
+        for share in shares {
+            let index = share.index;
+            let signer = Signer::new(share, run_id, &mut rng);
+            coordinator
+                .receive(index, None, signer.commitments())
+                .unwrap();
+        }
+        deserialize(&coordinator.snapshot()).unwrap()
+    }
+
+    #[test]
+    fn reject_inconsistent_ready_signers() {
+        let snapshot = snapshot_with_ready_signer();
+        assert_eq!(snapshot.ready, vec![3]);
+        assert!(Coordinator::<sapling::SpendAuth>::restore(&serialize(&snapshot)).is_ok());
+
+        // A ready signer without commitments.
+        let mut snapshot = snapshot_with_ready_signer();
+        snapshot
+            .latest_commitments
+            .retain(|commitments| commitments.index != 3);
+        assert_eq!(
+            Coordinator::<sapling::SpendAuth>::restore(&serialize(&snapshot)).err(),
+            Some(Error::MalformedSnapshot)
+        );
+
+        // A signer that is ready twice.
+        let mut snapshot = snapshot_with_ready_signer();
+        snapshot.ready.push(3);
+        assert_eq!(
+            Coordinator::<sapling::SpendAuth>::restore(&serialize(&snapshot)).err(),
+            Some(Error::MalformedSnapshot)
+        );
+
+        // A ready signer that is also part of a session.
+        let mut snapshot = snapshot_with_ready_signer();
+        snapshot.assigned_sessions.insert(3, 0);
+        assert_eq!(
+            Coordinator::<sapling::SpendAuth>::restore(&serialize(&snapshot)).err(),
+            Some(Error::MalformedSnapshot)
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Deterministic FROST test vectors, for cross-checking other implementations.
+//!
+//! [`generate`] runs dealer key generation with
+//! [`keygen_with_dealer_from_seed`] and a signing session among the first
+//! `threshold` participants, deriving every nonce and the session ID from the
+//! seed as well, and records all intermediate values. All byte strings are
+//! hex-encoded, with scalars and points in their canonical encodings.
+//!
+//! The `gen-frost-vectors` binary prints vectors for the RedPallas ciphersuite
+//! as JSON.
+
+use alloc::{string::String, vec::Vec};
+use std::collections::BTreeMap;
+
+use group::{ff::PrimeField, GroupEncoding};
+use serde::Serialize;
+
+use super::{
+    aggregate, gen_challenge, gen_group_commitment, gen_lagrange_coeff, gen_rho_i,
+    keygen_with_dealer_from_seed, sign, Error, SessionId, SigningCommitments, SigningNonces,
+    SigningPackage,
+};
+use crate::{HStar, SigType};
+
+/// A complete set of test vectors for one dealer key generation and signing
+/// session.
+#[derive(Clone, Debug, Serialize)]
+pub struct TestVectors {
+    /// The seed all values are derived from.
+    pub seed: String,
+    /// The number of participants.
+    pub num_signers: u8,
+    /// The number of signers needed to produce a signature.
+    pub threshold: u8,
+    /// The message that was signed.
+    pub message: String,
+    /// The ID of the signing session.
+    pub session_id: String,
+    /// The group public key.
+    pub group_public_key: String,
+    /// The share of every participant.
+    pub shares: Vec<ShareVector>,
+    /// The values computed by each participant taking part in signing.
+    pub signers: Vec<SignerVector>,
+    /// The group commitment `R`.
+    pub group_commitment: String,
+    /// The challenge `c`.
+    pub challenge: String,
+    /// The aggregated signature.
+    pub signature: String,
+}
+
+/// The share the dealer generated for one participant.
+#[derive(Clone, Debug, Serialize)]
+pub struct ShareVector {
+    /// The participant index.
+    pub index: u64,
+    /// The participant's secret share.
+    pub secret_share: String,
+    /// The participant's public key.
+    pub public_share: String,
+}
+
+/// The values one participant computed during signing.
+#[derive(Clone, Debug, Serialize)]
+pub struct SignerVector {
+    /// The participant index.
+    pub index: u64,
+    /// The hiding nonce.
+    pub hiding_nonce: String,
+    /// The binding nonce.
+    pub binding_nonce: String,
+    /// The commitment to the hiding nonce.
+    pub hiding_commitment: String,
+    /// The commitment to the binding nonce.
+    pub binding_commitment: String,
+    /// The participant's binding factor.
+    pub binding_factor: String,
+    /// The participant's Lagrange coefficient.
+    pub lagrange_coefficient: String,
+    /// The participant's signature share.
+    pub signature_share: String,
+}
+
+/// Generates the test vectors for `seed`, with `num_signers` participants,
+/// of which the first `threshold` sign `message`.
+pub fn generate<S: SigType>(
+    seed: &[u8],
+    num_signers: u8,
+    threshold: u8,
+    message: &[u8],
+) -> Result<TestVectors, Error> {
+    let (shares, pubkeys) = keygen_with_dealer_from_seed::<S>(seed, num_signers, threshold)?;
+
+    let mut session_id = [0u8; 32];
+    session_id.copy_from_slice(
+        blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"FROST_VectorSess")
+            .hash(seed)
+            .as_bytes(),
+    );
+    let session_id = SessionId(session_id);
+
+    let signers = &shares[..threshold as usize];
+    let nonces: Vec<SigningNonces<S>> = signers
+        .iter()
+        .map(|share| SigningNonces {
+            hiding: derive_nonce::<S>(seed, share.index, 0),
+            binding: derive_nonce::<S>(seed, share.index, 1),
+        })
+        .collect();
+    let commitments: Vec<SigningCommitments<S>> = signers
+        .iter()
+        .zip(nonces.iter())
+        .map(|(share, nonces)| SigningCommitments::from((share.index, nonces)))
+        .collect();
+    let signing_package =
+        SigningPackage::new(&pubkeys, session_id, commitments.clone(), message.to_vec())?;
+
+    let bindings: BTreeMap<u64, S::Scalar> = commitments
+        .iter()
+        .map(|comm| (comm.index, gen_rho_i(comm.index, &signing_package)))
+        .collect();
+    let group_commitment = gen_group_commitment(&signing_package, &bindings)?;
+    let challenge = gen_challenge(&signing_package, &group_commitment, &pubkeys.group_public);
+
+    let mut signature_shares = Vec::with_capacity(signers.len());
+    let mut signer_vectors = Vec::with_capacity(signers.len());
+    for ((share, nonces), commitment) in signers.iter().zip(nonces).zip(commitments.iter()) {
+        let signature_share = sign(&signing_package, session_id, nonces, share)?;
+        signer_vectors.push(SignerVector {
+            index: share.index,
+            hiding_nonce: hex(nonces.hiding.to_repr().as_ref()),
+            binding_nonce: hex(nonces.binding.to_repr().as_ref()),
+            hiding_commitment: hex(commitment.hiding.to_bytes().as_ref()),
+            binding_commitment: hex(commitment.binding.to_bytes().as_ref()),
+            binding_factor: hex(bindings[&share.index].to_repr().as_ref()),
+            lagrange_coefficient: hex(gen_lagrange_coeff(share.index, &signing_package)?
+                .to_repr()
+                .as_ref()),
+            signature_share: hex(signature_share.signature.0.to_repr().as_ref()),
+        });
+        signature_shares.push(signature_share);
+    }
+    let signature = aggregate(&signing_package, &signature_shares, &pubkeys)?;
+
+    Ok(TestVectors {
+        seed: hex(seed),
+        num_signers,
+        threshold,
+        message: hex(message),
+        session_id: hex(&session_id.0),
+        group_public_key: hex(&<[u8; 32]>::from(pubkeys.group_public)),
+        shares: shares
+            .iter()
+            .map(|share| ShareVector {
+                index: share.index,
+                secret_share: hex(share.share.value.0.to_repr().as_ref()),
+                public_share: hex(&<[u8; 32]>::from(share.public)),
+            })
+            .collect(),
+        signers: signer_vectors,
+        group_commitment: hex(group_commitment.0.to_bytes().as_ref()),
+        challenge: hex(challenge.to_repr().as_ref()),
+        signature: hex(&<[u8; 64]>::from(signature)),
+    })
+}
+
+/// Derives one of the two signing nonces of participant `index` from `seed`.
+fn derive_nonce<S: SigType>(seed: &[u8], index: u64, which: u8) -> S::Scalar {
+    HStar::<S>::default()
+        .update(b"FROST_VectorNonc")
+        .update(index.to_le_bytes())
+        .update([which])
+        .update(seed)
+        .finalize()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
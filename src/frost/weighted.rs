@@ -0,0 +1,109 @@
+//! Weighted threshold signing, where a participant can hold several shares.
+//!
+//! A participant's weight is the number of Shamir shares it receives from the
+//! dealer, and the threshold counts shares rather than participants. Each
+//! share is still an ordinary [`SharePackage`] with its own participant index,
+//! so the coordinator side of signing ([`aggregate`](super::aggregate)) is
+//! unchanged; the helpers in this module only let a participant commit and
+//! sign with all of its shares at once.
+
+use alloc::vec::Vec;
+
+use rand_core::{CryptoRng, RngCore};
+
+use super::{
+    Error, PublicKeyPackage, SessionId, SharePackage, SignatureShare, SigningCommitments,
+    SigningNonces, SigningPackage,
+};
+use crate::SigType;
+
+/// All the [`SharePackage`]s generated for a single weighted participant.
+pub struct WeightedSharePackage<S: SigType> {
+    /// The participant this package belongs to, counted from 1.
+    pub participant: u64,
+    /// One share package per unit of the participant's weight.
+    pub share_packages: Vec<SharePackage<S>>,
+}
+
+/// Allows all weighted participants' keys to be generated using a central,
+/// trusted dealer.
+///
+/// The participant at position `i` of `weights` receives `weights[i]` shares,
+/// with consecutive share indices. `threshold` is the number of shares, not
+/// participants, needed to sign. The total weight is limited to 255.
+pub fn keygen_with_dealer<R: RngCore + CryptoRng, S: SigType>(
+    weights: &[u8],
+    threshold: u8,
+    rng: R,
+) -> Result<(Vec<WeightedSharePackage<S>>, PublicKeyPackage<S>), Error> {
+    if weights.contains(&0) {
+        return Err(Error::ZeroWeight);
+    }
+
+    let total_weight: u16 = weights.iter().map(|weight| *weight as u16).sum();
+    if total_weight > u8::MAX as u16 {
+        return Err(Error::TotalWeightTooLarge);
+    }
+
+    let (sharepackages, pubkeys) = super::keygen_with_dealer(total_weight as u8, threshold, rng)?;
+
+    let mut sharepackages = sharepackages.into_iter();
+    let weighted_packages = weights
+        .iter()
+        .enumerate()
+        .map(|(i, weight)| WeightedSharePackage {
+            participant: i as u64 + 1,
+            share_packages: sharepackages.by_ref().take(*weight as usize).collect(),
+        })
+        .collect();
+
+    Ok((weighted_packages, pubkeys))
+}
+
+/// Generates one nonce and commitment pair for each of a participant's shares,
+/// to be used in a single signing operation.
+///
+/// The commitments are published, and are indexed by share, so the
+/// coordinator treats them as coming from independent signers.
+pub fn preprocess<R, S>(
+    package: &WeightedSharePackage<S>,
+    rng: &mut R,
+) -> (Vec<SigningNonces<S>>, Vec<SigningCommitments<S>>)
+where
+    R: CryptoRng + RngCore,
+    S: SigType,
+{
+    package
+        .share_packages
+        .iter()
+        .map(|share_package| {
+            let nonces = SigningNonces::new(rng);
+            let commitments = SigningCommitments::from((share_package.index, &nonces));
+            (nonces, commitments)
+        })
+        .unzip()
+}
+
+/// Produces a signature share for each of a participant's shares.
+///
+/// `nonces` must be the nonces returned by [`preprocess`] for this package,
+/// in the same order. `session_id` is checked as in [`sign`](super::sign).
+pub fn sign<S: SigType>(
+    signing_package: &SigningPackage<S>,
+    session_id: SessionId,
+    nonces: Vec<SigningNonces<S>>,
+    package: &WeightedSharePackage<S>,
+) -> Result<Vec<SignatureShare<S>>, Error> {
+    if nonces.len() != package.share_packages.len() {
+        return Err(Error::NonceCountMismatch);
+    }
+
+    package
+        .share_packages
+        .iter()
+        .zip(nonces)
+        .map(|(share_package, nonces)| {
+            super::sign(signing_package, session_id, nonces, share_package)
+        })
+        .collect()
+}
@@ -0,0 +1,557 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2020-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! FROST signing without heap allocation, for signers such as HSM firmware or
+//! secure elements that cannot use `alloc`.
+//!
+//! Signing packages hold their commitments in a fixed-capacity array whose size
+//! `N` is a const generic parameter, the maximum number of signers a device
+//! supports, and borrow rather than own the message. Signature shares produced
+//! here are identical to the ones produced by
+//! [`frost::sign`](crate::frost::sign) for the same inputs, so devices using
+//! this module can take part in signing sessions run by a coordinator using
+//! the `frost` module, and vice versa.
+//!
+//! Key generation is not part of this module; devices are provisioned with a
+//! [`KeyPackage`] by a dealer.
+
+use core::{convert::TryInto, fmt, marker::PhantomData};
+
+use group::{
+    ff::{Field, PrimeField},
+    Group, GroupEncoding,
+};
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::{DefaultIsZeroes, Zeroize};
+
+use crate::{
+    private::SealedScalar,
+    signing_key::{Fingerprint, SecretScalar},
+    HStar, SigType, Signature, VerificationKey,
+};
+
+/// An error related to no-alloc FROST signing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Error {
+    /// The encoding of a secret share was malformed.
+    MalformedShare,
+    /// The encoding of a public key was malformed.
+    MalformedPublicKey,
+    /// More signers or commitments were provided than the capacity `N`.
+    CapacityExceeded,
+    /// The public key of a signer was provided more than once.
+    DuplicateSigner,
+    /// The signing package contains more than one commitment for a
+    /// participant.
+    DuplicateCommitment,
+    /// A signing commitment was the identity element.
+    IdentityCommitment,
+    /// The signing package has no commitment for a participant.
+    MissingCommitment,
+    /// The participant is not part of the group.
+    UnknownSigner,
+    /// The signing package belongs to a different signing session than the
+    /// one the signer expected.
+    SessionMismatch,
+    /// A signature share failed verification.
+    InvalidSignatureShare,
+    /// The aggregated signature failed verification against the group public
+    /// key.
+    InvalidSignature,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedShare => write!(f, "Malformed secret share encoding."),
+            Self::MalformedPublicKey => write!(f, "Malformed public key."),
+            Self::CapacityExceeded => write!(f, "Capacity exceeded."),
+            Self::DuplicateSigner => write!(f, "Duplicate signer public key provided."),
+            Self::DuplicateCommitment => write!(f, "Duplicate signing commitments provided."),
+            Self::IdentityCommitment => write!(f, "Commitment equals the identity."),
+            Self::MissingCommitment => write!(f, "No matching signing commitment for signer."),
+            Self::UnknownSigner => write!(f, "Unknown signer."),
+            Self::SessionMismatch => write!(f, "Signing package is for a different session."),
+            Self::InvalidSignatureShare => write!(f, "Invalid signature share."),
+            Self::InvalidSignature => write!(f, "Invalid signature."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// A signer's long-lived key material: its participant index, its secret
+/// share, and the group public key.
+#[derive(Clone)]
+pub struct KeyPackage<S: SigType> {
+    index: u64,
+    secret_share: SecretScalar<S::Scalar>,
+    group_public: VerificationKey<S>,
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: SigType> Drop for KeyPackage<S> {
+    fn drop(&mut self) {
+        self.secret_share.zeroize();
+    }
+}
+
+/// Only shows a fingerprint of the public key of the secret share.
+impl<S: SigType> fmt::Debug for KeyPackage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let public = S::basepoint_mul(&self.secret_share.0).to_bytes();
+        f.debug_struct("KeyPackage")
+            .field("index", &self.index)
+            .field("fingerprint", &Fingerprint::new(&[public.as_ref()]))
+            .field("group_public", &self.group_public)
+            .finish()
+    }
+}
+
+impl<S: SigType> KeyPackage<S> {
+    /// Creates a key package from a participant index, the canonical encoding
+    /// of the participant's secret share, and the group public key.
+    pub fn new(
+        index: u64,
+        secret_share: [u8; 32],
+        group_public: VerificationKey<S>,
+    ) -> Result<Self, Error> {
+        let mut repr = <S::Scalar as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(&secret_share);
+        let maybe_share: Option<S::Scalar> = S::Scalar::from_repr(repr).into();
+
+        Ok(KeyPackage {
+            index,
+            secret_share: SecretScalar(maybe_share.ok_or(Error::MalformedShare)?),
+            group_public,
+        })
+    }
+
+    /// The participant index of this signer.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: SigType> From<&crate::frost::SharePackage<S>> for KeyPackage<S> {
+    fn from(share_package: &crate::frost::SharePackage<S>) -> Self {
+        KeyPackage {
+            index: share_package.index,
+            secret_share: SecretScalar(share_package.share.value.0),
+            group_public: share_package.group_public,
+        }
+    }
+}
+
+/// The public keys of up to `N` signers, and the group public key, used by a
+/// coordinator to check signature shares.
+#[derive(Clone)]
+pub struct PublicKeyPackage<S: SigType, const N: usize> {
+    signer_pubkeys: [Option<(u64, S::Point)>; N],
+    group_public: VerificationKey<S>,
+}
+
+impl<S: SigType, const N: usize> PublicKeyPackage<S, N> {
+    /// Creates a public key package for the group public key `group_public`,
+    /// with no signers.
+    pub fn new(group_public: VerificationKey<S>) -> Self {
+        PublicKeyPackage {
+            signer_pubkeys: [None; N],
+            group_public,
+        }
+    }
+
+    /// Adds the public key of the signer with participant index `index`, given
+    /// by its canonical encoding.
+    pub fn insert(&mut self, index: u64, public: [u8; 32]) -> Result<(), Error> {
+        let mut repr = <S::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&public);
+        let maybe_point: Option<S::Point> = S::Point::from_bytes(&repr).into();
+        let point = maybe_point.ok_or(Error::MalformedPublicKey)?;
+
+        if self.signer_pubkey(index).is_some() {
+            return Err(Error::DuplicateSigner);
+        }
+
+        let slot = self
+            .signer_pubkeys
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(Error::CapacityExceeded)?;
+        *slot = Some((index, point));
+
+        Ok(())
+    }
+
+    /// The group public key.
+    pub fn group_public(&self) -> VerificationKey<S> {
+        self.group_public
+    }
+
+    fn signer_pubkey(&self, index: u64) -> Option<S::Point> {
+        self.signer_pubkeys
+            .iter()
+            .flatten()
+            .find(|(i, _)| *i == index)
+            .map(|(_, point)| *point)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: SigType, const N: usize> core::convert::TryFrom<&crate::frost::PublicKeyPackage<S>>
+    for PublicKeyPackage<S, N>
+{
+    type Error = Error;
+
+    fn try_from(pubkeys: &crate::frost::PublicKeyPackage<S>) -> Result<Self, Self::Error> {
+        let mut package = PublicKeyPackage::new(pubkeys.group_public);
+        for (index, public) in pubkeys.signer_pubkeys() {
+            package.insert(index, (*public).into())?;
+        }
+        Ok(package)
+    }
+}
+
+/// Comprised of hiding and binding nonces.
+///
+/// Note that [`SigningNonces`] must be used *only once* for a signing
+/// operation; re-using nonces will result in leakage of a signer's long-lived
+/// signing key.
+#[derive(Clone, Copy, Default)]
+pub struct SigningNonces<S: SigType> {
+    hiding: S::Scalar,
+    binding: S::Scalar,
+}
+
+// Zeroizes `SigningNonces` to be the `Default` value.
+#[cfg(feature = "zeroize")]
+impl<S: SigType> DefaultIsZeroes for SigningNonces<S> {}
+
+/// Only shows a fingerprint of the commitments to the nonces, which matches
+/// the fingerprint of the same nonces in the `frost` module.
+impl<S: SigType> fmt::Debug for SigningNonces<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hiding = S::basepoint_mul(&self.hiding).to_bytes();
+        let binding = S::basepoint_mul(&self.binding).to_bytes();
+        f.debug_struct("SigningNonces")
+            .field(
+                "fingerprint",
+                &Fingerprint::new(&[hiding.as_ref(), binding.as_ref()]),
+            )
+            .finish()
+    }
+}
+
+impl<S: SigType> SigningNonces<S> {
+    /// Generates a new signing nonce.
+    pub fn new<R>(rng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        fn random_nonzero_scalar<R, F>(rng: &mut R) -> F
+        where
+            R: CryptoRng + RngCore,
+            F: Field + SealedScalar,
+        {
+            let mut bytes = [0; 64];
+            loop {
+                rng.fill_bytes(&mut bytes);
+                let scalar = F::from_bytes_wide(&bytes);
+                if scalar != F::zero() {
+                    return scalar;
+                }
+            }
+        }
+
+        // The values of 'hiding' and 'binding' must be non-zero so that commitments are not the
+        // identity.
+        Self {
+            hiding: random_nonzero_scalar(rng),
+            binding: random_nonzero_scalar(rng),
+        }
+    }
+}
+
+/// Published by each participant in the first round of the signing protocol.
+#[derive(Copy, Clone)]
+pub struct SigningCommitments<S: SigType> {
+    index: u64,
+    hiding: S::Point,
+    binding: S::Point,
+}
+
+impl<S: SigType> From<(u64, &SigningNonces<S>)> for SigningCommitments<S> {
+    fn from((index, nonces): (u64, &SigningNonces<S>)) -> Self {
+        Self {
+            index,
+            hiding: S::basepoint_mul(&nonces.hiding),
+            binding: S::basepoint_mul(&nonces.binding),
+        }
+    }
+}
+
+impl<S: SigType> SigningCommitments<S> {
+    /// The participant index of the signer that published these commitments.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: SigType> From<SigningCommitments<S>> for crate::frost::SigningCommitments<S> {
+    fn from(commitments: SigningCommitments<S>) -> Self {
+        crate::frost::SigningCommitments {
+            index: commitments.index,
+            hiding: commitments.hiding,
+            binding: commitments.binding,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: SigType> From<crate::frost::SigningCommitments<S>> for SigningCommitments<S> {
+    fn from(commitments: crate::frost::SigningCommitments<S>) -> Self {
+        SigningCommitments {
+            index: commitments.index,
+            hiding: commitments.hiding,
+            binding: commitments.binding,
+        }
+    }
+}
+
+/// The commitments of at most `N` signers and the message they will sign, for
+/// one signing session.
+#[derive(Clone)]
+pub struct SigningPackage<'msg, S: SigType, const N: usize> {
+    session_id: [u8; 32],
+    signing_commitments: [Option<SigningCommitments<S>>; N],
+    message: &'msg [u8],
+}
+
+impl<'msg, S: SigType, const N: usize> SigningPackage<'msg, S, N> {
+    /// Creates a signing package for `message` in the session `session_id`,
+    /// from one commitment per participating signer.
+    ///
+    /// Returns an error if there are more than `N` commitments, if two
+    /// commitments belong to the same participant, or if a commitment is the
+    /// identity.
+    pub fn new(
+        session_id: [u8; 32],
+        signing_commitments: &[SigningCommitments<S>],
+        message: &'msg [u8],
+    ) -> Result<Self, Error> {
+        if signing_commitments.len() > N {
+            return Err(Error::CapacityExceeded);
+        }
+
+        let identity = S::Point::identity();
+        let mut package = SigningPackage {
+            session_id,
+            signing_commitments: [None; N],
+            message,
+        };
+        for (i, commitment) in signing_commitments.iter().enumerate() {
+            if signing_commitments[..i]
+                .iter()
+                .any(|other| other.index == commitment.index)
+            {
+                return Err(Error::DuplicateCommitment);
+            }
+            if commitment.hiding == identity || commitment.binding == identity {
+                return Err(Error::IdentityCommitment);
+            }
+            package.signing_commitments[i] = Some(*commitment);
+        }
+
+        Ok(package)
+    }
+
+    fn commitments(&self) -> impl Iterator<Item = &SigningCommitments<S>> {
+        self.signing_commitments.iter().flatten()
+    }
+
+    /// Generates the binding factor of every signer, in the order of the
+    /// commitments, exactly as `frost` does.
+    fn bindings(&self) -> [S::Scalar; N] {
+        let message_hash = HStar::<S>::default().update(self.message).finalize();
+
+        let mut bindings = [S::Scalar::zero(); N];
+        for (binding, commitment) in bindings.iter_mut().zip(self.commitments()) {
+            let mut hasher = HStar::<S>::default();
+            hasher
+                .update("FROST_rho".as_bytes())
+                .update(self.session_id)
+                .update(commitment.index.to_be_bytes())
+                .update(message_hash.to_repr());
+
+            for item in self.commitments() {
+                hasher.update(item.index.to_be_bytes());
+                hasher.update(item.hiding.to_bytes());
+                hasher.update(item.binding.to_bytes());
+            }
+
+            *binding = hasher.finalize();
+        }
+
+        bindings
+    }
+
+    /// Generates the group commitment and the challenge of this package, given
+    /// its binding factors.
+    fn group_commitment_and_challenge(
+        &self,
+        bindings: &[S::Scalar; N],
+        group_public: &VerificationKey<S>,
+    ) -> (S::Point, S::Scalar) {
+        let mut group_commitment = S::Point::identity();
+        for (commitment, rho_i) in self.commitments().zip(bindings.iter()) {
+            group_commitment += commitment.hiding + (commitment.binding * rho_i);
+        }
+
+        let challenge = HStar::<S>::default()
+            .update(group_commitment.to_bytes())
+            .update(group_public.bytes.bytes)
+            .update(self.message)
+            .finalize();
+
+        (group_commitment, challenge)
+    }
+
+    /// Computes the Lagrange coefficient of the participant with index
+    /// `signer_index` over the signers of this package.
+    fn lagrange_coeff(&self, signer_index: u64) -> S::Scalar {
+        let mut num = S::Scalar::one();
+        let mut den = S::Scalar::one();
+        for commitment in self.commitments() {
+            if commitment.index == signer_index {
+                continue;
+            }
+            num *= S::Scalar::from(commitment.index);
+            den *= S::Scalar::from(commitment.index) - S::Scalar::from(signer_index);
+        }
+
+        // The indices are distinct, so `den` is not zero.
+        num * den.invert().unwrap()
+    }
+}
+
+/// A participant's signature share, which the coordinator will use to aggregate
+/// with all other signer's shares into the joint signature.
+#[derive(Clone, Copy, Default)]
+pub struct SignatureShare<S: SigType> {
+    index: u64,
+    signature: S::Scalar,
+}
+
+impl<S: SigType> SignatureShare<S> {
+    /// The participant index of the signer that produced this share.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: SigType> From<SignatureShare<S>> for crate::frost::SignatureShare<S> {
+    fn from(share: SignatureShare<S>) -> Self {
+        crate::frost::SignatureShare {
+            index: share.index,
+            signature: crate::frost::SignatureResponse(share.signature),
+        }
+    }
+}
+
+/// Performed once by each participant selected for the signing operation, as
+/// in [`frost::sign`](crate::frost::sign).
+///
+/// Signing packages for a session other than `session_id` are rejected with
+/// [`Error::SessionMismatch`].
+pub fn sign<S: SigType, const N: usize>(
+    signing_package: &SigningPackage<'_, S, N>,
+    session_id: [u8; 32],
+    participant_nonces: SigningNonces<S>,
+    key_package: &KeyPackage<S>,
+) -> Result<SignatureShare<S>, Error> {
+    if signing_package.session_id != session_id {
+        return Err(Error::SessionMismatch);
+    }
+
+    let position = signing_package
+        .commitments()
+        .position(|commitment| commitment.index == key_package.index)
+        .ok_or(Error::MissingCommitment)?;
+
+    let bindings = signing_package.bindings();
+    let (_, challenge) =
+        signing_package.group_commitment_and_challenge(&bindings, &key_package.group_public);
+    let lambda_i = signing_package.lagrange_coeff(key_package.index);
+    let rho_i = bindings[position];
+
+    let signature = participant_nonces.hiding
+        + (participant_nonces.binding * rho_i)
+        + (lambda_i * key_package.secret_share.0 * challenge);
+
+    Ok(SignatureShare {
+        index: key_package.index,
+        signature,
+    })
+}
+
+/// Verifies each participant's signature share, and if all are valid,
+/// aggregates the shares into a signature to publish.
+///
+/// As in [`frost::aggregate`](crate::frost::aggregate), the aggregated
+/// signature is verified against the group public key, and
+/// [`Error::InvalidSignature`] is returned if shares are missing for some of
+/// the participants in the signing package.
+pub fn aggregate<S: SigType, const N: usize>(
+    signing_package: &SigningPackage<'_, S, N>,
+    signing_shares: &[SignatureShare<S>],
+    pubkeys: &PublicKeyPackage<S, N>,
+) -> Result<Signature<S>, Error> {
+    let bindings = signing_package.bindings();
+    let (group_commitment, challenge) =
+        signing_package.group_commitment_and_challenge(&bindings, &pubkeys.group_public);
+
+    let mut z = S::Scalar::zero();
+    for signing_share in signing_shares {
+        let signer_pubkey = pubkeys
+            .signer_pubkey(signing_share.index)
+            .ok_or(Error::UnknownSigner)?;
+        let (commitment, rho_i) = signing_package
+            .commitments()
+            .zip(bindings.iter())
+            .find(|(commitment, _)| commitment.index == signing_share.index)
+            .ok_or(Error::MissingCommitment)?;
+
+        let lambda_i = signing_package.lagrange_coeff(signing_share.index);
+        let commitment_i = commitment.hiding + (commitment.binding * rho_i);
+        if S::basepoint() * signing_share.signature
+            != commitment_i + signer_pubkey * challenge * lambda_i
+        {
+            return Err(Error::InvalidSignatureShare);
+        }
+
+        z += signing_share.signature;
+    }
+
+    let signature = Signature {
+        r_bytes: group_commitment.to_bytes().as_ref().try_into().unwrap(),
+        s_bytes: z.to_repr().as_ref().try_into().unwrap(),
+        _marker: PhantomData,
+    };
+
+    // The shares are valid, but the signature is still invalid if shares are
+    // missing for some of the participants in the signing package.
+    pubkeys
+        .group_public
+        .verify(signing_package.message, &signature)
+        .map_err(|_| Error::InvalidSignature)?;
+
+    Ok(signature)
+}
@@ -0,0 +1,9 @@
+//! Conversions between the types of this crate and those of other Zcash crates.
+//!
+//! Each set of conversions is enabled by its own feature, so that depending on
+//! this crate does not pull in the other crates.
+
+#[cfg(feature = "orchard-interop")]
+pub mod orchard;
+#[cfg(feature = "sapling-interop")]
+pub mod sapling;
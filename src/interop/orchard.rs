@@ -0,0 +1,99 @@
+//! Conversions to and from the [`orchard`](::orchard) crate's RedPallas types.
+//!
+//! The `orchard` crate wraps RedPallas keys and signatures in
+//! [`orchard::primitives::redpallas`](::orchard::primitives::redpallas). This
+//! module implements `From` between those wrappers and the
+//! [`SigningKey`], [`VerificationKey`] and [`Signature`] types of this crate,
+//! for both [`SpendAuth`](crate::orchard::SpendAuth) and
+//! [`Binding`](crate::orchard::Binding) signatures.
+//!
+//! Both crates use the same encodings, so the conversions go through the
+//! canonical byte encodings and cannot fail.
+
+use core::convert::TryFrom;
+
+use ::orchard::primitives::redpallas;
+use group::ff::PrimeField;
+use pasta_curves::pallas;
+
+use crate::{orchard, Signature, SigningKey, VerificationKey, VerificationKeyBytes};
+
+macro_rules! impl_conversions {
+    ($ours:ty, $theirs:ty) => {
+        impl From<&redpallas::SigningKey<$theirs>> for SigningKey<$ours> {
+            fn from(sk: &redpallas::SigningKey<$theirs>) -> Self {
+                let bytes = <[u8; 32]>::from(sk);
+                SigningKey::from_scalar(
+                    Option::from(pallas::Scalar::from_repr(bytes))
+                        .expect("orchard signing keys are canonical scalars"),
+                )
+            }
+        }
+
+        impl From<&SigningKey<$ours>> for redpallas::SigningKey<$theirs> {
+            fn from(sk: &SigningKey<$ours>) -> Self {
+                redpallas::SigningKey::try_from(<[u8; 32]>::from(sk.clone()))
+                    .expect("signing keys are canonical scalars")
+            }
+        }
+
+        impl From<&redpallas::VerificationKey<$theirs>> for VerificationKey<$ours> {
+            fn from(vk: &redpallas::VerificationKey<$theirs>) -> Self {
+                VerificationKey::try_from(VerificationKeyBytes::from(<[u8; 32]>::from(vk)))
+                    .expect("orchard verification keys are canonical points")
+            }
+        }
+
+        impl From<&VerificationKey<$ours>> for redpallas::VerificationKey<$theirs> {
+            fn from(vk: &VerificationKey<$ours>) -> Self {
+                redpallas::VerificationKey::try_from(<[u8; 32]>::from(*vk))
+                    .expect("verification keys are canonical points")
+            }
+        }
+
+        impl From<&redpallas::Signature<$theirs>> for Signature<$ours> {
+            fn from(sig: &redpallas::Signature<$theirs>) -> Self {
+                Signature::from_bytes_unchecked(<[u8; 64]>::from(sig))
+            }
+        }
+
+        impl From<&Signature<$ours>> for redpallas::Signature<$theirs> {
+            fn from(sig: &Signature<$ours>) -> Self {
+                <[u8; 64]>::from(*sig).into()
+            }
+        }
+    };
+}
+
+impl_conversions!(orchard::SpendAuth, redpallas::SpendAuth);
+impl_conversions!(orchard::Binding, redpallas::Binding);
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+
+    use super::*;
+
+    #[test]
+    fn spendauth_roundtrip() {
+        let sk = SigningKey::<orchard::SpendAuth>::new(OsRng);
+        let vk: VerificationKey<orchard::SpendAuth> = (&sk).into();
+        let msg = b"orchard interop";
+        let sig = sk.sign(OsRng, msg);
+
+        let their_sk = redpallas::SigningKey::<redpallas::SpendAuth>::from(&sk);
+        let their_vk = redpallas::VerificationKey::from(&their_sk);
+        let ours: VerificationKey<orchard::SpendAuth> = (&their_vk).into();
+        assert_eq!(ours, vk);
+
+        let their_sig = redpallas::Signature::from(&sig);
+        assert!(their_vk.verify(msg, &their_sig).is_ok());
+
+        let their_sig = their_sk.sign(OsRng, msg);
+        assert!(vk.verify(msg, &Signature::from(&their_sig)).is_ok());
+        assert_eq!(
+            <[u8; 32]>::from(SigningKey::from(&their_sk)),
+            <[u8; 32]>::from(sk)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Conversions to and from the RedJubjub types of
+//! [`zcash_primitives`](::zcash_primitives).
+//!
+//! The Sapling transaction builder in `zcash_primitives` signs with
+//! [`redjubjub::PrivateKey`], [`redjubjub::PublicKey`] and
+//! [`redjubjub::Signature`], which do not distinguish between `SpendAuthSig`
+//! and `BindingSig`. This module implements `From` between those types and the
+//! [`SigningKey`], [`VerificationKey`] and [`Signature`] types of this crate,
+//! for both [`SpendAuth`](crate::sapling::SpendAuth) and
+//! [`Binding`](crate::sapling::Binding) signatures.
+//!
+//! Both crates use the same encodings, so the conversions go through the
+//! canonical byte encodings and cannot fail. Note that `zcash_primitives`
+//! hashes the message as given, so a signature made with this crate verifies
+//! there over the verification key encoding followed by the message.
+
+use core::convert::TryFrom;
+
+use ::zcash_primitives::sapling::redjubjub;
+use group::ff::PrimeField;
+
+use crate::{sapling, Signature, SigningKey, VerificationKey, VerificationKeyBytes};
+
+macro_rules! impl_conversions {
+    ($ours:ty) => {
+        impl From<&redjubjub::PrivateKey> for SigningKey<$ours> {
+            fn from(sk: &redjubjub::PrivateKey) -> Self {
+                SigningKey::from_scalar(sk.0)
+            }
+        }
+
+        impl From<&SigningKey<$ours>> for redjubjub::PrivateKey {
+            fn from(sk: &SigningKey<$ours>) -> Self {
+                let bytes = <[u8; 32]>::from(sk.clone());
+                redjubjub::PrivateKey(
+                    Option::from(jubjub::Fr::from_repr(bytes))
+                        .expect("signing keys are canonical scalars"),
+                )
+            }
+        }
+
+        impl From<&redjubjub::PublicKey> for VerificationKey<$ours> {
+            fn from(vk: &redjubjub::PublicKey) -> Self {
+                let mut bytes = [0; 32];
+                vk.write(&mut bytes[..])
+                    .expect("a public key encoding is 32 bytes");
+                VerificationKey::try_from(VerificationKeyBytes::from(bytes))
+                    .expect("zcash_primitives public keys are canonical points")
+            }
+        }
+
+        impl From<&VerificationKey<$ours>> for redjubjub::PublicKey {
+            fn from(vk: &VerificationKey<$ours>) -> Self {
+                redjubjub::PublicKey::read(&<[u8; 32]>::from(*vk)[..])
+                    .expect("verification keys are canonical points")
+            }
+        }
+
+        impl From<&redjubjub::Signature> for Signature<$ours> {
+            fn from(sig: &redjubjub::Signature) -> Self {
+                let mut bytes = [0; 64];
+                sig.write(&mut bytes[..])
+                    .expect("a signature encoding is 64 bytes");
+                Signature::from_bytes_unchecked(bytes)
+            }
+        }
+
+        impl From<&Signature<$ours>> for redjubjub::Signature {
+            fn from(sig: &Signature<$ours>) -> Self {
+                redjubjub::Signature::read(&<[u8; 64]>::from(*sig)[..])
+                    .expect("a signature encoding is 64 bytes")
+            }
+        }
+    };
+}
+
+impl_conversions!(sapling::SpendAuth);
+impl_conversions!(sapling::Binding);
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+    use zcash_primitives::constants::SPENDING_KEY_GENERATOR;
+
+    use super::*;
+
+    #[test]
+    fn spendauth_roundtrip() {
+        let sk = SigningKey::<sapling::SpendAuth>::new(OsRng);
+        let vk: VerificationKey<sapling::SpendAuth> = (&sk).into();
+        let msg = b"sapling interop";
+        let sig = sk.sign(OsRng, msg);
+        // zcash_primitives expects the verification key to be prepended to
+        // the message.
+        let mut their_msg = <[u8; 32]>::from(vk).to_vec();
+        their_msg.extend_from_slice(msg);
+
+        let their_sk = redjubjub::PrivateKey::from(&sk);
+        let their_vk = redjubjub::PublicKey::from_private(&their_sk, SPENDING_KEY_GENERATOR);
+        let ours: VerificationKey<sapling::SpendAuth> = (&their_vk).into();
+        assert_eq!(ours, vk);
+        assert!(their_vk.verify(
+            &their_msg,
+            &redjubjub::Signature::from(&sig),
+            SPENDING_KEY_GENERATOR
+        ));
+
+        let their_sig = their_sk.sign(&their_msg, &mut OsRng, SPENDING_KEY_GENERATOR);
+        assert!(vk.verify(msg, &Signature::from(&their_sig)).is_ok());
+        assert_eq!(
+            <[u8; 32]>::from(SigningKey::<sapling::SpendAuth>::from(&their_sk)),
+            <[u8; 32]>::from(sk)
+        );
+    }
+}
@@ -20,15 +20,20 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-#[cfg(feature = "alloc")]
+pub mod adaptor;
+#[cfg(feature = "std")]
+mod basepoint_table;
 pub mod batch;
 mod constants;
 mod error;
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 pub mod frost;
+pub mod frost_heapless;
 mod hash;
+#[cfg(any(feature = "orchard-interop", feature = "sapling-interop"))]
+pub mod interop;
 #[cfg(feature = "std")]
-mod messages;
+pub mod messages;
 pub mod orchard;
 pub mod sapling;
 #[cfg(feature = "alloc")]
@@ -36,14 +41,21 @@ mod scalar_mul;
 pub(crate) mod signature;
 mod signing_key;
 mod verification_key;
+pub mod vesta;
+#[cfg(feature = "alloc")]
+pub mod vrf;
 
 /// An element of the protocol's scalar field used for randomization of public and secret keys.
+///
+/// Randomizers compose with the field operations: randomizing by `a` and then
+/// by `b` is the same as randomizing by `a + b`, and randomizing by `-a` undoes
+/// a randomization by `a`.
 pub type Randomizer<S> = <S as private::Sealed<S>>::Scalar;
 
 use hash::HStar;
 
+pub use crate::signature::Signature;
 pub use error::Error;
-pub use signature::Signature;
 pub use signing_key::SigningKey;
 pub use verification_key::{VerificationKey, VerificationKeyBytes};
 
@@ -58,8 +70,16 @@ pub use verification_key::{VerificationKey, VerificationKeyBytes};
 /// To handle this, we encode the parameter choice as a genuine type
 /// parameter.
 ///
+/// The trait is sealed, so it cannot be implemented outside this crate, but
+/// downstream code can use its constants to refer to the exact parameters each
+/// signature type signs with.
+///
 /// [concretereddsa]: https://zips.z.cash/protocol/protocol.pdf#concretereddsa
-pub trait SigType: private::Sealed<Self> {}
+pub trait SigType: private::Sealed<Self> {
+    /// The byte encoding of the basepoint that signatures of this type are
+    /// made with.
+    const BASEPOINT_BYTES: [u8; 32];
+}
 
 /// A trait corresponding to `BindingSig` in Zcash protocols.
 pub trait Binding: SigType {}
@@ -85,7 +105,7 @@ pub(crate) mod private {
     }
 
     pub trait Sealed<T: SigType>:
-        Copy + Clone + Default + Eq + PartialEq + core::fmt::Debug
+        Copy + Clone + Default + Eq + PartialEq + core::fmt::Debug + Send + Sync
     {
         const H_STAR_PERSONALIZATION: &'static [u8; 16];
         type Scalar: group::ff::PrimeField + SealedScalar;
@@ -99,6 +119,23 @@ pub(crate) mod private {
         type Point: group::cofactor::CofactorCurve<Scalar = Self::Scalar>;
 
         fn basepoint() -> T::Point;
+
+        /// Multiply the basepoint by `scalar`, using a precomputed table when
+        /// one is available.
+        fn basepoint_mul(scalar: &T::Scalar) -> T::Point {
+            T::basepoint() * scalar
+        }
+
+        /// Compute `b` times the basepoint plus `a` times `A`, in variable
+        /// time.
+        #[cfg(feature = "alloc")]
+        #[allow(non_snake_case)]
+        fn vartime_double_base_mul(b: &T::Scalar, a: &T::Scalar, A: &T::Point) -> T::Point {
+            <T::Point as scalar_mul::VartimeMultiscalarMul>::vartime_multiscalar_mul(
+                [*b, *a],
+                [T::basepoint(), *A],
+            )
+        }
     }
     impl Sealed<sapling::Binding> for sapling::Binding {
         const H_STAR_PERSONALIZATION: &'static [u8; 16] = b"Zcash_RedJubjubH";
@@ -110,6 +147,26 @@ pub(crate) mod private {
                 .unwrap()
                 .into()
         }
+
+        #[cfg(feature = "std")]
+        fn basepoint_mul(scalar: &jubjub::Scalar) -> jubjub::ExtendedPoint {
+            basepoint_table::SAPLING_BINDING.mul(scalar)
+        }
+
+        #[cfg(feature = "std")]
+        #[allow(non_snake_case)]
+        fn vartime_double_base_mul(
+            b: &jubjub::Scalar,
+            a: &jubjub::Scalar,
+            A: &jubjub::ExtendedPoint,
+        ) -> jubjub::ExtendedPoint {
+            scalar_mul::jubjub_vartime_double_base_mul(
+                b,
+                &basepoint_table::SAPLING_BINDING_NAF,
+                a,
+                A,
+            )
+        }
     }
     impl Sealed<sapling::SpendAuth> for sapling::SpendAuth {
         const H_STAR_PERSONALIZATION: &'static [u8; 16] = b"Zcash_RedJubjubH";
@@ -121,5 +178,25 @@ pub(crate) mod private {
                 .unwrap()
                 .into()
         }
+
+        #[cfg(feature = "std")]
+        fn basepoint_mul(scalar: &jubjub::Scalar) -> jubjub::ExtendedPoint {
+            basepoint_table::SAPLING_SPENDAUTH.mul(scalar)
+        }
+
+        #[cfg(feature = "std")]
+        #[allow(non_snake_case)]
+        fn vartime_double_base_mul(
+            b: &jubjub::Scalar,
+            a: &jubjub::Scalar,
+            A: &jubjub::ExtendedPoint,
+        ) -> jubjub::ExtendedPoint {
+            scalar_mul::jubjub_vartime_double_base_mul(
+                b,
+                &basepoint_table::SAPLING_SPENDAUTH_NAF,
+                a,
+                A,
+            )
+        }
     }
 }
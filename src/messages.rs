@@ -1,32 +1,58 @@
 //! The FROST communication messages specified in [RFC-001]
 //!
+//! Every message is a [`Header`], naming the protocol version, sender and
+//! receiver, followed by a [`Payload`]. Messages are validated when they are
+//! constructed with [`Message::new`] and when they are decoded with
+//! [`Message::from_bytes`], and [`Message::to_bytes`] produces the canonical
+//! encoding described in the RFC, so that independent implementations
+//! interoperate byte-for-byte. Other encodings are available in [`codec`].
+//!
 //! [RFC-001]: https://github.com/ZcashFoundation/redjubjub/blob/main/rfcs/0001-messages.md
 
 use crate::{frost, signature, verification_key, SpendAuth};
-use group::GroupEncoding;
+use group::{ff::PrimeField, Curve, GroupEncoding};
 use serde::{Deserialize, Serialize};
 
 use alloc::vec::Vec;
-use std::{collections::BTreeMap, convert::TryInto};
+use core::fmt;
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
 
 #[cfg(test)]
 use proptest_derive::Arbitrary;
 
 #[cfg(test)]
 mod arbitrary;
-mod constants;
+#[cfg(feature = "secure-channel")]
+pub mod channel;
+pub mod codec;
+pub mod constants;
 mod serialize;
 #[cfg(test)]
 mod tests;
 mod validate;
 
+pub use codec::{Canonical, Decode, Encode};
+pub use validate::{MsgErr, Validate};
+
 /// Define our own `Secret` type instead of using [`frost::Secret`].
 ///
 /// The serialization design specifies that `Secret` is a [`jubjub::Scalar`] that uses:
 /// "a 32-byte little-endian canonical representation".
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+///
+/// Its `Debug` output is redacted. Unlike [`frost::Secret`] it does not show a
+/// fingerprint, since the bytes do not say which curve they belong to.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy)]
 #[cfg_attr(test, derive(Arbitrary))]
-pub struct Secret([u8; 32]);
+pub struct Secret(pub [u8; 32]);
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
 
 /// Define our own `Commitment` type instead of using [`frost::Commitment`].
 ///
@@ -34,7 +60,7 @@ pub struct Secret([u8; 32]);
 /// "a 32-byte little-endian canonical representation".
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Copy)]
 #[cfg_attr(test, derive(Arbitrary))]
-pub struct Commitment([u8; 32]);
+pub struct Commitment(pub [u8; 32]);
 
 impl<S: SpendAuth> From<frost::Commitment<S>> for Commitment {
     fn from(value: frost::Commitment<S>) -> Commitment {
@@ -51,7 +77,7 @@ impl<S: SpendAuth> From<frost::Commitment<S>> for Commitment {
 /// "a 32-byte little-endian canonical representation".
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
 #[cfg_attr(test, derive(Arbitrary))]
-pub struct GroupCommitment([u8; 32]);
+pub struct GroupCommitment(pub [u8; 32]);
 
 /// Define our own `SignatureResponse` type instead of using [`frost::SignatureResponse`].
 ///
@@ -59,7 +85,7 @@ pub struct GroupCommitment([u8; 32]);
 /// "a 32-byte little-endian canonical representation".
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
 #[cfg_attr(test, derive(Arbitrary))]
-pub struct SignatureResponse([u8; 32]);
+pub struct SignatureResponse(pub [u8; 32]);
 
 impl<S: SpendAuth> From<signature::Signature<S>> for SignatureResponse {
     fn from(value: signature::Signature<S>) -> SignatureResponse {
@@ -79,7 +105,7 @@ impl<S: SpendAuth> From<signature::Signature<S>> for GroupCommitment {
 /// "a 32-byte little-endian canonical representation".
 #[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
 #[cfg_attr(test, derive(Arbitrary))]
-pub struct VerificationKey([u8; 32]);
+pub struct VerificationKey(pub [u8; 32]);
 
 impl<S: SpendAuth> From<verification_key::VerificationKey<S>> for VerificationKey {
     fn from(value: verification_key::VerificationKey<S>) -> VerificationKey {
@@ -95,6 +121,43 @@ pub struct Message {
     payload: Payload,
 }
 
+impl Message {
+    /// Creates a new message, checking that it follows the validation rules.
+    pub fn new(header: Header, payload: Payload) -> Result<Message, MsgErr> {
+        let message = Message { header, payload };
+        message.validate()?;
+        Ok(message)
+    }
+
+    /// The header of this message.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// The payload of this message.
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    /// Serializes this message into its canonical byte encoding.
+    ///
+    /// # Panics
+    ///
+    /// If a signer [`ParticipantId`] is greater than
+    /// [`constants::MAX_SIGNER_PARTICIPANT_ID`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        Canonical::encode(self)
+    }
+
+    /// Deserializes a message from its canonical byte encoding, and checks that
+    /// it follows the validation rules.
+    ///
+    /// Encodings with trailing bytes are rejected.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Message, MsgErr> {
+        Canonical::decode(bytes)
+    }
+}
+
 /// The data required to serialize the common header fields for every message.
 ///
 /// Note: the `msg_type` is derived from the `payload` enum variant.
@@ -105,14 +168,48 @@ pub struct Header {
     receiver: ParticipantId,
 }
 
+impl Header {
+    /// Creates a header for a message from `sender` to `receiver`, using the
+    /// current message version.
+    pub fn new(sender: ParticipantId, receiver: ParticipantId) -> Result<Header, MsgErr> {
+        let header = Header {
+            version: constants::BASIC_FROST_SERIALIZATION,
+            sender,
+            receiver,
+        };
+        header.validate()?;
+        Ok(header)
+    }
+
+    /// The message version.
+    pub fn version(&self) -> MsgVersion {
+        self.version
+    }
+
+    /// The participant that sent the message.
+    pub fn sender(&self) -> ParticipantId {
+        self.sender
+    }
+
+    /// The participant the message is sent to.
+    pub fn receiver(&self) -> ParticipantId {
+        self.receiver
+    }
+}
+
 /// The data required to serialize the payload for a message.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 #[cfg_attr(test, derive(Arbitrary))]
 pub enum Payload {
+    /// A dealer's share package for a signer.
     SharePackage(SharePackage),
+    /// A signer's commitments for the first round of signing.
     SigningCommitments(SigningCommitments),
+    /// The aggregator's signing package for the second round of signing.
     SigningPackage(SigningPackage),
+    /// A signer's signature share.
     SignatureShare(SignatureShare),
+    /// The aggregator's final signature.
     AggregateSignature(AggregateSignature),
 }
 
@@ -122,6 +219,7 @@ pub enum Payload {
 #[repr(u32)]
 #[non_exhaustive]
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[allow(dead_code)]
 enum MsgType {
     SharePackage,
     SigningCommitments,
@@ -148,6 +246,10 @@ pub struct MsgVersion(u8);
 /// ID `i` will be given a share with value `f(i)`.
 /// Since a DKG may be implemented in the future, we recommend that the ID `0` be declared invalid."
 /// https://raw.githubusercontent.com/ZcashFoundation/redjubjub/main/zcash-frost-audit-report-20210323.pdf#d
+///
+/// Participant IDs are ordered by their `u64` encoding: signers in order of
+/// their index, then the dealer, then the aggregator. Coordinators can sort
+/// participants by ID to process them in a deterministic order.
 #[derive(PartialEq, Eq, Hash, PartialOrd, Debug, Copy, Clone, Ord)]
 pub enum ParticipantId {
     /// A serialized participant ID for a signer.
@@ -178,20 +280,93 @@ impl From<ParticipantId> for u64 {
 /// the [`frost::sign()`] function.
 ///
 /// Note: [`frost::SharePackage::public`] can be calculated from [`SharePackage::secret_share`].
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
 #[cfg_attr(test, derive(Arbitrary))]
 pub struct SharePackage {
     /// The public signing key that represents the entire group:
     /// [`frost::SharePackage::group_public`].
-    group_public: VerificationKey,
+    pub group_public: VerificationKey,
     /// This participant's secret key share: [`frost::SharePackage::share`].
-    secret_share: Secret,
+    pub secret_share: Secret,
     /// The commitments to the coefficients for our secret polynomial _f_,
     /// used to generate participants' key shares. Participants use these to perform
     /// verifiable secret sharing.
     /// Share packages that contain duplicate or missing [`ParticipantId`]s are invalid.
     /// [`ParticipantId`]s must be serialized in ascending numeric order.
-    share_commitment: BTreeMap<ParticipantId, Commitment>,
+    pub share_commitment: BTreeMap<ParticipantId, Commitment>,
+}
+
+impl fmt::Debug for SharePackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharePackage")
+            .field("group_public", &self.group_public)
+            .field("secret_share", &self.secret_share)
+            .field("share_commitment", &self.share_commitment)
+            .finish()
+    }
+}
+
+impl<S: SpendAuth> From<&frost::SharePackage<S>> for SharePackage {
+    fn from(value: &frost::SharePackage<S>) -> SharePackage {
+        SharePackage {
+            group_public: VerificationKey::from(value.group_public),
+            secret_share: Secret(value.share.value.0.to_repr().as_ref().try_into().unwrap()),
+            share_commitment: value
+                .share
+                .commitment
+                .0
+                .iter()
+                .enumerate()
+                .map(|(i, commitment)| {
+                    (
+                        ParticipantId::Signer(i as u64),
+                        Commitment::from(commitment.clone()),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<S: SpendAuth> TryFrom<(ParticipantId, &SharePackage)> for frost::SharePackage<S> {
+    type Error = frost::Error;
+
+    /// Decodes the share package sent to the signer `receiver`, and verifies
+    /// the share against the dealer's commitment.
+    fn try_from((receiver, value): (ParticipantId, &SharePackage)) -> Result<Self, Self::Error> {
+        let index = signer_index(receiver)?;
+        let group_public = verification_key::VerificationKey::<S>::try_from(value.group_public.0)
+            .map_err(|_| frost::Error::MalformedPublicKey)?;
+        let secret = decode_scalar::<S>(&value.secret_share.0).ok_or(frost::Error::InvalidShare)?;
+
+        let mut commitments = Vec::with_capacity(value.share_commitment.len());
+        for commitment in value.share_commitment.values() {
+            commitments.push(frost::Commitment(
+                decode_point::<S>(&commitment.0)?.to_affine(),
+            ));
+        }
+        // The commitment to the constant term of the dealer's polynomial is the
+        // group public key.
+        if commitments.first().map(|commitment| commitment.0)
+            != Some(group_public.point.to_affine())
+        {
+            return Err(frost::Error::InvalidShare);
+        }
+
+        let share = frost::Share {
+            receiver_index: index,
+            value: frost::Secret(secret),
+            commitment: frost::ShareCommitment(commitments),
+        };
+        frost::verify_share(&share)?;
+
+        Ok(frost::SharePackage {
+            group_public,
+            index,
+            public: frost::Public(S::basepoint() * secret),
+            share,
+        })
+    }
 }
 
 /// The data required to serialize [`frost::SigningCommitments`].
@@ -202,9 +377,33 @@ pub struct SharePackage {
 #[cfg_attr(test, derive(Arbitrary))]
 pub struct SigningCommitments {
     /// The hiding point: [`frost::SigningCommitments::hiding`]
-    hiding: Commitment,
+    pub hiding: Commitment,
     /// The binding point: [`frost::SigningCommitments::binding`]
-    binding: Commitment,
+    pub binding: Commitment,
+}
+
+impl<S: SpendAuth> From<&frost::SigningCommitments<S>> for SigningCommitments {
+    fn from(value: &frost::SigningCommitments<S>) -> SigningCommitments {
+        SigningCommitments {
+            hiding: Commitment(value.hiding.to_bytes().as_ref().try_into().unwrap()),
+            binding: Commitment(value.binding.to_bytes().as_ref().try_into().unwrap()),
+        }
+    }
+}
+
+impl<S: SpendAuth> TryFrom<(ParticipantId, &SigningCommitments)> for frost::SigningCommitments<S> {
+    type Error = frost::Error;
+
+    /// Decodes the signing commitments sent by the signer `sender`.
+    fn try_from(
+        (sender, value): (ParticipantId, &SigningCommitments),
+    ) -> Result<Self, Self::Error> {
+        Ok(frost::SigningCommitments {
+            index: signer_index(sender)?,
+            hiding: decode_point::<S>(&value.hiding.0)?,
+            binding: decode_point::<S>(&value.binding.0)?,
+        })
+    }
 }
 
 /// The data required to serialize [`frost::SigningPackage`].
@@ -218,14 +417,20 @@ pub struct SigningPackage {
     /// unique participant identifiers: [`frost::SigningPackage::signing_commitments`]
     ///
     /// Signing packages that contain duplicate or missing [`ParticipantId`]s are invalid.
-    signing_commitments: BTreeMap<ParticipantId, SigningCommitments>,
+    pub signing_commitments: BTreeMap<ParticipantId, SigningCommitments>,
     /// The message to be signed: [`frost::SigningPackage::message`].
     ///
     /// Each signer should perform protocol-specific verification on the message.
-    message: Vec<u8>,
+    pub message: Vec<u8>,
+    /// The signing session this package belongs to: [`frost::SigningPackage::session_id`].
+    pub session_id: [u8; 32],
 }
 
 impl<S: SpendAuth> From<SigningPackage> for frost::SigningPackage<S> {
+    /// # Panics
+    ///
+    /// If a commitment is not a valid point encoding. Use the [`TryFrom`]
+    /// conversion from `&SigningPackage` for packages received from the network.
     fn from(value: SigningPackage) -> frost::SigningPackage<S> {
         let mut signing_commitments = Vec::new();
         for (participant_id, commitment) in &value.signing_commitments {
@@ -246,12 +451,56 @@ impl<S: SpendAuth> From<SigningPackage> for frost::SigningPackage<S> {
         }
 
         frost::SigningPackage {
+            session_id: frost::SessionId::from(value.session_id),
             signing_commitments,
             message: value.message,
         }
     }
 }
 
+impl<S: SpendAuth> TryFrom<&SigningPackage> for frost::SigningPackage<S> {
+    type Error = frost::Error;
+
+    /// Decodes a signing package sent by the aggregator, rejecting commitments
+    /// that are not valid point encodings.
+    fn try_from(value: &SigningPackage) -> Result<Self, Self::Error> {
+        let signing_commitments = value
+            .signing_commitments
+            .iter()
+            .map(|(participant_id, commitment)| {
+                frost::SigningCommitments::try_from((*participant_id, commitment))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(frost::SigningPackage {
+            session_id: frost::SessionId::from(value.session_id),
+            signing_commitments,
+            message: value.message.clone(),
+        })
+    }
+}
+
+impl<S: SpendAuth> From<&frost::SigningPackage<S>> for SigningPackage {
+    fn from(value: &frost::SigningPackage<S>) -> SigningPackage {
+        SigningPackage {
+            signing_commitments: value
+                .signing_commitments
+                .iter()
+                .map(|commitment| {
+                    // Participant IDs are indexed from 0, while `frost` indices
+                    // start at 1.
+                    (
+                        ParticipantId::Signer(commitment.index - 1),
+                        SigningCommitments::from(commitment),
+                    )
+                })
+                .collect(),
+            message: value.message.clone(),
+            session_id: value.session_id.into(),
+        }
+    }
+}
+
 /// The data required to serialize [`frost::SignatureShare`].
 ///
 /// Each signer sends their signatures to the aggregator who is going to collect them
@@ -260,7 +509,30 @@ impl<S: SpendAuth> From<SigningPackage> for frost::SigningPackage<S> {
 #[cfg_attr(test, derive(Arbitrary))]
 pub struct SignatureShare {
     /// This participant's signature over the message: [`frost::SignatureShare::signature`]
-    signature: SignatureResponse,
+    pub signature: SignatureResponse,
+}
+
+impl<S: SpendAuth> From<frost::SignatureShare<S>> for SignatureShare {
+    fn from(value: frost::SignatureShare<S>) -> SignatureShare {
+        SignatureShare {
+            signature: SignatureResponse(value.signature.0.to_repr().as_ref().try_into().unwrap()),
+        }
+    }
+}
+
+impl<S: SpendAuth> TryFrom<(ParticipantId, &SignatureShare)> for frost::SignatureShare<S> {
+    type Error = frost::Error;
+
+    /// Decodes the signature share sent by the signer `sender`.
+    fn try_from((sender, value): (ParticipantId, &SignatureShare)) -> Result<Self, Self::Error> {
+        Ok(frost::SignatureShare {
+            index: signer_index(sender)?,
+            signature: frost::SignatureResponse(
+                decode_scalar::<S>(&value.signature.0)
+                    .ok_or(frost::Error::InvalidSignatureShare)?,
+            ),
+        })
+    }
 }
 
 /// The data required to serialize a successful output from [`frost::aggregate()`].
@@ -270,8 +542,47 @@ pub struct SignatureShare {
 #[cfg_attr(test, derive(Arbitrary))]
 pub struct AggregateSignature {
     /// The aggregated group commitment: [`signature::Signature::r_bytes`] returned by [`frost::aggregate()`]
-    group_commitment: GroupCommitment,
+    pub group_commitment: GroupCommitment,
     /// A plain Schnorr signature created by summing all the signature shares:
     /// [`signature::Signature::s_bytes`] returned by [`frost::aggregate()`]
-    schnorr_signature: SignatureResponse,
+    pub schnorr_signature: SignatureResponse,
+}
+
+impl<S: SpendAuth> From<signature::Signature<S>> for AggregateSignature {
+    fn from(value: signature::Signature<S>) -> AggregateSignature {
+        AggregateSignature {
+            group_commitment: GroupCommitment::from(value),
+            schnorr_signature: SignatureResponse::from(value),
+        }
+    }
+}
+
+impl<S: SpendAuth> From<&AggregateSignature> for signature::Signature<S> {
+    fn from(value: &AggregateSignature) -> signature::Signature<S> {
+        signature::Signature {
+            r_bytes: value.group_commitment.0,
+            s_bytes: value.schnorr_signature.0,
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+/// Returns the `frost` participant index of a signer.
+fn signer_index(participant: ParticipantId) -> Result<u64, frost::Error> {
+    match participant {
+        ParticipantId::Signer(_) => Ok(u64::from(participant)),
+        _ => Err(frost::Error::UnknownSigner),
+    }
+}
+
+fn decode_scalar<S: SpendAuth>(bytes: &[u8; 32]) -> Option<S::Scalar> {
+    let mut repr = <S::Scalar as PrimeField>::Repr::default();
+    repr.as_mut().copy_from_slice(bytes);
+    S::Scalar::from_repr(repr).into()
+}
+
+fn decode_point<S: SpendAuth>(bytes: &[u8; 32]) -> Result<S::Point, frost::Error> {
+    let mut repr = <S::Point as GroupEncoding>::Repr::default();
+    repr.as_mut().copy_from_slice(bytes);
+    Option::from(S::Point::from_bytes(&repr)).ok_or(frost::Error::MalformedPublicKey)
 }
@@ -0,0 +1,283 @@
+//! Authenticated encryption of messages between FROST participants.
+//!
+//! Round messages, and the secret shares in particular, must not be readable
+//! or modifiable by the transport that carries them. A [`SecureChannel`] holds
+//! a participant's long-lived identity key and the identity keys of its peers,
+//! and seals each serialized message to a single receiver.
+//!
+//! Sealing draws an ephemeral scalar \\(e\\) and derives the message key from
+//! two Diffie-Hellman shared secrets over the curve of the signature type: the
+//! ephemeral-static secret \\(e \cdot VK_{receiver}\\), which is new for every
+//! message, and the static-static secret \\(sk_{sender} \cdot
+//! VK_{receiver}\\), which only the sender and the receiver can compute and so
+//! authenticates the sender. Both are hashed with BLAKE2b, together with the
+//! participant identifiers and the ephemeral point, into a ChaCha20-Poly1305
+//! key. A sealed message is laid out as:
+//!
+//! | Bytes    | Contents                                     |
+//! |----------|----------------------------------------------|
+//! | 32       | The ephemeral point \\(e \cdot B\\)          |
+//! | the rest | Ciphertext and 16-byte authentication tag    |
+//!
+//! Every message has its own key, so the nonce is always zero. A channel does
+//! not detect replayed messages; the session identifiers of the signing
+//! protocol do.
+
+use alloc::vec::Vec;
+use std::{collections::BTreeMap, convert::TryInto};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use group::{cofactor::CofactorGroup, Group, GroupEncoding};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use super::{MsgErr, ParticipantId};
+use crate::{
+    private::SealedScalar, signing_key::SecretScalar, SigningKey, SpendAuth, VerificationKey,
+};
+
+/// The length of the ephemeral point that prefixes a sealed message.
+const EPHEMERAL_LEN: usize = 32;
+
+/// The length of the ChaCha20-Poly1305 authentication tag.
+const TAG_LEN: usize = 16;
+
+/// One participant's end of the authenticated channels to all of its peers.
+pub struct SecureChannel<S: SpendAuth> {
+    identifier: ParticipantId,
+    identity: SigningKey<S>,
+    peers: BTreeMap<ParticipantId, VerificationKey<S>>,
+}
+
+impl<S: SpendAuth> SecureChannel<S> {
+    /// Creates the channel of the participant `identifier`, whose identity key
+    /// is `identity`, to the peers in `peers`, keyed by their identifiers.
+    ///
+    /// The identity keys of the peers must be authenticated out of band, for
+    /// example when the group is set up.
+    ///
+    /// Returns [`MsgErr::InvalidIdentityKey`] if the key of a peer is the
+    /// identity or is not in the prime-order subgroup: the static-static
+    /// secret with such a key would depend on this participant's identity key
+    /// modulo the cofactor, and a peer could learn those bits of the key by
+    /// probing which messages open.
+    pub fn new(
+        identifier: ParticipantId,
+        identity: SigningKey<S>,
+        peers: BTreeMap<ParticipantId, VerificationKey<S>>,
+    ) -> Result<SecureChannel<S>, MsgErr> {
+        if peers.values().any(|key| {
+            bool::from(key.point.is_identity()) || !bool::from(key.point.is_torsion_free())
+        }) {
+            return Err(MsgErr::InvalidIdentityKey);
+        }
+
+        Ok(SecureChannel {
+            identifier,
+            identity,
+            peers,
+        })
+    }
+
+    /// The identifier of the participant this channel belongs to.
+    pub fn identifier(&self) -> ParticipantId {
+        self.identifier
+    }
+
+    /// Encrypts and authenticates `msg` so that only `receiver` can open it.
+    ///
+    /// Returns [`MsgErr::UnknownParticipant`] if `receiver` is not a peer of
+    /// this channel.
+    pub fn seal_to<R: RngCore + CryptoRng>(
+        &self,
+        receiver: ParticipantId,
+        msg: &[u8],
+        mut rng: R,
+    ) -> Result<Vec<u8>, MsgErr> {
+        let receiver_key = self
+            .peers
+            .get(&receiver)
+            .ok_or(MsgErr::UnknownParticipant)?;
+
+        let mut bytes = [0; 64];
+        rng.fill_bytes(&mut bytes);
+        let mut ephemeral = SecretScalar(S::Scalar::from_bytes_wide(&bytes));
+        bytes.zeroize();
+        let ephemeral_point = S::basepoint_mul(&ephemeral.0).to_bytes();
+
+        let cipher = self.cipher(
+            self.identifier,
+            receiver,
+            ephemeral_point.as_ref(),
+            receiver_key.point * ephemeral.0,
+            receiver_key.point,
+        );
+        ephemeral.zeroize();
+
+        let mut sealed = Vec::with_capacity(EPHEMERAL_LEN + msg.len() + TAG_LEN);
+        sealed.extend_from_slice(ephemeral_point.as_ref());
+        sealed.extend(
+            cipher
+                .encrypt(Nonce::from_slice(&[0; 12]), msg)
+                .expect("encrypting into a Vec never fails"),
+        );
+        Ok(sealed)
+    }
+
+    /// Authenticates and decrypts a message that `sender` sealed to this
+    /// channel's participant.
+    ///
+    /// Returns [`MsgErr::UnknownParticipant`] if `sender` is not a peer of
+    /// this channel, and [`MsgErr::UndecryptableMessage`] if the message was
+    /// not sealed by `sender` to this participant, or was modified.
+    ///
+    /// An ephemeral point outside of the prime-order subgroup is rejected:
+    /// otherwise a crafted message would open or not depending on the identity
+    /// key modulo the cofactor, which leaks those bits of the key.
+    pub fn open_from(&self, sender: ParticipantId, sealed: &[u8]) -> Result<Vec<u8>, MsgErr> {
+        let sender_key = self.peers.get(&sender).ok_or(MsgErr::UnknownParticipant)?;
+        if sealed.len() < EPHEMERAL_LEN + TAG_LEN {
+            return Err(MsgErr::UndecryptableMessage);
+        }
+
+        let (ephemeral_bytes, ciphertext) = sealed.split_at(EPHEMERAL_LEN);
+        let mut repr = <S::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(ephemeral_bytes);
+        let ephemeral_point: S::Point = Option::from(S::Point::from_bytes(&repr))
+            .filter(|point: &S::Point| {
+                !bool::from(point.is_identity()) && bool::from(point.is_torsion_free())
+            })
+            .ok_or(MsgErr::UndecryptableMessage)?;
+
+        let cipher = self.cipher(
+            sender,
+            self.identifier,
+            ephemeral_bytes,
+            ephemeral_point * self.identity.sk.0,
+            sender_key.point,
+        );
+        cipher
+            .decrypt(Nonce::from_slice(&[0; 12]), ciphertext)
+            .map_err(|_| MsgErr::UndecryptableMessage)
+    }
+
+    /// Derives the cipher for a message from `sender` to `receiver`, from the
+    /// ephemeral point, the ephemeral-static shared secret and the identity
+    /// key of the peer.
+    fn cipher(
+        &self,
+        sender: ParticipantId,
+        receiver: ParticipantId,
+        ephemeral_point: &[u8],
+        ephemeral_secret: S::Point,
+        peer_key: S::Point,
+    ) -> ChaCha20Poly1305 {
+        let static_secret = peer_key * self.identity.sk.0;
+        let mut ephemeral_secret = ephemeral_secret.to_bytes();
+        let mut static_secret = static_secret.to_bytes();
+
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"reddsa_FROSTChan")
+            .to_state()
+            .update(S::H_STAR_PERSONALIZATION)
+            .update(&u64::from(sender).to_le_bytes())
+            .update(&u64::from(receiver).to_le_bytes())
+            .update(ephemeral_point)
+            .update(ephemeral_secret.as_ref())
+            .update(static_secret.as_ref())
+            .finalize();
+        ephemeral_secret.as_mut().zeroize();
+        static_secret.as_mut().zeroize();
+
+        let mut key: [u8; 32] = hash.as_bytes().try_into().unwrap();
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        key.zeroize();
+        cipher
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jubjub::{AffinePoint, ExtendedPoint, Fq};
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{private::Sealed, sapling};
+
+    #[test]
+    fn ephemeral_points_with_torsion_are_rejected() {
+        let (alice, bob) = (ParticipantId::Signer(1), ParticipantId::Signer(2));
+        let alice_key = SigningKey::<sapling::SpendAuth>::new(thread_rng());
+        // An identity key that is a multiple of 4 maps the points of order 4
+        // to the identity, so adding one to the ephemeral point does not
+        // change the shared secret.
+        let bob_key = SigningKey::<sapling::SpendAuth>::from_scalar(jubjub::Scalar::from(4 * 7));
+        let channel = |identifier, identity: &SigningKey<_>, peer, peer_key: &SigningKey<_>| {
+            let peers = core::iter::once((peer, peer_key.into())).collect();
+            SecureChannel::new(identifier, identity.clone(), peers).unwrap()
+        };
+        let alice_channel = channel(alice, &alice_key, bob, &bob_key);
+        let bob_channel = channel(bob, &bob_key, alice, &alice_key);
+
+        // Alice seals a message with an ephemeral point that has a component
+        // of order 4. The encoding of (1, 0) decodes to a point of order 4.
+        let ephemeral = jubjub::Scalar::from(5);
+        let order4 = AffinePoint::from_raw_unchecked(Fq::one(), Fq::zero()).to_bytes();
+        let order4 = ExtendedPoint::from_bytes(&order4).unwrap();
+        let ephemeral_point = (sapling::SpendAuth::basepoint() * ephemeral + order4).to_bytes();
+        let bob_point = bob_key.pk.point;
+        let cipher = alice_channel.cipher(
+            alice,
+            bob,
+            &ephemeral_point,
+            bob_point * ephemeral,
+            bob_point,
+        );
+        let mut sealed = ephemeral_point.to_vec();
+        sealed.extend(
+            cipher
+                .encrypt(Nonce::from_slice(&[0; 12]), &b"probe"[..])
+                .unwrap(),
+        );
+
+        assert_eq!(
+            bob_channel.open_from(alice, &sealed),
+            Err(MsgErr::UndecryptableMessage)
+        );
+    }
+
+    #[test]
+    fn peer_keys_with_torsion_are_rejected() {
+        use core::convert::TryFrom;
+
+        let key = SigningKey::<sapling::SpendAuth>::new(thread_rng());
+        let order4 = AffinePoint::from_raw_unchecked(Fq::one(), Fq::zero()).to_bytes();
+        let order4 = ExtendedPoint::from_bytes(&order4).unwrap();
+        let peer_key = |point: ExtendedPoint| {
+            let peer: VerificationKey<sapling::SpendAuth> =
+                VerificationKey::try_from(point.to_bytes()).unwrap();
+            core::iter::once((ParticipantId::Signer(2), peer)).collect()
+        };
+
+        // A peer key m·B + T4 would let the peer probe the receiver's
+        // identity key modulo 4 through the static-static secret.
+        let torsion = sapling::SpendAuth::basepoint() * jubjub::Scalar::from(5) + order4;
+        assert_eq!(
+            SecureChannel::new(ParticipantId::Signer(1), key.clone(), peer_key(torsion)).err(),
+            Some(MsgErr::InvalidIdentityKey)
+        );
+        assert_eq!(
+            SecureChannel::new(
+                ParticipantId::Signer(1),
+                key,
+                peer_key(ExtendedPoint::identity())
+            )
+            .err(),
+            Some(MsgErr::InvalidIdentityKey)
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Encodings of the FROST messages.
+//!
+//! A backend implements [`Encode`] and [`Decode`] for the message types it
+//! supports. [`Canonical`] is the byte encoding of [RFC-001], which
+//! [`Message::to_bytes`] and [`Message::from_bytes`] use; `Json` (with the
+//! `json-codec` feature) suits human-readable configuration and logs, and
+//! `Postcard` (with the `postcard-codec` feature) is a compact encoding for
+//! embedded links. Decoding always checks the validation rules, whatever the
+//! backend.
+//!
+//! [RFC-001]: https://github.com/ZcashFoundation/redjubjub/blob/main/rfcs/0001-messages.md
+
+use bincode::Options;
+use serde::de::DeserializeOwned;
+
+use super::*;
+
+/// A backend that can encode values of type `T`.
+pub trait Encode<T> {
+    /// Encodes `value` into bytes.
+    fn encode(value: &T) -> Vec<u8>;
+}
+
+/// A backend that can decode values of type `T`.
+pub trait Decode<T> {
+    /// Decodes a value from `bytes`, and checks that it follows the validation
+    /// rules.
+    ///
+    /// Encodings with trailing bytes are rejected.
+    fn decode(bytes: &[u8]) -> Result<T, MsgErr>;
+}
+
+/// The canonical byte encoding of [RFC-001].
+///
+/// [RFC-001]: https://github.com/ZcashFoundation/redjubjub/blob/main/rfcs/0001-messages.md
+#[derive(Copy, Clone, Debug)]
+pub struct Canonical;
+
+impl<T: Serialize> Encode<T> for Canonical {
+    /// # Panics
+    ///
+    /// If a signer [`ParticipantId`] is greater than
+    /// [`constants::MAX_SIGNER_PARTICIPANT_ID`].
+    fn encode(value: &T) -> Vec<u8> {
+        bincode::serialize(value).expect("serializing into a Vec never fails")
+    }
+}
+
+impl<T: DeserializeOwned + Validate> Decode<T> for Canonical {
+    fn decode(bytes: &[u8]) -> Result<T, MsgErr> {
+        let value: T = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .reject_trailing_bytes()
+            .deserialize(bytes)
+            .map_err(|_| MsgErr::MalformedMessage)?;
+        value.validate()?;
+        Ok(value)
+    }
+}
+
+/// A JSON encoding, for human-readable configuration and logs.
+#[cfg(feature = "json-codec")]
+#[derive(Copy, Clone, Debug)]
+pub struct Json;
+
+#[cfg(feature = "json-codec")]
+impl<T: Serialize> Encode<T> for Json {
+    fn encode(value: &T) -> Vec<u8> {
+        serde_json::to_vec(value).expect("serializing into a Vec never fails")
+    }
+}
+
+#[cfg(feature = "json-codec")]
+impl<T: DeserializeOwned + Validate> Decode<T> for Json {
+    fn decode(bytes: &[u8]) -> Result<T, MsgErr> {
+        let value: T = serde_json::from_slice(bytes).map_err(|_| MsgErr::MalformedMessage)?;
+        value.validate()?;
+        Ok(value)
+    }
+}
+
+/// The [postcard](https://docs.rs/postcard) encoding, a compact encoding with
+/// variable-length integers for embedded links.
+#[cfg(feature = "postcard-codec")]
+#[derive(Copy, Clone, Debug)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard-codec")]
+impl<T: Serialize> Encode<T> for Postcard {
+    fn encode(value: &T) -> Vec<u8> {
+        postcard::to_allocvec(value).expect("serializing into a Vec never fails")
+    }
+}
+
+#[cfg(feature = "postcard-codec")]
+impl<T: DeserializeOwned + Validate> Decode<T> for Postcard {
+    fn decode(bytes: &[u8]) -> Result<T, MsgErr> {
+        match postcard::take_from_bytes::<T>(bytes) {
+            Ok((value, [])) => {
+                value.validate()?;
+                Ok(value)
+            }
+            _ => Err(MsgErr::MalformedMessage),
+        }
+    }
+}
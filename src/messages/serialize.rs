@@ -38,9 +38,7 @@ impl<'de> Visitor<'de> for ParticipantIdVisitor {
     type Value = ParticipantId;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str(
-            format!("an integer between {} and {}", std::u64::MIN, std::u64::MAX).as_str(),
-        )
+        formatter.write_str(format!("an integer between {} and {}", u64::MIN, u64::MAX).as_str())
     }
 
     fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
@@ -49,11 +47,11 @@ impl<'de> Visitor<'de> for ParticipantIdVisitor {
     {
         // Note: deserialization can't fail, because all values are valid.
         if value == DEALER_PARTICIPANT_ID {
-            return Ok(ParticipantId::Dealer);
+            Ok(ParticipantId::Dealer)
         } else if value == AGGREGATOR_PARTICIPANT_ID {
-            return Ok(ParticipantId::Aggregator);
+            Ok(ParticipantId::Aggregator)
         } else {
-            return Ok(ParticipantId::Signer(value));
+            Ok(ParticipantId::Signer(value))
         }
     }
 }
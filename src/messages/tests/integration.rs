@@ -73,8 +73,8 @@ fn validate_sharepackage() {
 
     let payload = Payload::SharePackage(SharePackage {
         group_public,
-        secret_share: secret_share,
-        share_commitment: share_commitment,
+        secret_share,
+        share_commitment,
     });
     let validate_payload = Validate::validate(&payload);
     let valid_payload = validate_payload.expect("a valid payload").clone();
@@ -105,7 +105,7 @@ fn validate_sharepackage() {
     // change the payload to have only 1 commitment
     let payload = Payload::SharePackage(SharePackage {
         group_public,
-        secret_share: secret_share,
+        secret_share,
         share_commitment: share_commitment.clone(),
     });
     let validate_payload = Validate::validate(&payload);
@@ -159,7 +159,7 @@ fn serialize_sharepackage() {
     });
 
     let message = Message {
-        header: header,
+        header,
         payload: payload.clone(),
     };
 
@@ -174,8 +174,7 @@ fn serialize_sharepackage() {
     assert_eq!(deserialized_msg_type, MsgType::SharePackage);
 
     // remove the msg_type from the the payload
-    payload_serialized_bytes =
-        (&payload_serialized_bytes[4..payload_serialized_bytes.len()]).to_vec();
+    payload_serialized_bytes = payload_serialized_bytes[4..payload_serialized_bytes.len()].to_vec();
 
     // group_public is 32 bytes
     let deserialized_group_public: VerificationKey =
@@ -256,7 +255,7 @@ fn serialize_signingcommitments() {
     let payload = Payload::SigningCommitments(SigningCommitments { hiding, binding });
 
     let message = Message {
-        header: header,
+        header,
         payload: payload.clone(),
     };
 
@@ -271,8 +270,7 @@ fn serialize_signingcommitments() {
     assert_eq!(deserialized_msg_type, MsgType::SigningCommitments);
 
     // remove the msg_type from the the payload
-    payload_serialized_bytes =
-        (&payload_serialized_bytes[4..payload_serialized_bytes.len()]).to_vec();
+    payload_serialized_bytes = payload_serialized_bytes[4..payload_serialized_bytes.len()].to_vec();
 
     // hiding is 32 bytes
     let deserialized_hiding: Commitment =
@@ -305,6 +303,7 @@ fn validate_signingpackage() {
     let payload = Payload::SigningPackage(SigningPackage {
         signing_commitments: signing_commitments.clone(),
         message: "hola".as_bytes().to_vec(),
+        session_id: [0; 32],
     });
     let validate_payload = Validate::validate(&payload);
     assert_eq!(
@@ -323,6 +322,7 @@ fn validate_signingpackage() {
     let payload = Payload::SigningPackage(SigningPackage {
         signing_commitments: big_signing_commitments,
         message: "hola".as_bytes().to_vec(),
+        session_id: [0; 32],
     });
     let validate_payload = Validate::validate(&payload);
     assert_eq!(validate_payload, Err(MsgErr::TooManyCommitments));
@@ -336,6 +336,7 @@ fn validate_signingpackage() {
     let payload = Payload::SigningPackage(SigningPackage {
         signing_commitments: signing_commitments.clone(),
         message: big_message,
+        session_id: [0; 32],
     });
     let validate_payload = Validate::validate(&payload);
     assert_eq!(validate_payload, Err(MsgErr::MsgTooBig));
@@ -351,7 +352,7 @@ fn validate_signingpackage() {
     let header = create_valid_header(setup.aggregator, setup.dealer);
 
     let message = Message {
-        header: header,
+        header,
         payload: payload.clone(),
     };
 
@@ -362,6 +363,7 @@ fn validate_signingpackage() {
     let payload = Payload::SigningPackage(SigningPackage {
         signing_commitments,
         message: "hola".as_bytes().to_vec(),
+        session_id: [0; 32],
     });
 
     let validate_message = Validate::validate(&Message { header, payload }).err();
@@ -384,10 +386,11 @@ fn serialize_signingpackage() {
     let payload = Payload::SigningPackage(SigningPackage {
         signing_commitments: signing_commitments.clone(),
         message: "hola".as_bytes().to_vec(),
+        session_id: [0; 32],
     });
 
     let message = Message {
-        header: header,
+        header,
         payload: payload.clone(),
     };
 
@@ -403,8 +406,7 @@ fn serialize_signingpackage() {
     assert_eq!(deserialized_msg_type, MsgType::SigningPackage);
 
     // remove the msg_type from the the payload
-    payload_serialized_bytes =
-        (&payload_serialized_bytes[4..payload_serialized_bytes.len()]).to_vec();
+    payload_serialized_bytes = payload_serialized_bytes[4..payload_serialized_bytes.len()].to_vec();
 
     // check the map len
     let deserialized_map_len: u64 = bincode::deserialize(&payload_serialized_bytes[0..8]).unwrap();
@@ -415,16 +417,20 @@ fn serialize_signingpackage() {
     let deserialized_signing_commitments: BTreeMap<ParticipantId, SigningCommitments> =
         bincode::deserialize(&payload_serialized_bytes[0..152]).unwrap();
 
-    // Message is from the end of the map up to the end of the message.
+    // Message is from the end of the map up to the session ID.
     let deserialized_message: Vec<u8> =
-        bincode::deserialize(&payload_serialized_bytes[152..payload_serialized_bytes.len()])
-            .unwrap();
+        bincode::deserialize(&payload_serialized_bytes[152..164]).unwrap();
+
+    // The session ID is the last 32 bytes.
+    let deserialized_session_id: [u8; 32] =
+        bincode::deserialize(&payload_serialized_bytes[164..196]).unwrap();
 
     // no leftover bytes
-    assert_eq!(payload_serialized_bytes.len(), 164);
+    assert_eq!(payload_serialized_bytes.len(), 196);
 
     assert_eq!(deserialized_signing_commitments, signing_commitments);
     assert_eq!(deserialized_message, "hola".as_bytes().to_vec());
+    assert_eq!(deserialized_session_id, [0; 32]);
 }
 
 #[test]
@@ -446,10 +452,17 @@ fn validate_signatureshare() {
     let signing_package = frost::SigningPackage::from(SigningPackage {
         signing_commitments: signing_commitments.clone(),
         message: "hola".as_bytes().to_vec(),
+        session_id: [0; 32],
     });
 
     // here we get started with the `SignatureShare` message.
-    let signature_share = frost::sign(&signing_package, nonce1[0], &shares[0]).unwrap();
+    let signature_share = frost::sign(
+        &signing_package,
+        frost::SessionId::from([0; 32]),
+        nonce1[0],
+        &shares[0],
+    )
+    .unwrap();
 
     // this header is invalid
     let header = create_valid_header(setup.aggregator, setup.signer1);
@@ -504,10 +517,17 @@ fn serialize_signatureshare() {
     let signing_package = frost::SigningPackage::from(SigningPackage {
         signing_commitments: signing_commitments.clone(),
         message: "hola".as_bytes().to_vec(),
+        session_id: [0; 32],
     });
 
     // here we get started with the `SignatureShare` message.
-    let signature_share = frost::sign(&signing_package, nonce1[0], &shares[0]).unwrap();
+    let signature_share = frost::sign(
+        &signing_package,
+        frost::SessionId::from([0; 32]),
+        nonce1[0],
+        &shares[0],
+    )
+    .unwrap();
 
     // valid header
     let header = create_valid_header(setup.signer1, setup.aggregator);
@@ -516,7 +536,7 @@ fn serialize_signatureshare() {
     let payload = Payload::SignatureShare(SignatureShare { signature });
 
     let message = Message {
-        header: header,
+        header,
         payload: payload.clone(),
     };
 
@@ -532,8 +552,7 @@ fn serialize_signatureshare() {
     assert_eq!(deserialized_msg_type, MsgType::SignatureShare);
 
     // remove the msg_type from the the payload
-    payload_serialized_bytes =
-        (&payload_serialized_bytes[4..payload_serialized_bytes.len()]).to_vec();
+    payload_serialized_bytes = payload_serialized_bytes[4..payload_serialized_bytes.len()].to_vec();
 
     // signature is 32 bytes
     let deserialized_signature: SignatureResponse =
@@ -614,8 +633,7 @@ fn serialize_aggregatesignature() {
     assert_eq!(deserialized_msg_type, MsgType::AggregateSignature);
 
     // remove the msg_type from the the payload
-    payload_serialized_bytes =
-        (&payload_serialized_bytes[4..payload_serialized_bytes.len()]).to_vec();
+    payload_serialized_bytes = payload_serialized_bytes[4..payload_serialized_bytes.len()].to_vec();
 
     // group_commitment is 32 bytes
     let deserialized_group_commiment: GroupCommitment =
@@ -661,16 +679,247 @@ fn btreemap() {
     assert_eq!(map.len(), 3);
 }
 
+#[test]
+fn message_bytes_roundtrip() {
+    let (setup, group_signature_res) = full_setup();
+
+    let header = Header::new(setup.aggregator, setup.signer1).unwrap();
+    let payload = Payload::AggregateSignature(AggregateSignature::from(group_signature_res));
+    let message = Message::new(header, payload).unwrap();
+
+    let bytes = message.to_bytes();
+    assert_eq!(bytes, bincode::serialize(&message).unwrap());
+    assert_eq!(Message::from_bytes(&bytes), Ok(message));
+
+    // trailing bytes are rejected
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        Message::from_bytes(&trailing),
+        Err(MsgErr::MalformedMessage)
+    );
+
+    // truncated messages are rejected
+    assert_eq!(
+        Message::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(MsgErr::MalformedMessage)
+    );
+
+    // decoded messages are validated
+    let invalid = Message {
+        header: create_valid_header(setup.signer1, setup.signer2),
+        payload: Payload::AggregateSignature(AggregateSignature::from(group_signature_res)),
+    };
+    assert_eq!(
+        Message::from_bytes(&bincode::serialize(&invalid).unwrap()),
+        Err(MsgErr::SenderMustBeAggregator)
+    );
+    assert_eq!(
+        Message::new(invalid.header, invalid.payload),
+        Err(MsgErr::SenderMustBeAggregator)
+    );
+}
+
+#[test]
+fn message_codec_roundtrip() {
+    let (setup, group_signature_res) = full_setup();
+
+    let header = Header::new(setup.aggregator, setup.signer1).unwrap();
+    let payload = Payload::AggregateSignature(AggregateSignature::from(group_signature_res));
+    let message = Message::new(header, payload).unwrap();
+    let invalid = Message {
+        header: create_valid_header(setup.signer1, setup.signer2),
+        payload: message.payload.clone(),
+    };
+
+    fn check<B: Encode<Message> + Decode<Message>>(message: &Message, invalid: &Message) {
+        let bytes = B::encode(message);
+        assert_eq!(B::decode(&bytes).as_ref(), Ok(message));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(B::decode(&trailing), Err(MsgErr::MalformedMessage));
+
+        assert_eq!(
+            B::decode(&B::encode(invalid)),
+            Err(MsgErr::SenderMustBeAggregator)
+        );
+    }
+
+    assert_eq!(Canonical::encode(&message), message.to_bytes());
+    check::<Canonical>(&message, &invalid);
+    #[cfg(feature = "json-codec")]
+    check::<codec::Json>(&message, &invalid);
+    #[cfg(feature = "postcard-codec")]
+    check::<codec::Postcard>(&message, &invalid);
+}
+
+#[cfg(feature = "secure-channel")]
+#[test]
+fn secure_channel_roundtrip() {
+    use crate::SigningKey;
+    use channel::SecureChannel;
+    use std::collections::BTreeMap;
+
+    let mut rng = thread_rng();
+    let (setup, group_signature_res) = full_setup();
+    let ids = [setup.aggregator, setup.signer1, setup.signer2];
+    let keys: Vec<SigningKey<sapling::SpendAuth>> =
+        ids.iter().map(|_| SigningKey::new(&mut rng)).collect();
+    let channels: Vec<_> = ids
+        .iter()
+        .zip(keys.iter())
+        .map(|(id, key)| {
+            let peers: BTreeMap<_, _> = ids
+                .iter()
+                .zip(keys.iter())
+                .filter(|(peer, _)| *peer != id)
+                .map(|(peer, key)| (*peer, key.into()))
+                .collect();
+            SecureChannel::new(*id, key.clone(), peers).unwrap()
+        })
+        .collect();
+    let (aggregator, signer1, signer2) = (&channels[0], &channels[1], &channels[2]);
+
+    let header = Header::new(setup.aggregator, setup.signer1).unwrap();
+    let payload = Payload::AggregateSignature(AggregateSignature::from(group_signature_res));
+    let message = Message::new(header, payload).unwrap();
+    let sealed = aggregator
+        .seal_to(setup.signer1, &message.to_bytes(), &mut rng)
+        .unwrap();
+
+    let opened = signer1.open_from(setup.aggregator, &sealed).unwrap();
+    assert_eq!(Message::from_bytes(&opened), Ok(message));
+
+    // Only the receiver can open the message, and only as coming from its
+    // sender.
+    assert_eq!(
+        signer2.open_from(setup.aggregator, &sealed),
+        Err(MsgErr::UndecryptableMessage)
+    );
+    assert_eq!(
+        signer1.open_from(setup.signer2, &sealed),
+        Err(MsgErr::UndecryptableMessage)
+    );
+    assert_eq!(
+        signer1.open_from(setup.signer1, &sealed),
+        Err(MsgErr::UnknownParticipant)
+    );
+
+    // Modified messages are rejected.
+    for i in [0, 40, sealed.len() - 1] {
+        let mut tampered = sealed.clone();
+        tampered[i] ^= 1;
+        assert_eq!(
+            signer1.open_from(setup.aggregator, &tampered),
+            Err(MsgErr::UndecryptableMessage)
+        );
+    }
+    assert_eq!(
+        signer1.open_from(setup.aggregator, &sealed[..40]),
+        Err(MsgErr::UndecryptableMessage)
+    );
+}
+
+#[test]
+fn frost_conversions_roundtrip() {
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+
+    for share in &shares {
+        let receiver = ParticipantId::Signer(share.index - 1);
+        let message = SharePackage::from(share);
+        let decoded =
+            frost::SharePackage::<sapling::SpendAuth>::try_from((receiver, &message)).unwrap();
+        assert_eq!(decoded.index, share.index);
+        assert_eq!(SharePackage::from(&decoded), message);
+
+        // The share does not verify for another participant.
+        let other = ParticipantId::Signer(share.index % 3);
+        assert_eq!(
+            frost::SharePackage::<sapling::SpendAuth>::try_from((other, &message)).err(),
+            Some(frost::Error::InvalidShare)
+        );
+        assert_eq!(
+            frost::SharePackage::<sapling::SpendAuth>::try_from((ParticipantId::Dealer, &message))
+                .err(),
+            Some(frost::Error::UnknownSigner)
+        );
+    }
+
+    let session_id = frost::SessionId::new(&mut rng);
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for share in &shares[..2] {
+        let (nonce, commitment) = frost::preprocess(1, share.index, &mut rng);
+        let sender = ParticipantId::Signer(share.index - 1);
+        let decoded = frost::SigningCommitments::<sapling::SpendAuth>::try_from((
+            sender,
+            &SigningCommitments::from(&commitment[0]),
+        ))
+        .unwrap();
+        assert_eq!(
+            SigningCommitments::from(&decoded),
+            SigningCommitments::from(&commitment[0])
+        );
+        nonces.push(nonce[0]);
+        commitments.push(decoded);
+    }
+
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, b"message".to_vec()).unwrap();
+    let decoded_package = frost::SigningPackage::<sapling::SpendAuth>::try_from(
+        &SigningPackage::from(&signing_package),
+    )
+    .unwrap();
+    assert_eq!(
+        SigningPackage::from(&decoded_package),
+        SigningPackage::from(&signing_package)
+    );
+
+    // Invalid commitments are rejected rather than unwrapped.
+    let mut invalid_package = SigningPackage::from(&signing_package);
+    for commitment in invalid_package.signing_commitments.values_mut() {
+        commitment.binding = Commitment([0xff; 32]);
+    }
+    assert_eq!(
+        frost::SigningPackage::<sapling::SpendAuth>::try_from(&invalid_package).err(),
+        Some(frost::Error::MalformedPublicKey)
+    );
+
+    let mut signature_shares = Vec::new();
+    for (share, nonce) in shares[..2].iter().zip(nonces) {
+        let signature_share = frost::sign(&decoded_package, session_id, nonce, share).unwrap();
+        let sender = ParticipantId::Signer(share.index - 1);
+        signature_shares.push(
+            frost::SignatureShare::<sapling::SpendAuth>::try_from((
+                sender,
+                &SignatureShare::from(signature_share),
+            ))
+            .unwrap(),
+        );
+    }
+
+    let group_signature = frost::aggregate(&signing_package, &signature_shares, &pubkeys).unwrap();
+    let decoded_signature = signature::Signature::<sapling::SpendAuth>::from(
+        &AggregateSignature::from(group_signature),
+    );
+    assert!(pubkeys
+        .group_public
+        .verify(b"message", &decoded_signature)
+        .is_ok());
+}
+
 // utility functions
 
 fn create_valid_header(sender: ParticipantId, receiver: ParticipantId) -> Header {
-    Validate::validate(&Header {
+    *Validate::validate(&Header {
         version: constants::BASIC_FROST_SERIALIZATION,
-        sender: sender,
-        receiver: receiver,
+        sender,
+        receiver,
     })
     .expect("always a valid header")
-    .clone()
 }
 
 fn serialize_header(
@@ -757,7 +1006,9 @@ fn full_setup() -> (Setup, signature::Signature<sapling::SpendAuth>) {
     let mut signature_shares: Vec<frost::SignatureShare<sapling::SpendAuth>> =
         Vec::with_capacity(setup.threshold as usize);
     let message = "message to sign".as_bytes().to_vec();
+    let session_id = frost::SessionId::new(&mut setup.rng);
     let signing_package = frost::SigningPackage {
+        session_id,
         message: message.clone(),
         signing_commitments: commitments,
     };
@@ -769,7 +1020,8 @@ fn full_setup() -> (Setup, signature::Signature<sapling::SpendAuth>) {
             .find(|share| participant_index == share.index)
             .unwrap();
         let nonce_to_use = nonce[0];
-        let signature_share = frost::sign(&signing_package, nonce_to_use, share_package).unwrap();
+        let signature_share =
+            frost::sign(&signing_package, session_id, nonce_to_use, share_package).unwrap();
         signature_shares.push(signature_share);
     }
 
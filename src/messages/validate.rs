@@ -10,7 +10,12 @@ use super::*;
 
 use thiserror::Error;
 
+/// Checks the validation rules of [RFC-001#rules] for a message or one of
+/// its parts.
+///
+/// [RFC-001#rules]: https://github.com/ZcashFoundation/redjubjub/blob/main/rfcs/0001-messages.md#rules
 pub trait Validate {
+    /// Returns `self` if it is valid, or the broken rule otherwise.
     fn validate(&self) -> Result<&Self, MsgErr>;
 }
 
@@ -117,27 +122,51 @@ impl Validate for Payload {
 /// The error a message can produce if it fails validation.
 #[derive(Error, Debug, PartialEq)]
 pub enum MsgErr {
+    /// The message version is not supported.
     #[error("wrong version number")]
     WrongVersion,
+    /// The sender and the receiver are the same participant.
     #[error("sender and receiver are the same")]
     SameSenderAndReceiver,
+    /// The message type can only be sent by the dealer.
     #[error("the sender of this message must be the dealer")]
     SenderMustBeDealer,
+    /// The message type can only be sent to a signer.
     #[error("the receiver of this message must be a signer")]
     ReceiverMustBeSigner,
+    /// The message type can only be sent by a signer.
     #[error("the sender of this message must be a signer")]
     SenderMustBeSigner,
+    /// The message type can only be sent to the aggregator.
     #[error("the receiver of this message must be the aggregator")]
     ReceiverMustBeAggregator,
+    /// The message type can only be sent by the aggregator.
     #[error("the sender of this message must be the aggregator")]
     SenderMustBeAggregator,
+    /// The payload contains fewer commitments than the given minimum.
     #[error("the number of signers must be at least {0}")]
     NotEnoughCommitments(usize),
+    /// The payload contains more commitments than [`MAX_SIGNERS`].
     #[error("the number of signers can't be more than {}", MAX_SIGNERS)]
     TooManyCommitments,
+    /// The message to be signed is longer than [`ZCASH_MAX_PROTOCOL_MESSAGE_LEN`].
     #[error(
         "the message field can't be bigger than {}",
         ZCASH_MAX_PROTOCOL_MESSAGE_LEN
     )]
     MsgTooBig,
+    /// The bytes are not a canonical encoding of a message.
+    #[error("malformed message encoding")]
+    MalformedMessage,
+    /// The participant is not a peer of the secure channel.
+    #[error("unknown participant")]
+    UnknownParticipant,
+    /// A sealed message was not sealed by the claimed sender to this
+    /// participant, or was modified.
+    #[error("could not decrypt sealed message")]
+    UndecryptableMessage,
+    /// The identity key of a peer of the secure channel is the identity, or
+    /// has a component of small order.
+    #[error("invalid peer identity key")]
+    InvalidIdentityKey,
 }
@@ -8,21 +8,30 @@ use core::borrow::Borrow;
 use group::GroupEncoding;
 #[cfg(feature = "alloc")]
 use group::{ff::PrimeField, Group};
+#[cfg(feature = "alloc")]
+use pasta_curves::arithmetic::CurveExt;
 use pasta_curves::pallas;
 
 use crate::{private, SigType};
 
+#[cfg(feature = "std")]
+use crate::scalar_mul::LookupTable8;
 #[cfg(feature = "alloc")]
-use crate::scalar_mul::{LookupTable5, NonAdjacentForm, VartimeMultiscalarMul};
+use crate::scalar_mul::{
+    le_bytes_non_adjacent_form, naf_top, pallas_glv_decompose, LookupTable5, NonAdjacentForm,
+    VartimeMultiscalarMul,
+};
+
+pub(crate) mod complete;
 
 /// The byte-encoding of the basepoint for `OrchardSpendAuthSig`.
-const ORCHARD_SPENDAUTHSIG_BASEPOINT_BYTES: [u8; 32] = [
+pub const SPENDAUTHSIG_BASEPOINT_BYTES: [u8; 32] = [
     99, 201, 117, 184, 132, 114, 26, 141, 12, 161, 112, 123, 227, 12, 127, 12, 95, 68, 95, 62, 124,
     24, 141, 59, 6, 214, 241, 40, 179, 35, 85, 183,
 ];
 
 /// The byte-encoding of the basepoint for `OrchardBindingSig`.
-const ORCHARD_BINDINGSIG_BASEPOINT_BYTES: [u8; 32] = [
+pub const BINDINGSIG_BASEPOINT_BYTES: [u8; 32] = [
     145, 90, 60, 136, 104, 198, 195, 14, 47, 128, 144, 238, 69, 215, 110, 64, 72, 32, 141, 234, 91,
     35, 102, 79, 187, 9, 164, 15, 85, 68, 244, 7,
 ];
@@ -36,7 +45,9 @@ impl Default for SpendAuth {
         unimplemented!()
     }
 }
-impl SigType for SpendAuth {}
+impl SigType for SpendAuth {
+    const BASEPOINT_BYTES: [u8; 32] = SPENDAUTHSIG_BASEPOINT_BYTES;
+}
 impl super::SpendAuth for SpendAuth {}
 
 /// A type variable corresponding to Zcash's `OrchardBindingSig`.
@@ -48,7 +59,9 @@ impl Default for Binding {
         unimplemented!()
     }
 }
-impl SigType for Binding {}
+impl SigType for Binding {
+    const BASEPOINT_BYTES: [u8; 32] = BINDINGSIG_BASEPOINT_BYTES;
+}
 impl super::Binding for Binding {}
 
 impl private::SealedScalar for pallas::Scalar {
@@ -65,7 +78,27 @@ impl private::Sealed<SpendAuth> for SpendAuth {
     type Scalar = pallas::Scalar;
 
     fn basepoint() -> pallas::Point {
-        pallas::Point::from_bytes(&ORCHARD_SPENDAUTHSIG_BASEPOINT_BYTES).unwrap()
+        pallas::Point::from_bytes(&SPENDAUTHSIG_BASEPOINT_BYTES).unwrap()
+    }
+
+    #[cfg(feature = "std")]
+    fn basepoint_mul(scalar: &pallas::Scalar) -> pallas::Point {
+        crate::basepoint_table::ORCHARD_SPENDAUTH.mul_pallas(scalar)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn basepoint_mul(scalar: &pallas::Scalar) -> pallas::Point {
+        complete::mul(&pallas::Affine::from(Self::basepoint()), scalar)
+    }
+
+    #[cfg(feature = "std")]
+    #[allow(non_snake_case)]
+    fn vartime_double_base_mul(
+        b: &pallas::Scalar,
+        a: &pallas::Scalar,
+        A: &pallas::Point,
+    ) -> pallas::Point {
+        pallas_vartime_double_base_mul(b, &crate::basepoint_table::ORCHARD_SPENDAUTH_NAF, a, A)
     }
 }
 impl private::Sealed<Binding> for Binding {
@@ -74,70 +107,34 @@ impl private::Sealed<Binding> for Binding {
     type Scalar = pallas::Scalar;
 
     fn basepoint() -> pallas::Point {
-        pallas::Point::from_bytes(&ORCHARD_BINDINGSIG_BASEPOINT_BYTES).unwrap()
+        pallas::Point::from_bytes(&BINDINGSIG_BASEPOINT_BYTES).unwrap()
+    }
+
+    #[cfg(feature = "std")]
+    fn basepoint_mul(scalar: &pallas::Scalar) -> pallas::Point {
+        crate::basepoint_table::ORCHARD_BINDING.mul_pallas(scalar)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn basepoint_mul(scalar: &pallas::Scalar) -> pallas::Point {
+        complete::mul(&pallas::Affine::from(Self::basepoint()), scalar)
+    }
+
+    #[cfg(feature = "std")]
+    #[allow(non_snake_case)]
+    fn vartime_double_base_mul(
+        b: &pallas::Scalar,
+        a: &pallas::Scalar,
+        A: &pallas::Point,
+    ) -> pallas::Point {
+        pallas_vartime_double_base_mul(b, &crate::basepoint_table::ORCHARD_BINDING_NAF, a, A)
     }
 }
 
 #[cfg(feature = "alloc")]
 impl NonAdjacentForm for pallas::Scalar {
-    /// Compute a width-\\(w\\) "Non-Adjacent Form" of this scalar.
-    ///
-    /// Thanks to curve25519-dalek
     fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
-        // required by the NAF definition
-        debug_assert!(w >= 2);
-        // required so that the NAF digits fit in i8
-        debug_assert!(w <= 8);
-
-        use byteorder::{ByteOrder, LittleEndian};
-
-        let mut naf = [0i8; 256];
-
-        let mut x_u64 = [0u64; 5];
-        LittleEndian::read_u64_into(&self.to_repr().as_ref(), &mut x_u64[0..4]);
-
-        let width = 1 << w;
-        let window_mask = width - 1;
-
-        let mut pos = 0;
-        let mut carry = 0;
-        while pos < 256 {
-            // Construct a buffer of bits of the scalar, starting at bit `pos`
-            let u64_idx = pos / 64;
-            let bit_idx = pos % 64;
-            let bit_buf: u64;
-            if bit_idx < 64 - w {
-                // This window's bits are contained in a single u64
-                bit_buf = x_u64[u64_idx] >> bit_idx;
-            } else {
-                // Combine the current u64's bits with the bits from the next u64
-                bit_buf = (x_u64[u64_idx] >> bit_idx) | (x_u64[1 + u64_idx] << (64 - bit_idx));
-            }
-
-            // Add the carry into the current window
-            let window = carry + (bit_buf & window_mask);
-
-            if window & 1 == 0 {
-                // If the window value is even, preserve the carry and continue.
-                // Why is the carry preserved?
-                // If carry == 0 and window & 1 == 0, then the next carry should be 0
-                // If carry == 1 and window & 1 == 0, then bit_buf & 1 == 1 so the next carry should be 1
-                pos += 1;
-                continue;
-            }
-
-            if window < width / 2 {
-                carry = 0;
-                naf[pos] = window as i8;
-            } else {
-                carry = 1;
-                naf[pos] = (window as i8).wrapping_sub(width as i8);
-            }
-
-            pos += w;
-        }
-
-        naf
+        le_bytes_non_adjacent_form(self.to_repr().as_ref(), w)
     }
 }
 
@@ -148,13 +145,95 @@ impl<'a> From<&'a pallas::Point> for LookupTable5<pallas::Point> {
         let mut Ai = [*A; 8];
         let A2 = A.double();
         for i in 0..7 {
-            Ai[i + 1] = &A2 + Ai[i];
+            Ai[i + 1] = A2 + Ai[i];
         }
         // Now Ai = [A, 3A, 5A, 7A, 9A, 11A, 13A, 15A]
         LookupTable5(Ai)
     }
 }
 
+#[cfg(feature = "std")]
+impl<'a> From<&'a pallas::Point> for LookupTable8<pallas::Point> {
+    #[allow(non_snake_case)]
+    fn from(A: &'a pallas::Point) -> Self {
+        let mut Ai = [*A; 64];
+        let A2 = A.double();
+        for i in 0..63 {
+            Ai[i + 1] = A2 + Ai[i];
+        }
+        // Now Ai = [A, 3A, 5A, ..., 127A]
+        LookupTable8(Ai)
+    }
+}
+
+/// Return the width-\\(w\\) NAF of one half of a GLV decomposition, with the
+/// sign of the half folded into the digits.
+#[cfg(feature = "std")]
+fn signed_naf((negative, k): (bool, pallas::Scalar), w: usize) -> [i8; 256] {
+    let mut naf = k.non_adjacent_form(w);
+    if negative {
+        for digit in naf.iter_mut() {
+            *digit = -*digit;
+        }
+    }
+    naf
+}
+
+/// Compute \\(bB + aA\\) in variable time with Straus's method, where
+/// `B_tables` holds the odd multiples of the fixed point \\(B\\) and of
+/// \\(\phi(B)\\).
+///
+/// Both scalars are split with the Pallas endomorphism, so the loop only runs
+/// over half-length scalars.
+#[cfg(feature = "std")]
+#[allow(non_snake_case)]
+pub(crate) fn pallas_vartime_double_base_mul(
+    b: &pallas::Scalar,
+    B_tables: &[LookupTable8<pallas::Point>; 2],
+    a: &pallas::Scalar,
+    A: &pallas::Point,
+) -> pallas::Point {
+    let [b_1, b_2] = pallas_glv_decompose(b);
+    let [a_1, a_2] = pallas_glv_decompose(a);
+    let b_nafs = [signed_naf(b_1, 8), signed_naf(b_2, 8)];
+    let a_nafs = [signed_naf(a_1, 5), signed_naf(a_2, 5)];
+    let A_tables = [
+        LookupTable5::<pallas::Point>::from(A),
+        LookupTable5::<pallas::Point>::from(&A.endo()),
+    ];
+
+    let top = match naf_top(b_nafs.iter().chain(a_nafs.iter())) {
+        Some(top) => top,
+        None => return pallas::Point::identity(),
+    };
+
+    let mut r = pallas::Point::identity();
+
+    for i in (0..=top).rev() {
+        let mut t = r.double();
+
+        for (naf, lookup_table) in b_nafs.iter().zip(B_tables.iter()) {
+            if naf[i] > 0 {
+                t += lookup_table.select(naf[i] as usize);
+            } else if naf[i] < 0 {
+                t -= lookup_table.select(-naf[i] as usize);
+            }
+        }
+
+        for (naf, lookup_table) in a_nafs.iter().zip(A_tables.iter()) {
+            if naf[i] > 0 {
+                t += lookup_table.select(naf[i] as usize);
+            } else if naf[i] < 0 {
+                t -= lookup_table.select(-naf[i] as usize);
+            }
+        }
+
+        r = t;
+    }
+
+    r
+}
+
 #[cfg(feature = "alloc")]
 impl VartimeMultiscalarMul for pallas::Point {
     type Scalar = pallas::Scalar;
@@ -167,26 +246,38 @@ impl VartimeMultiscalarMul for pallas::Point {
         I::Item: Borrow<Self::Scalar>,
         J: IntoIterator<Item = Option<pallas::Point>>,
     {
-        let nafs: Vec<_> = scalars
-            .into_iter()
-            .map(|c| c.borrow().non_adjacent_form(5))
-            .collect();
+        let points = points.into_iter().collect::<Option<Vec<_>>>()?;
+
+        // Split each term cP into c_1 P + c_2 \phi(P) with half-length scalars,
+        // so that the loop below needs half as many doublings.
+        let mut nafs = Vec::with_capacity(2 * points.len());
+        let mut lookup_tables = Vec::with_capacity(2 * points.len());
+        for (c, P) in scalars.into_iter().zip(points.iter()) {
+            let [(neg_1, c_1), (neg_2, c_2)] = pallas_glv_decompose(c.borrow());
+            let P_1 = if neg_1 { -*P } else { *P };
+            let P_2 = if neg_2 { -P.endo() } else { P.endo() };
+
+            nafs.push(c_1.non_adjacent_form(5));
+            lookup_tables.push(LookupTable5::<pallas::Point>::from(&P_1));
+            nafs.push(c_2.non_adjacent_form(5));
+            lookup_tables.push(LookupTable5::<pallas::Point>::from(&P_2));
+        }
 
-        let lookup_tables = points
-            .into_iter()
-            .map(|P_opt| P_opt.map(|P| LookupTable5::<pallas::Point>::from(&P)))
-            .collect::<Option<Vec<_>>>()?;
+        let top = match naf_top(nafs.iter()) {
+            Some(top) => top,
+            None => return Some(pallas::Point::identity()),
+        };
 
         let mut r = pallas::Point::identity();
 
-        for i in (0..256).rev() {
+        for i in (0..=top).rev() {
             let mut t = r.double();
 
             for (naf, lookup_table) in nafs.iter().zip(lookup_tables.iter()) {
                 if naf[i] > 0 {
-                    t = &t + &lookup_table.select(naf[i] as usize);
+                    t += lookup_table.select(naf[i] as usize);
                 } else if naf[i] < 0 {
-                    t = &t - &lookup_table.select(-naf[i] as usize);
+                    t -= lookup_table.select(-naf[i] as usize);
                 }
             }
 
@@ -201,25 +292,89 @@ impl VartimeMultiscalarMul for pallas::Point {
 mod tests {
     #[test]
     fn orchard_spendauth_basepoint() {
-        use super::ORCHARD_SPENDAUTHSIG_BASEPOINT_BYTES;
+        use super::SPENDAUTHSIG_BASEPOINT_BYTES;
         use group::GroupEncoding;
         use pasta_curves::{arithmetic::CurveExt, pallas};
 
         assert_eq!(
             pallas::Point::hash_to_curve("z.cash:Orchard")(b"G").to_bytes(),
-            ORCHARD_SPENDAUTHSIG_BASEPOINT_BYTES
+            SPENDAUTHSIG_BASEPOINT_BYTES
         );
     }
 
     #[test]
     fn orchard_binding_basepoint() {
-        use super::ORCHARD_BINDINGSIG_BASEPOINT_BYTES;
+        use super::BINDINGSIG_BASEPOINT_BYTES;
         use group::GroupEncoding;
         use pasta_curves::{arithmetic::CurveExt, pallas};
 
         assert_eq!(
             pallas::Point::hash_to_curve("z.cash:Orchard-cv")(b"r").to_bytes(),
-            ORCHARD_BINDINGSIG_BASEPOINT_BYTES
+            BINDINGSIG_BASEPOINT_BYTES
         );
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[allow(non_snake_case)]
+    fn glv_decomposition() {
+        use crate::scalar_mul::{pallas_glv_decompose, PALLAS_LAMBDA};
+        use group::{
+            ff::{Field, PrimeField},
+            Group,
+        };
+        use pasta_curves::{arithmetic::CurveExt, pallas};
+        use rand::thread_rng;
+
+        let lambda = pallas::Scalar::from_raw(PALLAS_LAMBDA);
+        let P = pallas::Point::random(thread_rng());
+        assert_eq!(P.endo(), P * lambda);
+
+        let mut scalars = vec![
+            pallas::Scalar::zero(),
+            pallas::Scalar::one(),
+            -pallas::Scalar::one(),
+            lambda,
+        ];
+        scalars.extend((0..100).map(|_| pallas::Scalar::random(thread_rng())));
+
+        for k in scalars {
+            let [(neg_1, k_1), (neg_2, k_2)] = pallas_glv_decompose(&k);
+            assert!(k_1.to_repr()[16..].iter().all(|b| *b == 0));
+            assert!(k_2.to_repr()[16..].iter().all(|b| *b == 0));
+            let k_1 = if neg_1 { -k_1 } else { k_1 };
+            let k_2 = if neg_2 { -k_2 } else { k_2 };
+            assert_eq!(k_1 + k_2 * lambda, k);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[allow(non_snake_case)]
+    fn glv_multiscalar_mul_matches_scalar_mul() {
+        use crate::scalar_mul::VartimeMultiscalarMul;
+        use alloc::vec::Vec;
+        use group::{ff::Field, Group};
+        use pasta_curves::pallas;
+        use rand::thread_rng;
+
+        for n in [0, 1, 2, 7] {
+            let scalars: Vec<_> = (0..n)
+                .map(|_| pallas::Scalar::random(thread_rng()))
+                .collect();
+            let points: Vec<_> = (0..n)
+                .map(|_| pallas::Point::random(thread_rng()))
+                .collect();
+
+            let expected = scalars
+                .iter()
+                .zip(points.iter())
+                .fold(pallas::Point::identity(), |acc, (c, P)| acc + P * c);
+
+            assert_eq!(
+                pallas::Point::vartime_multiscalar_mul(scalars.iter(), points.iter()),
+                expected
+            );
+        }
+    }
 }
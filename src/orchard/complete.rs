@@ -0,0 +1,166 @@
+//! Complete addition on Pallas, for constant-time scalar multiplication.
+//!
+//! The point addition of `pasta_curves` branches on the identity and on equal
+//! inputs, so a scalar multiplication built on it takes a different path for
+//! every zero digit of the scalar. The points here are in homogeneous
+//! projective coordinates and are added with the complete formulas of
+//! [Renes, Costello and Batina][rcb] (Algorithm 7, for \\(a = 0\\)), which have
+//! no exceptional cases: the identity, doublings and inverses all go through
+//! the same field operations.
+//!
+//! [rcb]: https://eprint.iacr.org/2015/1060
+
+use core::ops::Add;
+
+#[cfg(any(test, not(feature = "std")))]
+use group::ff::PrimeField;
+use group::{ff::Field, prime::PrimeCurveAffine};
+use pasta_curves::{
+    arithmetic::{Coordinates, CurveAffine},
+    pallas,
+};
+use subtle::{Choice, ConditionallySelectable};
+
+/// A Pallas point \\((X : Y : Z)\\) in homogeneous projective coordinates,
+/// standing for the affine point \\((X/Z, Y/Z)\\), or the identity if
+/// \\(Z = 0\\).
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CompletePoint {
+    x: pallas::Base,
+    y: pallas::Base,
+    z: pallas::Base,
+}
+
+impl CompletePoint {
+    pub(crate) fn identity() -> CompletePoint {
+        CompletePoint {
+            x: pallas::Base::zero(),
+            y: pallas::Base::one(),
+            z: pallas::Base::zero(),
+        }
+    }
+
+    /// Convert a public affine point.
+    pub(crate) fn from_affine(point: &pallas::Affine) -> CompletePoint {
+        Option::from(point.coordinates()).map_or(
+            CompletePoint::identity(),
+            |coordinates: Coordinates<pallas::Affine>| CompletePoint {
+                x: *coordinates.x(),
+                y: *coordinates.y(),
+                z: pallas::Base::one(),
+            },
+        )
+    }
+
+    /// Convert to a `pasta_curves` point, in constant time.
+    pub(crate) fn to_point(self) -> pallas::Point {
+        // The inverse of zero only occurs for the identity, which maps to the
+        // affine encoding (0, 0) of the identity.
+        let z_inv = self.z.invert().unwrap_or(pallas::Base::zero());
+        pallas::Affine::from_xy(self.x * z_inv, self.y * z_inv)
+            .unwrap_or(pallas::Affine::identity())
+            .to_curve()
+    }
+}
+
+impl Add for CompletePoint {
+    type Output = CompletePoint;
+
+    fn add(self, other: CompletePoint) -> CompletePoint {
+        // 3b, for the curve y^2 = x^3 + 5.
+        let b3 = pallas::Base::from(15);
+
+        let xx = self.x * other.x;
+        let yy = self.y * other.y;
+        let zz = self.z * other.z;
+        let xy_pairs = (self.x + self.y) * (other.x + other.y) - (xx + yy);
+        let yz_pairs = (self.y + self.z) * (other.y + other.z) - (yy + zz);
+        let xz_pairs = (self.x + self.z) * (other.x + other.z) - (xx + zz);
+
+        let bzz3 = b3 * zz;
+        let yy_m_bzz3 = yy - bzz3;
+        let yy_p_bzz3 = yy + bzz3;
+        let byz3 = b3 * yz_pairs;
+        let xx3 = xx + xx + xx;
+        let bxx9 = b3 * xx3;
+
+        CompletePoint {
+            x: xy_pairs * yy_m_bzz3 - byz3 * xz_pairs,
+            y: yy_p_bzz3 * yy_m_bzz3 + bxx9 * xz_pairs,
+            z: yz_pairs * yy_p_bzz3 + xx3 * xy_pairs,
+        }
+    }
+}
+
+impl ConditionallySelectable for CompletePoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        CompletePoint {
+            x: pallas::Base::conditional_select(&a.x, &b.x, choice),
+            y: pallas::Base::conditional_select(&a.y, &b.y, choice),
+            z: pallas::Base::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+/// Compute \\(sP\\) for a public point \\(P\\) and a secret scalar `s`, in
+/// constant time, with a double-and-add ladder over every bit of `s`.
+///
+/// This is for builds without `std`, which have no precomputed tables.
+#[cfg(any(test, not(feature = "std")))]
+pub(crate) fn mul(point: &pallas::Affine, s: &pallas::Scalar) -> pallas::Point {
+    let base = CompletePoint::from_affine(point);
+    let repr = s.to_repr();
+    let mut acc = CompletePoint::identity();
+    for byte in repr.as_ref().iter().rev() {
+        for i in (0..8).rev() {
+            acc = acc + acc;
+            let sum = acc + base;
+            acc.conditional_assign(&sum, Choice::from((byte >> i) & 1));
+        }
+    }
+    acc.to_point()
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{ff::Field, Curve, Group};
+    use rand_core::OsRng;
+
+    use super::*;
+
+    #[test]
+    fn addition_is_complete() {
+        let p = pallas::Point::random(OsRng);
+        let q = pallas::Point::random(OsRng);
+        let identity = pallas::Point::identity();
+        let complete = |point: pallas::Point| CompletePoint::from_affine(&point.to_affine());
+
+        for (a, b) in [
+            (p, q),
+            (p, p),
+            (p, -p),
+            (p, identity),
+            (identity, p),
+            (identity, identity),
+        ]
+        .iter()
+        {
+            assert_eq!((complete(*a) + complete(*b)).to_point(), a + b);
+        }
+    }
+
+    #[test]
+    fn ladder_matches_scalar_mul() {
+        let p = pallas::Point::random(OsRng);
+        for s in [
+            pallas::Scalar::zero(),
+            pallas::Scalar::one(),
+            -pallas::Scalar::one(),
+            pallas::Scalar::random(OsRng),
+        ]
+        .iter()
+        {
+            assert_eq!(mul(&p.to_affine(), s), p * s);
+        }
+    }
+}
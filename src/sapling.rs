@@ -2,6 +2,8 @@
 
 use super::SigType;
 
+pub use crate::constants::{BINDINGSIG_BASEPOINT_BYTES, SPENDAUTHSIG_BASEPOINT_BYTES};
+
 /// A type variable corresponding to Zcash's Sapling `SpendAuthSig`.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum SpendAuth {}
@@ -11,7 +13,9 @@ impl Default for SpendAuth {
         unimplemented!()
     }
 }
-impl SigType for SpendAuth {}
+impl SigType for SpendAuth {
+    const BASEPOINT_BYTES: [u8; 32] = SPENDAUTHSIG_BASEPOINT_BYTES;
+}
 impl super::SpendAuth for SpendAuth {}
 
 /// A type variable corresponding to Zcash's Sapling `BindingSig`.
@@ -23,5 +27,7 @@ impl Default for Binding {
         unimplemented!()
     }
 }
-impl SigType for Binding {}
+impl SigType for Binding {
+    const BASEPOINT_BYTES: [u8; 32] = BINDINGSIG_BASEPOINT_BYTES;
+}
 impl super::Binding for Binding {}
@@ -13,7 +13,10 @@
 use alloc::vec::Vec;
 use core::{borrow::Borrow, fmt::Debug};
 
+use byteorder::{ByteOrder, LittleEndian};
+use group::ff::PrimeField;
 use jubjub::{ExtendedNielsPoint, ExtendedPoint};
+use pasta_curves::pallas;
 
 pub trait NonAdjacentForm {
     fn non_adjacent_form(&self, w: usize) -> [i8; 256];
@@ -63,65 +66,67 @@ pub trait VartimeMultiscalarMul {
 }
 
 impl NonAdjacentForm for jubjub::Scalar {
-    /// Compute a width-\\(w\\) "Non-Adjacent Form" of this scalar.
-    ///
-    /// Thanks to curve25519-dalek
     fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
-        // required by the NAF definition
-        debug_assert!(w >= 2);
-        // required so that the NAF digits fit in i8
-        debug_assert!(w <= 8);
-
-        use byteorder::{ByteOrder, LittleEndian};
-
-        let mut naf = [0i8; 256];
-
-        let mut x_u64 = [0u64; 5];
-        LittleEndian::read_u64_into(&self.to_bytes(), &mut x_u64[0..4]);
-
-        let width = 1 << w;
-        let window_mask = width - 1;
-
-        let mut pos = 0;
-        let mut carry = 0;
-        while pos < 256 {
-            // Construct a buffer of bits of the scalar, starting at bit `pos`
-            let u64_idx = pos / 64;
-            let bit_idx = pos % 64;
-            let bit_buf: u64;
-            if bit_idx < 64 - w {
-                // This window's bits are contained in a single u64
-                bit_buf = x_u64[u64_idx] >> bit_idx;
-            } else {
-                // Combine the current u64's bits with the bits from the next u64
-                bit_buf = (x_u64[u64_idx] >> bit_idx) | (x_u64[1 + u64_idx] << (64 - bit_idx));
-            }
+        le_bytes_non_adjacent_form(&self.to_bytes(), w)
+    }
+}
 
-            // Add the carry into the current window
-            let window = carry + (bit_buf & window_mask);
+/// Compute a width-\\(w\\) "Non-Adjacent Form" of the scalar whose canonical
+/// little-endian encoding is `bytes`.
+///
+/// Thanks to curve25519-dalek
+pub(crate) fn le_bytes_non_adjacent_form(bytes: &[u8], w: usize) -> [i8; 256] {
+    // required by the NAF definition
+    debug_assert!(w >= 2);
+    // required so that the NAF digits fit in i8
+    debug_assert!(w <= 8);
 
-            if window & 1 == 0 {
-                // If the window value is even, preserve the carry and continue.
-                // Why is the carry preserved?
-                // If carry == 0 and window & 1 == 0, then the next carry should be 0
-                // If carry == 1 and window & 1 == 0, then bit_buf & 1 == 1 so the next carry should be 1
-                pos += 1;
-                continue;
-            }
+    let mut naf = [0i8; 256];
 
-            if window < width / 2 {
-                carry = 0;
-                naf[pos] = window as i8;
-            } else {
-                carry = 1;
-                naf[pos] = (window as i8).wrapping_sub(width as i8);
-            }
+    let mut x_u64 = [0u64; 5];
+    LittleEndian::read_u64_into(bytes, &mut x_u64[0..4]);
+
+    let width = 1 << w;
+    let window_mask = width - 1;
+
+    let mut pos = 0;
+    let mut carry = 0;
+    while pos < 256 {
+        // Construct a buffer of bits of the scalar, starting at bit `pos`
+        let u64_idx = pos / 64;
+        let bit_idx = pos % 64;
+        let bit_buf: u64 = if bit_idx < 64 - w {
+            // This window's bits are contained in a single u64
+            x_u64[u64_idx] >> bit_idx
+        } else {
+            // Combine the current u64's bits with the bits from the next u64
+            (x_u64[u64_idx] >> bit_idx) | (x_u64[1 + u64_idx] << (64 - bit_idx))
+        };
 
-            pos += w;
+        // Add the carry into the current window
+        let window = carry + (bit_buf & window_mask);
+
+        if window & 1 == 0 {
+            // If the window value is even, preserve the carry and continue.
+            // Why is the carry preserved?
+            // If carry == 0 and window & 1 == 0, then the next carry should be 0
+            // If carry == 1 and window & 1 == 0, then bit_buf & 1 == 1 so the next carry should be 1
+            pos += 1;
+            continue;
+        }
+
+        if window < width / 2 {
+            carry = 0;
+            naf[pos] = window as i8;
+        } else {
+            carry = 1;
+            naf[pos] = (window as i8).wrapping_sub(width as i8);
         }
 
-        naf
+        pos += w;
     }
+
+    naf
 }
 
 /// Holds odd multiples 1A, 3A, ..., 15A of a point A.
@@ -144,13 +149,58 @@ impl<T: Debug> Debug for LookupTable5<T> {
     }
 }
 
+/// Holds odd multiples 1A, 3A, ..., 127A of a point A.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone)]
+pub(crate) struct LookupTable8<T>(pub(crate) [T; 64]);
+
+#[cfg(feature = "std")]
+impl<T: Copy> LookupTable8<T> {
+    /// Given public, odd \\( x \\) with \\( 0 < x < 2^7 \\), return \\(xA\\).
+    pub fn select(&self, x: usize) -> T {
+        debug_assert_eq!(x & 1, 1);
+        debug_assert!(x < 128);
+
+        self.0[x / 2]
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Debug> Debug for LookupTable8<T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "LookupTable8({:?})", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> From<&'a ExtendedPoint> for LookupTable8<ExtendedNielsPoint> {
+    #[allow(non_snake_case)]
+    fn from(A: &'a ExtendedPoint) -> Self {
+        let mut Ai = [A.to_niels(); 64];
+        let A2 = A.double();
+        for i in 0..63 {
+            Ai[i + 1] = (A2 + Ai[i]).to_niels();
+        }
+        // Now Ai = [A, 3A, 5A, ..., 127A]
+        LookupTable8(Ai)
+    }
+}
+
+/// Return the position of the highest nonzero digit in any of `nafs`, or
+/// `None` if they are all zero.
+pub(crate) fn naf_top<'a>(nafs: impl IntoIterator<Item = &'a [i8; 256]>) -> Option<usize> {
+    nafs.into_iter()
+        .filter_map(|naf| naf.iter().rposition(|digit| *digit != 0))
+        .max()
+}
+
 impl<'a> From<&'a ExtendedPoint> for LookupTable5<ExtendedNielsPoint> {
     #[allow(non_snake_case)]
     fn from(A: &'a ExtendedPoint) -> Self {
         let mut Ai = [A.to_niels(); 8];
         let A2 = A.double();
         for i in 0..7 {
-            Ai[i + 1] = (&A2 + &Ai[i]).to_niels();
+            Ai[i + 1] = (A2 + Ai[i]).to_niels();
         }
         // Now Ai = [A, 3A, 5A, 7A, 9A, 11A, 13A, 15A]
         LookupTable5(Ai)
@@ -185,9 +235,9 @@ impl VartimeMultiscalarMul for ExtendedPoint {
 
             for (naf, lookup_table) in nafs.iter().zip(lookup_tables.iter()) {
                 if naf[i] > 0 {
-                    t = &t + &lookup_table.select(naf[i] as usize);
+                    t += lookup_table.select(naf[i] as usize);
                 } else if naf[i] < 0 {
-                    t = &t - &lookup_table.select(-naf[i] as usize);
+                    t -= lookup_table.select(-naf[i] as usize);
                 }
             }
 
@@ -197,3 +247,124 @@ impl VartimeMultiscalarMul for ExtendedPoint {
         Some(r)
     }
 }
+
+/// Compute \\(bB + aA\\) in variable time with Straus's method, where
+/// `B_table` holds the odd multiples of the fixed point \\(B\\).
+#[cfg(feature = "std")]
+#[allow(non_snake_case)]
+pub(crate) fn jubjub_vartime_double_base_mul(
+    b: &jubjub::Scalar,
+    B_table: &LookupTable8<ExtendedNielsPoint>,
+    a: &jubjub::Scalar,
+    A: &ExtendedPoint,
+) -> ExtendedPoint {
+    let b_naf = b.non_adjacent_form(8);
+    let a_naf = a.non_adjacent_form(5);
+    let A_table = LookupTable5::<ExtendedNielsPoint>::from(A);
+
+    let top = match naf_top([&b_naf, &a_naf]) {
+        Some(top) => top,
+        None => return ExtendedPoint::identity(),
+    };
+
+    let mut r = ExtendedPoint::identity();
+
+    for i in (0..=top).rev() {
+        let mut t = r.double();
+
+        if b_naf[i] > 0 {
+            t += B_table.select(b_naf[i] as usize);
+        } else if b_naf[i] < 0 {
+            t -= B_table.select(-b_naf[i] as usize);
+        }
+
+        if a_naf[i] > 0 {
+            t += A_table.select(a_naf[i] as usize);
+        } else if a_naf[i] < 0 {
+            t -= A_table.select(-a_naf[i] as usize);
+        }
+
+        r = t;
+    }
+
+    r
+}
+
+/// The cube root of unity \\(\lambda\\) in the Pallas scalar field for which
+/// `pallas::Point::endo` computes \\([\lambda] P\\).
+pub(crate) const PALLAS_LAMBDA: [u64; 4] = [
+    0x2aa9d2e050aa0e4f,
+    0x0fed467d47c033af,
+    0x511db4d81cf70f5a,
+    0x06819a58283e528e,
+];
+
+// A short basis \\((a_1, b_1), (a_2, b_2)\\) of the lattice of pairs with
+// \\(a + b \lambda \equiv 0\\), as the magnitudes of \\(b_1 < 0\\) and
+// \\(b_2 > 0\\), and the rounded constants \\(g_1 = 2^{320} b_2 / q\\) and
+// \\(g_2 = -2^{320} b_1 / q\\) used to find the closest lattice point.
+const PALLAS_MINUS_B1: [u64; 4] = [0x8cb1279300000000, 0x49e69d1640a89953, 0, 0];
+const PALLAS_B2: [u64; 4] = [0x0c7c095a00000001, 0x93cd3a2c8198e269, 0, 0];
+const PALLAS_G1: [u64; 4] = [
+    0xc35fbd4d086862e0,
+    0x31f0256800000002,
+    0x4f34e8b2066389a4,
+    0x0000000000000002,
+];
+const PALLAS_G2: [u64; 4] = [
+    0x61afdea68480fa55,
+    0x32c49e4bffffffff,
+    0x279a745902a2654e,
+    0x0000000000000001,
+];
+
+/// Split the scalar \\(k\\) into \\(k_1 + k_2 \lambda\\), where \\(k_1\\) and
+/// \\(k_2\\) are at most 128 bits, so that \\(kP = k_1 P + k_2 \phi(P)\\)
+/// for the Pallas endomorphism \\(\phi\\).
+///
+/// Each half is returned as its magnitude and whether it is negative. This
+/// runs in variable time, so it must only be used with public scalars.
+pub(crate) fn pallas_glv_decompose(k: &pallas::Scalar) -> [(bool, pallas::Scalar); 2] {
+    let mut k_u64 = [0u64; 4];
+    LittleEndian::read_u64_into(k.to_repr().as_ref(), &mut k_u64);
+
+    let c1 = mul_shift_320(&k_u64, &PALLAS_G1);
+    let c2 = mul_shift_320(&k_u64, &PALLAS_G2);
+
+    let k2 =
+        c1 * pallas::Scalar::from_raw(PALLAS_MINUS_B1) - c2 * pallas::Scalar::from_raw(PALLAS_B2);
+    let k1 = *k - k2 * pallas::Scalar::from_raw(PALLAS_LAMBDA);
+
+    [signed_half(k1), signed_half(k2)]
+}
+
+/// Compute \\(\lfloor k g / 2^{320} \rceil\\), which is less than \\(2^{128}\\)
+/// for the GLV constants.
+fn mul_shift_320(k: &[u64; 4], g: &[u64; 4]) -> pallas::Scalar {
+    let mut product = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let t = (k[i] as u128) * (g[j] as u128) + (product[i + j] as u128) + carry;
+            product[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        product[i + 4] = carry as u64;
+    }
+    debug_assert_eq!(product[7], 0);
+
+    let c = ((product[5] as u128) | ((product[6] as u128) << 64)) + ((product[4] >> 63) as u128);
+    pallas::Scalar::from_raw([c as u64, (c >> 64) as u64, 0, 0])
+}
+
+/// Interpret `x` as a signed integer of at most 128 bits.
+fn signed_half(x: pallas::Scalar) -> (bool, pallas::Scalar) {
+    let is_short = |x: &pallas::Scalar| x.to_repr().as_ref()[16..].iter().all(|b| *b == 0);
+    if is_short(&x) {
+        (false, x)
+    } else {
+        let minus_x = -x;
+        debug_assert!(is_short(&minus_x));
+        (true, minus_x)
+    }
+}
@@ -8,21 +8,95 @@
 // - Henry de Valence <hdevalence@hdevalence.ca>
 
 //! RedDSA Signatures
-use core::marker::PhantomData;
+use core::{convert::TryFrom, marker::PhantomData};
 
-use crate::SigType;
+use group::{cofactor::CofactorCurve, ff::PrimeField, Curve, GroupEncoding};
+
+use crate::{Error, HStar, SigType, VerificationKeyBytes};
 
 /// A RedDSA signature.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerdeHelper"))]
+#[cfg_attr(feature = "serde", serde(into = "SerdeHelper"))]
+#[cfg_attr(feature = "serde", serde(bound = "T: SigType"))]
 pub struct Signature<T: SigType> {
     pub(crate) r_bytes: [u8; 32],
     pub(crate) s_bytes: [u8; 32],
     pub(crate) _marker: PhantomData<T>,
 }
 
-impl<T: SigType> From<[u8; 64]> for Signature<T> {
-    fn from(bytes: [u8; 64]) -> Signature<T> {
+/// The serialized form of a [`Signature`], which keeps the field layout of the
+/// derived implementation so that existing encodings still decode.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename = "Signature"))]
+struct SerdeHelper {
+    r_bytes: [u8; 32],
+    s_bytes: [u8; 32],
+    _marker: PhantomData<()>,
+}
+
+impl<T: SigType> TryFrom<SerdeHelper> for Signature<T> {
+    type Error = Error;
+
+    fn try_from(helper: SerdeHelper) -> Result<Self, Self::Error> {
+        let mut bytes = [0; 64];
+        bytes[0..32].copy_from_slice(&helper.r_bytes);
+        bytes[32..64].copy_from_slice(&helper.s_bytes);
+        Signature::try_from(bytes)
+    }
+}
+
+impl<T: SigType> From<Signature<T>> for SerdeHelper {
+    fn from(signature: Signature<T>) -> Self {
+        SerdeHelper {
+            r_bytes: signature.r_bytes,
+            s_bytes: signature.s_bytes,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Decodes a signature.
+///
+/// Returns [`Error::MalformedSignaturePoint`] if `R` is not a canonical point
+/// encoding, and [`Error::MalformedSignatureScalar`] if `s` is not a canonical
+/// scalar encoding.
+impl<T: SigType> TryFrom<[u8; 64]> for Signature<T> {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 64]) -> Result<Self, Self::Error> {
+        let signature = Signature::from_bytes_unchecked(bytes);
+
+        let mut repr = <T::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&signature.r_bytes);
+        if bool::from(T::Point::from_bytes(&repr).is_none()) {
+            return Err(Error::MalformedSignaturePoint);
+        }
+
+        let mut repr = <T::Scalar as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(&signature.s_bytes);
+        if bool::from(T::Scalar::from_repr(repr).is_none()) {
+            return Err(Error::MalformedSignatureScalar);
+        }
+
+        Ok(signature)
+    }
+}
+
+impl<T: SigType> From<Signature<T>> for [u8; 64] {
+    fn from(sig: Signature<T>) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[0..32].copy_from_slice(&sig.r_bytes[..]);
+        bytes[32..64].copy_from_slice(&sig.s_bytes[..]);
+        bytes
+    }
+}
+
+impl<T: SigType> Signature<T> {
+    /// Splits `bytes` into `R` and `s` without checking their encodings, which
+    /// verification checks instead.
+    pub(crate) fn from_bytes_unchecked(bytes: [u8; 64]) -> Signature<T> {
         let mut r_bytes = [0; 32];
         r_bytes.copy_from_slice(&bytes[0..32]);
         let mut s_bytes = [0; 32];
@@ -33,13 +107,63 @@ impl<T: SigType> From<[u8; 64]> for Signature<T> {
             _marker: PhantomData,
         }
     }
+
+    /// Returns the challenge \\(c = H^\star(\underline{R} || \underline{vk} || M)\\)
+    /// of this signature on `msg` under the verification key `vk`.
+    ///
+    /// This is the exact scalar that verification uses, for circuits that
+    /// re-prove the validity of a signature.
+    pub fn challenge(&self, vk: &VerificationKeyBytes<T>, msg: &[u8]) -> T::Scalar {
+        self.challenge_parts(vk, &[msg])
+    }
+
+    /// Returns the challenge of this signature on `msg` in the application
+    /// domain `domain` under the verification key `vk`, as described in
+    /// [`SigningKey::sign_with_domain`](crate::SigningKey::sign_with_domain).
+    pub fn challenge_with_domain(
+        &self,
+        vk: &VerificationKeyBytes<T>,
+        domain: &[u8],
+        msg: &[u8],
+    ) -> T::Scalar {
+        let len = (domain.len() as u64).to_le_bytes();
+        self.challenge_parts(vk, &[&len[..], domain, msg])
+    }
+
+    /// Returns the challenge of this signature on the concatenation of
+    /// `parts`.
+    pub(crate) fn challenge_parts(
+        &self,
+        vk: &VerificationKeyBytes<T>,
+        parts: &[&[u8]],
+    ) -> T::Scalar {
+        let mut hash = HStar::<T>::default();
+        hash.update(&self.r_bytes[..]).update(&vk.bytes[..]);
+        for part in parts {
+            hash.update(part);
+        }
+        hash.finalize()
+    }
+
+    /// Returns the affine form of the signature's commitment point `R`, or an
+    /// error if `R` is not a valid encoding of a point.
+    pub fn r_affine(&self) -> Result<<T::Point as CofactorCurve>::Affine, Error> {
+        let mut repr = <T::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&self.r_bytes);
+        let maybe_point = T::Point::from_bytes(&repr);
+        if maybe_point.is_some().into() {
+            Ok(maybe_point.unwrap().to_affine())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
 }
 
-impl<T: SigType> From<Signature<T>> for [u8; 64] {
-    fn from(sig: Signature<T>) -> [u8; 64] {
+#[cfg(feature = "arbitrary")]
+impl<'a, T: SigType> arbitrary::Arbitrary<'a> for Signature<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         let mut bytes = [0; 64];
-        bytes[0..32].copy_from_slice(&sig.r_bytes[..]);
-        bytes[32..64].copy_from_slice(&sig.s_bytes[..]);
-        bytes
+        bytes.copy_from_slice(u.bytes(64)?);
+        Ok(Signature::from_bytes_unchecked(bytes))
     }
 }
@@ -10,6 +10,7 @@
 
 use core::{
     convert::{TryFrom, TryInto},
+    fmt,
     marker::PhantomData,
 };
 
@@ -17,50 +18,141 @@ use crate::{
     private::SealedScalar, Error, Randomizer, SigType, Signature, SpendAuth, VerificationKey,
 };
 
-use group::{ff::PrimeField, GroupEncoding};
+use group::{
+    ff::{Field, PrimeField},
+    GroupEncoding,
+};
 use rand_core::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::{DefaultIsZeroes, Zeroize};
+
+/// A secret scalar, which is wiped from memory when the `zeroize` feature is
+/// enabled.
+///
+/// It deliberately does not implement `Debug`, so that secret-bearing types
+/// can only be formatted through a redacted [`Fingerprint`].
+#[derive(Copy, Clone, Default)]
+pub(crate) struct SecretScalar<S>(pub(crate) S);
+
+// Zeroizes `SecretScalar` to be the `Default` value, which for the scalar
+// fields of both jubjub and pallas is zero.
+#[cfg(feature = "zeroize")]
+impl<S: Copy + Default> DefaultIsZeroes for SecretScalar<S> {}
+
+/// A short hash of the public counterpart of a secret, shown in place of the
+/// secret by the `Debug` implementations of secret-bearing types.
+///
+/// The fingerprint only depends on public values, such as the verification
+/// key of a signing key, so logging it reveals nothing about the secret while
+/// still telling keys apart.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub(crate) struct Fingerprint([u8; 8]);
+
+impl Fingerprint {
+    /// Computes the fingerprint of the concatenation of the public encodings
+    /// in `parts`.
+    pub(crate) fn new(parts: &[&[u8]]) -> Fingerprint {
+        let mut state = blake2b_simd::Params::new()
+            .hash_length(8)
+            .personal(b"reddsa_KeyFprint")
+            .to_state();
+        for part in parts {
+            state.update(part);
+        }
+        Fingerprint(state.finalize().as_bytes().try_into().unwrap())
+    }
+}
+
+impl fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
 
 /// A RedDSA signing key.
-#[derive(Copy, Clone, Debug)]
+///
+/// With the `zeroize` feature, the secret scalar is wiped from memory when the
+/// signing key is dropped.
+///
+/// The `Debug` output only shows a fingerprint of the verification key, never
+/// the secret scalar.
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "SerdeHelper"))]
 #[cfg_attr(feature = "serde", serde(into = "SerdeHelper"))]
 #[cfg_attr(feature = "serde", serde(bound = "T: SigType"))]
 pub struct SigningKey<T: SigType> {
-    sk: T::Scalar,
-    pk: VerificationKey<T>,
+    pub(crate) sk: SecretScalar<T::Scalar>,
+    pub(crate) pk: VerificationKey<T>,
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: SigType> Drop for SigningKey<T> {
+    fn drop(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
+impl<T: SigType> fmt::Debug for SigningKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vk_bytes: [u8; 32] = self.pk.into();
+        f.debug_struct("SigningKey")
+            .field("fingerprint", &Fingerprint::new(&[&vk_bytes]))
+            .finish()
+    }
 }
 
 impl<'a, T: SigType> From<&'a SigningKey<T>> for VerificationKey<T> {
     fn from(sk: &'a SigningKey<T>) -> VerificationKey<T> {
-        sk.pk.clone()
+        sk.pk
     }
 }
 
 impl<T: SigType> From<SigningKey<T>> for [u8; 32] {
     fn from(sk: SigningKey<T>) -> [u8; 32] {
-        sk.sk.to_repr().as_ref().try_into().unwrap()
+        sk.sk.0.to_repr().as_ref().try_into().unwrap()
     }
 }
 
+/// Decodes a signing key.
+///
+/// Returns [`Error::MalformedSigningKey`] if the bytes are not a canonical
+/// scalar encoding, and [`Error::ZeroSigningKey`] if the scalar is zero.
 impl<T: SigType> TryFrom<[u8; 32]> for SigningKey<T> {
     type Error = Error;
 
     fn try_from(bytes: [u8; 32]) -> Result<Self, Self::Error> {
-        // XXX-jubjub: this should not use CtOption
         let mut repr = <T::Scalar as PrimeField>::Repr::default();
         repr.as_mut().copy_from_slice(&bytes);
         let maybe_sk = T::Scalar::from_repr(repr);
-        if maybe_sk.is_some().into() {
-            let sk = maybe_sk.unwrap();
-            let pk = VerificationKey::from(&sk);
-            Ok(SigningKey { sk, pk })
-        } else {
+        // Derive the verification key whether or not the encoding is
+        // canonical, so that decoding does not branch on the secret bytes
+        // before the validity of the encoding is returned.
+        let sk = maybe_sk.unwrap_or(T::Scalar::zero());
+        let pk = VerificationKey::from(&sk);
+        if bool::from(maybe_sk.is_none()) {
             Err(Error::MalformedSigningKey)
+        } else if bool::from(sk.is_zero()) {
+            Err(Error::ZeroSigningKey)
+        } else {
+            Ok(SigningKey {
+                sk: SecretScalar(sk),
+                pk,
+            })
         }
     }
 }
 
+impl<T: SigType> ConstantTimeEq for SigningKey<T> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.sk.0.ct_eq(&other.sk.0)
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct SerdeHelper([u8; 32]);
 
@@ -81,60 +173,204 @@ impl<T: SigType> From<SigningKey<T>> for SerdeHelper {
 impl<T: SpendAuth> SigningKey<T> {
     /// Randomize this public key with the given `randomizer`.
     pub fn randomize(&self, randomizer: &Randomizer<T>) -> SigningKey<T> {
-        let sk = self.sk + randomizer;
+        let sk = self.sk.0 + randomizer;
         let pk = VerificationKey::from(&sk);
-        SigningKey { sk, pk }
+        SigningKey {
+            sk: SecretScalar(sk),
+            pk,
+        }
+    }
+
+    /// Recover the signing key that was randomized with `randomizer` to give
+    /// this one.
+    pub fn derandomize(&self, randomizer: &Randomizer<T>) -> SigningKey<T> {
+        self.randomize(&-*randomizer)
     }
 }
 
 impl<T: SigType> SigningKey<T> {
+    /// Returns the signing key for the scalar `sk`, which may be zero.
+    #[cfg(any(
+        feature = "orchard-interop",
+        feature = "sapling-interop",
+        all(
+            test,
+            any(feature = "frost-share-encryption", feature = "secure-channel")
+        )
+    ))]
+    pub(crate) fn from_scalar(sk: T::Scalar) -> SigningKey<T> {
+        let pk = VerificationKey::from(&sk);
+        SigningKey {
+            sk: SecretScalar(sk),
+            pk,
+        }
+    }
+
     /// Generate a new signing key.
     pub fn new<R: RngCore + CryptoRng>(mut rng: R) -> SigningKey<T> {
         let sk = {
             let mut bytes = [0; 64];
             rng.fill_bytes(&mut bytes);
-            T::Scalar::from_bytes_wide(&bytes)
+            let sk = T::Scalar::from_bytes_wide(&bytes);
+            #[cfg(feature = "zeroize")]
+            bytes.zeroize();
+            sk
         };
         let pk = VerificationKey::from(&sk);
-        SigningKey { sk, pk }
+        SigningKey {
+            sk: SecretScalar(sk),
+            pk,
+        }
+    }
+
+    /// Generate a new signing key from the operating system's RNG.
+    #[cfg(feature = "std")]
+    pub fn new_default() -> SigningKey<T> {
+        SigningKey::new(rand_core::OsRng)
     }
 
     /// Create a signature of type `T` on `msg` using this `SigningKey`.
     // Similar to signature::Signer but without boxed errors.
-    pub fn sign<R: RngCore + CryptoRng>(&self, mut rng: R, msg: &[u8]) -> Signature<T> {
+    pub fn sign<R: RngCore + CryptoRng>(&self, rng: R, msg: &[u8]) -> Signature<T> {
+        self.sign_parts(rng, &[msg])
+    }
+
+    /// Create a signature of type `T` on `msg` in the application domain
+    /// `domain`, such as `b"myapp/login/v1"`.
+    ///
+    /// The signature can only be checked with
+    /// [`VerificationKey::verify_with_domain`] and the same domain, so that a
+    /// signature made for one application cannot be replayed in another that
+    /// uses a different domain. The challenge is
+    /// \\(H^\star(\underline{R} || \underline{vk} || len || domain || M)\\),
+    /// where `len` is the length of `domain` in bytes, encoded as 8
+    /// little-endian bytes.
+    ///
+    /// This is a plain RedDSA signature on the message `len || domain || M`,
+    /// so applications that sign both with and without a domain lose the
+    /// separation; Zcash consensus signatures never use a domain.
+    pub fn sign_with_domain<R: RngCore + CryptoRng>(
+        &self,
+        rng: R,
+        domain: &[u8],
+        msg: &[u8],
+    ) -> Signature<T> {
+        let len = (domain.len() as u64).to_le_bytes();
+        self.sign_parts(rng, &[&len[..], domain, msg])
+    }
+
+    /// Sign the concatenation of `parts`, without copying them into one
+    /// message.
+    fn sign_parts<R: RngCore + CryptoRng>(&self, mut rng: R, parts: &[&[u8]]) -> Signature<T> {
         use crate::HStar;
 
         // Choose a byte sequence uniformly at random of length
         // (\ell_H + 128)/8 bytes.  For RedJubjub and RedPallas this is
         // (512 + 128)/8 = 80.
-        let random_bytes = {
+        #[allow(unused_mut)]
+        let mut random_bytes = {
             let mut bytes = [0; 80];
             rng.fill_bytes(&mut bytes);
             bytes
         };
 
-        let nonce = HStar::<T>::default()
+        let mut nonce_hash = HStar::<T>::default();
+        nonce_hash
             .update(&random_bytes[..])
-            .update(&self.pk.bytes.bytes[..]) // XXX ugly
-            .update(msg)
-            .finalize();
+            .update(&self.pk.bytes.bytes[..]); // XXX ugly
+        for part in parts {
+            nonce_hash.update(part);
+        }
+        #[allow(unused_mut)]
+        let mut nonce = SecretScalar(nonce_hash.finalize());
+        #[cfg(feature = "zeroize")]
+        random_bytes.zeroize();
 
-        let r: T::Point = T::basepoint() * nonce;
+        let r: T::Point = T::basepoint_mul(&nonce.0);
         let r_bytes: [u8; 32] = r.to_bytes().as_ref().try_into().unwrap();
 
-        let c = HStar::<T>::default()
-            .update(&r_bytes[..])
-            .update(&self.pk.bytes.bytes[..]) // XXX ugly
-            .update(msg)
-            .finalize();
-
-        let s = nonce + (c * self.sk);
-        let s_bytes = s.to_repr().as_ref().try_into().unwrap();
-
-        Signature {
+        let mut signature = Signature {
             r_bytes,
-            s_bytes,
+            s_bytes: [0; 32],
             _marker: PhantomData,
+        };
+        let c = signature.challenge_parts(&self.pk.bytes, parts);
+
+        let s = nonce.0 + (c * self.sk.0);
+        signature.s_bytes = s.to_repr().as_ref().try_into().unwrap();
+        #[cfg(feature = "zeroize")]
+        nonce.zeroize();
+
+        signature
+    }
+}
+
+#[cfg(feature = "mnemonic-keys")]
+impl<T: SigType> SigningKey<T> {
+    /// Deterministically derive the signing key with the given `index` from a
+    /// 64-byte [BIP-39] seed.
+    ///
+    /// The key is the RedDSA hash-to-scalar \\(H^\star\\) of the string
+    /// `"RedDSA_SeedKey"`, the seed, and the index encoded as 4 little-endian
+    /// bytes. This is not [ZIP 32] derivation: it lets standalone tools
+    /// generate keys that can be recovered from a mnemonic, without the key
+    /// tree of a wallet.
+    ///
+    /// [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+    /// [ZIP 32]: https://zips.z.cash/zip-0032
+    pub fn from_seed(seed: &[u8; 64], index: u32) -> SigningKey<T> {
+        use crate::HStar;
+
+        let sk = HStar::<T>::default()
+            .update(b"RedDSA_SeedKey")
+            .update(&seed[..])
+            .update(index.to_le_bytes())
+            .finalize();
+        let pk = VerificationKey::from(&sk);
+        SigningKey {
+            sk: SecretScalar(sk),
+            pk,
         }
     }
+
+    /// Deterministically derive the signing key with the given `index` from a
+    /// [BIP-39] mnemonic phrase in English and an optional passphrase.
+    ///
+    /// The mnemonic is turned into a seed as specified by BIP-39, and the key
+    /// is derived from the seed with [`SigningKey::from_seed`]. Returns
+    /// [`Error::MalformedMnemonic`] if the phrase is not a valid mnemonic.
+    ///
+    /// [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+    pub fn from_mnemonic(
+        phrase: &str,
+        passphrase: &str,
+        index: u32,
+    ) -> Result<SigningKey<T>, Error> {
+        let mnemonic = bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+            .map_err(|_| Error::MalformedMnemonic)?;
+        let mut seed = mnemonic.to_seed(passphrase);
+        let sk = SigningKey::from_seed(&seed, index);
+        seed.zeroize();
+        Ok(sk)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl<T: SigType> ::signature::RandomizedSigner<Signature<T>> for SigningKey<T> {
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut impl ::signature::rand_core::CryptoRngCore,
+        msg: &[u8],
+    ) -> Result<Signature<T>, ::signature::Error> {
+        Ok(self.sign(rng, msg))
+    }
+}
+
+/// RedDSA signatures are randomized, so this draws the signing randomness from
+/// the operating system's RNG.
+#[cfg(all(feature = "signature", feature = "std"))]
+impl<T: SigType> ::signature::Signer<Signature<T>> for SigningKey<T> {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<T>, ::signature::Error> {
+        Ok(self.sign(rand_core::OsRng, msg))
+    }
 }
@@ -9,14 +9,28 @@
 // - Henry de Valence <hdevalence@hdevalence.ca>
 
 use core::{
+    cmp::Ordering,
     convert::{TryFrom, TryInto},
     hash::{Hash, Hasher},
     marker::PhantomData,
 };
 
-use group::{cofactor::CofactorGroup, ff::PrimeField, GroupEncoding};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
-use crate::{Error, Randomizer, SigType, Signature, SpendAuth};
+#[cfg(feature = "alloc")]
+use group::ff::Field;
+use group::{
+    cofactor::{CofactorCurve, CofactorGroup},
+    ff::PrimeField,
+    Curve, Group, GroupEncoding,
+};
+#[cfg(feature = "alloc")]
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(feature = "alloc")]
+use crate::{batch::gen_128_bits, private::SealedScalar, scalar_mul::VartimeMultiscalarMul};
+use crate::{Error, HStar, Randomizer, SigType, Signature, SpendAuth};
 
 /// A refinement type for `[u8; 32]` indicating that the bytes represent
 /// an encoding of a RedDSA verification key.
@@ -24,6 +38,10 @@ use crate::{Error, Randomizer, SigType, Signature, SpendAuth};
 /// This is useful for representing a compressed verification key; the
 /// [`VerificationKey`] type in this library holds other decompressed state
 /// used in signature verification.
+///
+/// Verification key bytes are ordered lexicographically by their 32-byte
+/// encoding, so they can be used as `BTreeMap` keys, and sorting a list of
+/// keys gives the same order on every platform.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VerificationKeyBytes<T: SigType> {
@@ -46,6 +64,15 @@ impl<T: SigType> From<VerificationKeyBytes<T>> for [u8; 32] {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, T: SigType> arbitrary::Arbitrary<'a> for VerificationKeyBytes<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(u.bytes(32)?);
+        Ok(bytes.into())
+    }
+}
+
 impl<T: SigType> Hash for VerificationKeyBytes<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.bytes.hash(state);
@@ -53,6 +80,18 @@ impl<T: SigType> Hash for VerificationKeyBytes<T> {
     }
 }
 
+impl<T: SigType> PartialOrd for VerificationKeyBytes<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: SigType> Ord for VerificationKeyBytes<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
 /// A valid RedDSA verification key.
 ///
 /// This type holds decompressed state used in signature verification; if the
@@ -126,18 +165,86 @@ impl<T: SpendAuth> VerificationKey<T> {
     ///
     /// Randomization is only supported for `SpendAuth` keys.
     pub fn randomize(&self, randomizer: &Randomizer<T>) -> VerificationKey<T> {
-        let point = self.point + (T::basepoint() * randomizer);
+        let point = self.point + T::basepoint_mul(randomizer);
         let bytes = VerificationKeyBytes {
             bytes: point.to_bytes().as_ref().try_into().unwrap(),
             _marker: PhantomData,
         };
         VerificationKey { bytes, point }
     }
+
+    /// Recover the verification key that was randomized with `randomizer` to
+    /// give this one.
+    ///
+    /// This recovers `ak` from `rk` given the randomizer `alpha`.
+    pub fn derandomize(&self, randomizer: &Randomizer<T>) -> VerificationKey<T> {
+        self.randomize(&-*randomizer)
+    }
+
+    /// Verify a purported `signature` over `msg` made by the randomization of
+    /// this verification key with `randomizer`.
+    pub fn verify_with_randomizer(
+        &self,
+        randomizer: &Randomizer<T>,
+        msg: &[u8],
+        signature: &Signature<T>,
+    ) -> Result<(), Error> {
+        self.randomize(randomizer).verify(msg, signature)
+    }
+
+    /// Deterministically derive the randomizer for the action with index
+    /// `index` from `seed`.
+    ///
+    /// The seed should be bound to the transaction being built, so that the
+    /// randomizers of different transactions are independent. The randomizer
+    /// is \(H^\star(\texttt{"RedDSA\_Randomizer"} || seed || index)\), with
+    /// the index encoded as 4 little-endian bytes.
+    pub fn derive_randomizer(seed: &[u8; 32], index: u32) -> Randomizer<T> {
+        HStar::<T>::default()
+            .update(b"RedDSA_Randomizer")
+            .update(seed)
+            .update(index.to_le_bytes())
+            .finalize()
+    }
+
+    /// Derive the randomizers of `count` actions from `seed` with
+    /// [`derive_randomizer`](Self::derive_randomizer), together with the
+    /// randomizations of this verification key by each of them.
+    #[cfg(feature = "alloc")]
+    pub fn derive_randomized_keys(
+        &self,
+        seed: &[u8; 32],
+        count: u32,
+    ) -> Vec<(Randomizer<T>, VerificationKey<T>)> {
+        (0..count)
+            .map(|index| {
+                let randomizer = Self::derive_randomizer(seed, index);
+                (randomizer, self.randomize(&randomizer))
+            })
+            .collect()
+    }
 }
 
 impl<T: SigType> VerificationKey<T> {
+    /// Decodes a verification key for use outside of Zcash consensus.
+    ///
+    /// Returns [`Error::MalformedVerificationKey`] if the bytes are not a
+    /// canonical point encoding, and [`Error::IdentityVerificationKey`] if the
+    /// point is the identity, for which any signature with the identity as `R`
+    /// and a zero `s` is valid. `TryFrom<[u8; 32]>` and
+    /// `TryFrom<VerificationKeyBytes>` only apply the consensus rules, which
+    /// allow the identity.
+    pub fn from_bytes_strict(bytes: [u8; 32]) -> Result<VerificationKey<T>, Error> {
+        let vk = VerificationKey::<T>::try_from(VerificationKeyBytes::from(bytes))?;
+        if vk.point.is_identity().into() {
+            Err(Error::IdentityVerificationKey)
+        } else {
+            Ok(vk)
+        }
+    }
+
     pub(crate) fn from(s: &T::Scalar) -> VerificationKey<T> {
-        let point = T::basepoint() * s;
+        let point = T::basepoint_mul(s);
         let bytes = VerificationKeyBytes {
             bytes: point.to_bytes().as_ref().try_into().unwrap(),
             _marker: PhantomData,
@@ -145,15 +252,28 @@ impl<T: SigType> VerificationKey<T> {
         VerificationKey { bytes, point }
     }
 
+    /// Returns the affine form of this verification key's point.
+    pub fn to_affine(&self) -> <T::Point as CofactorCurve>::Affine {
+        self.point.to_affine()
+    }
+
     /// Verify a purported `signature` over `msg` made by this verification key.
     // This is similar to impl signature::Verifier but without boxed errors
     pub fn verify(&self, msg: &[u8], signature: &Signature<T>) -> Result<(), Error> {
-        use crate::HStar;
-        let c = HStar::<T>::default()
-            .update(&signature.r_bytes[..])
-            .update(&self.bytes.bytes[..]) // XXX ugly
-            .update(msg)
-            .finalize();
+        let c = signature.challenge(&self.bytes, msg);
+        self.verify_prehashed(signature, c)
+    }
+
+    /// Verify a purported `signature` over `msg` made by this verification key
+    /// with [`SigningKey::sign_with_domain`](crate::SigningKey::sign_with_domain)
+    /// in the application domain `domain`.
+    pub fn verify_with_domain(
+        &self,
+        domain: &[u8],
+        msg: &[u8],
+        signature: &Signature<T>,
+    ) -> Result<(), Error> {
+        let c = signature.challenge_with_domain(&self.bytes, domain, msg);
         self.verify_prehashed(signature, c)
     }
 
@@ -164,37 +284,18 @@ impl<T: SigType> VerificationKey<T> {
         signature: &Signature<T>,
         c: T::Scalar,
     ) -> Result<(), Error> {
-        let r = {
-            // XXX-jubjub: should not use CtOption here
-            // XXX-jubjub: inconsistent ownership in from_bytes
-            let mut repr = <T::Point as GroupEncoding>::Repr::default();
-            repr.as_mut().copy_from_slice(&signature.r_bytes);
-            let maybe_point = T::Point::from_bytes(&repr);
-            if maybe_point.is_some().into() {
-                maybe_point.unwrap()
-            } else {
-                return Err(Error::InvalidSignature);
-            }
-        };
+        let (r, s) = decode_signature(signature).ok_or(Error::InvalidSignature)?;
 
-        let s = {
-            // XXX-jubjub: should not use CtOption here
-            let mut repr = <T::Scalar as PrimeField>::Repr::default();
-            repr.as_mut().copy_from_slice(&signature.s_bytes);
-            let maybe_scalar = T::Scalar::from_repr(repr);
-            if maybe_scalar.is_some().into() {
-                maybe_scalar.unwrap()
-            } else {
-                return Err(Error::InvalidSignature);
-            }
-        };
-
-        // XXX rewrite as normal double scalar mul
         // Verify check is h * ( - s * B + R  + c * A) == 0
         //                 h * ( s * B - c * A - R) == 0
-        let sB = T::basepoint() * s;
-        let cA = self.point * c;
-        let check = sB - cA - r;
+        #[cfg(feature = "alloc")]
+        let check = T::vartime_double_base_mul(&s, &-c, &self.point) - r;
+        #[cfg(not(feature = "alloc"))]
+        let check = {
+            let sB = T::basepoint() * s;
+            let cA = self.point * c;
+            sB - cA - r
+        };
 
         if check.is_small_order().into() {
             Ok(())
@@ -203,3 +304,114 @@ impl<T: SigType> VerificationKey<T> {
         }
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<T: SigType> VerificationKey<T> {
+    /// Verify many `(msg, signature)` pairs made by this verification key.
+    ///
+    /// The signatures are checked together with a random linear combination, as
+    /// in [`batch::Verifier`](crate::batch::Verifier), but since every signature
+    /// shares this key, its term collapses into a single scalar multiplication.
+    ///
+    /// Returns `Ok(())` if all signatures were valid, and otherwise the indices
+    /// of the invalid items, found by verifying each item on its own.
+    pub fn verify_many<R: RngCore + CryptoRng>(
+        &self,
+        mut rng: R,
+        items: &[(&[u8], Signature<T>)],
+    ) -> Result<(), Vec<usize>> {
+        let challenges: Vec<T::Scalar> = items
+            .iter()
+            .map(|(msg, signature)| signature.challenge(&self.bytes, msg))
+            .collect();
+
+        let mut s_acc = T::Scalar::zero();
+        let mut c_acc = T::Scalar::zero();
+        let mut scalars = Vec::with_capacity(items.len() + 2);
+        let mut points = Vec::with_capacity(items.len() + 2);
+        let mut all_decoded = true;
+
+        for ((_, signature), c) in items.iter().zip(challenges.iter()) {
+            let (r, s) = match decode_signature(signature) {
+                Some(decoded) => decoded,
+                None => {
+                    all_decoded = false;
+                    break;
+                }
+            };
+
+            let z = T::Scalar::from_raw(gen_128_bits(&mut rng));
+            s_acc += z * s;
+            c_acc += z * c;
+            scalars.push(-z);
+            points.push(r);
+        }
+
+        if all_decoded {
+            // h * ( [sum(z_i * s_i)]B - [sum(z_i * c_i)]A - sum([z_i]R_i) ) == 0
+            scalars.push(s_acc);
+            points.push(T::basepoint());
+            scalars.push(-c_acc);
+            points.push(self.point);
+
+            let check = T::Point::vartime_multiscalar_mul(scalars.iter(), points.iter());
+            if check.is_small_order().into() {
+                return Ok(());
+            }
+        }
+
+        let failures: Vec<usize> = items
+            .iter()
+            .zip(challenges)
+            .enumerate()
+            .filter(|(_, ((_, signature), c))| self.verify_prehashed(signature, *c).is_err())
+            .map(|(i, _)| i)
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+/// Decode the `R` point and `s` scalar of `signature`, or return `None` if
+/// either encoding is invalid.
+pub(crate) fn decode_signature<T: SigType>(
+    signature: &Signature<T>,
+) -> Option<(T::Point, T::Scalar)> {
+    let r = {
+        // XXX-jubjub: should not use CtOption here
+        // XXX-jubjub: inconsistent ownership in from_bytes
+        let mut repr = <T::Point as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&signature.r_bytes);
+        let maybe_point = T::Point::from_bytes(&repr);
+        if maybe_point.is_some().into() {
+            maybe_point.unwrap()
+        } else {
+            return None;
+        }
+    };
+
+    let s = {
+        // XXX-jubjub: should not use CtOption here
+        let mut repr = <T::Scalar as PrimeField>::Repr::default();
+        repr.as_mut().copy_from_slice(&signature.s_bytes);
+        let maybe_scalar = T::Scalar::from_repr(repr);
+        if maybe_scalar.is_some().into() {
+            maybe_scalar.unwrap()
+        } else {
+            return None;
+        }
+    };
+
+    Some((r, s))
+}
+
+#[cfg(feature = "signature")]
+impl<T: SigType> ::signature::Verifier<Signature<T>> for VerificationKey<T> {
+    fn verify(&self, msg: &[u8], signature: &Signature<T>) -> Result<(), ::signature::Error> {
+        VerificationKey::verify(self, msg, signature).map_err(|_| ::signature::Error::new())
+    }
+}
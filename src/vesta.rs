@@ -0,0 +1,230 @@
+//! Signature types over the Vesta curve.
+//!
+//! Vesta is the other half of the Pasta cycle: its scalar field is the base
+//! field of Pallas, and the reverse. Recursive proof systems built on the
+//! cycle can verify RedPallas signatures in circuits over one curve and
+//! RedVesta signatures in circuits over the other.
+//!
+//! Unlike [`orchard`](crate::orchard), these types are not part of any Zcash
+//! protocol, so the basepoints and the personalization of \\(H^\star\\) are
+//! specific to this crate. Each basepoint is found by try-and-increment: for
+//! `i = 0, 1, ...`, the BLAKE2b-512 hash of a tag and the byte `i`, with the
+//! personalization `"RedVesta_BasePnt"`, is reduced to a field element, and
+//! the first one that is the \\(x\\)-coordinate of a point gives the
+//! basepoint with an even \\(y\\)-coordinate. The tag is `"G"` for `SpendAuth`
+//! and `"r"` for `Binding`.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+
+use group::GroupEncoding;
+#[cfg(feature = "alloc")]
+use group::{ff::PrimeField, Group};
+use pasta_curves::vesta;
+
+use crate::{private, SigType};
+
+#[cfg(feature = "alloc")]
+use crate::scalar_mul::{
+    le_bytes_non_adjacent_form, naf_top, LookupTable5, NonAdjacentForm, VartimeMultiscalarMul,
+};
+
+/// The byte-encoding of the basepoint for RedVesta `SpendAuth` signatures.
+pub const SPENDAUTHSIG_BASEPOINT_BYTES: [u8; 32] = [
+    157, 65, 254, 19, 42, 53, 188, 82, 117, 96, 165, 78, 129, 184, 188, 214, 104, 159, 52, 97, 133,
+    104, 68, 160, 186, 114, 116, 91, 209, 12, 147, 4,
+];
+
+/// The byte-encoding of the basepoint for RedVesta `Binding` signatures.
+pub const BINDINGSIG_BASEPOINT_BYTES: [u8; 32] = [
+    192, 58, 63, 198, 69, 189, 68, 118, 33, 202, 35, 248, 45, 3, 144, 23, 191, 197, 165, 209, 46,
+    223, 251, 4, 134, 48, 39, 143, 98, 121, 112, 32,
+];
+
+/// A type variable corresponding to spend authorization signatures over
+/// Vesta, the analogue of Orchard's `SpendAuthSig`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SpendAuth {}
+// This should not exist, but is necessary to use zeroize::DefaultIsZeroes.
+impl Default for SpendAuth {
+    fn default() -> Self {
+        unimplemented!()
+    }
+}
+impl SigType for SpendAuth {
+    const BASEPOINT_BYTES: [u8; 32] = SPENDAUTHSIG_BASEPOINT_BYTES;
+}
+impl super::SpendAuth for SpendAuth {}
+
+/// A type variable corresponding to binding signatures over Vesta, the
+/// analogue of Orchard's `BindingSig`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Binding {}
+// This should not exist, but is necessary to use zeroize::DefaultIsZeroes.
+impl Default for Binding {
+    fn default() -> Self {
+        unimplemented!()
+    }
+}
+impl SigType for Binding {
+    const BASEPOINT_BYTES: [u8; 32] = BINDINGSIG_BASEPOINT_BYTES;
+}
+impl super::Binding for Binding {}
+
+impl private::SealedScalar for vesta::Scalar {
+    fn from_bytes_wide(bytes: &[u8; 64]) -> Self {
+        <vesta::Scalar as pasta_curves::arithmetic::FieldExt>::from_bytes_wide(bytes)
+    }
+    fn from_raw(val: [u64; 4]) -> Self {
+        vesta::Scalar::from_raw(val)
+    }
+}
+impl private::Sealed<SpendAuth> for SpendAuth {
+    const H_STAR_PERSONALIZATION: &'static [u8; 16] = b"Pasta_RedVesta_H";
+    type Point = vesta::Point;
+    type Scalar = vesta::Scalar;
+
+    fn basepoint() -> vesta::Point {
+        vesta::Point::from_bytes(&SPENDAUTHSIG_BASEPOINT_BYTES).unwrap()
+    }
+}
+impl private::Sealed<Binding> for Binding {
+    const H_STAR_PERSONALIZATION: &'static [u8; 16] = b"Pasta_RedVesta_H";
+    type Point = vesta::Point;
+    type Scalar = vesta::Scalar;
+
+    fn basepoint() -> vesta::Point {
+        vesta::Point::from_bytes(&BINDINGSIG_BASEPOINT_BYTES).unwrap()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl NonAdjacentForm for vesta::Scalar {
+    fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
+        le_bytes_non_adjacent_form(self.to_repr().as_ref(), w)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a vesta::Point> for LookupTable5<vesta::Point> {
+    #[allow(non_snake_case)]
+    fn from(A: &'a vesta::Point) -> Self {
+        let mut Ai = [*A; 8];
+        let A2 = A.double();
+        for i in 0..7 {
+            Ai[i + 1] = A2 + Ai[i];
+        }
+        // Now Ai = [A, 3A, 5A, 7A, 9A, 11A, 13A, 15A]
+        LookupTable5(Ai)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VartimeMultiscalarMul for vesta::Point {
+    type Scalar = vesta::Scalar;
+    type Point = vesta::Point;
+
+    #[allow(non_snake_case)]
+    fn optional_multiscalar_mul<I, J>(scalars: I, points: J) -> Option<vesta::Point>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Self::Scalar>,
+        J: IntoIterator<Item = Option<vesta::Point>>,
+    {
+        let nafs: Vec<_> = scalars
+            .into_iter()
+            .map(|c| c.borrow().non_adjacent_form(5))
+            .collect();
+
+        let lookup_tables = points
+            .into_iter()
+            .map(|P_opt| P_opt.map(|P| LookupTable5::<vesta::Point>::from(&P)))
+            .collect::<Option<Vec<_>>>()?;
+
+        let top = match naf_top(nafs.iter()) {
+            Some(top) => top,
+            None => return Some(vesta::Point::identity()),
+        };
+
+        let mut r = vesta::Point::identity();
+
+        for i in (0..=top).rev() {
+            let mut t = r.double();
+
+            for (naf, lookup_table) in nafs.iter().zip(lookup_tables.iter()) {
+                if naf[i] > 0 {
+                    t += lookup_table.select(naf[i] as usize);
+                } else if naf[i] < 0 {
+                    t -= lookup_table.select(-naf[i] as usize);
+                }
+            }
+
+            r = t;
+        }
+
+        Some(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{ff::PrimeField, GroupEncoding};
+    use pasta_curves::{arithmetic::FieldExt, vesta};
+
+    /// Returns the first valid point encoding derived from `tag`, as described
+    /// in the module documentation.
+    fn derive_basepoint(tag: &[u8]) -> [u8; 32] {
+        (0..=u8::MAX)
+            .map(|i| {
+                let hash = blake2b_simd::Params::new()
+                    .hash_length(64)
+                    .personal(b"RedVesta_BasePnt")
+                    .to_state()
+                    .update(tag)
+                    .update(&[i])
+                    .finalize();
+                vesta::Base::from_bytes_wide(hash.as_array()).to_repr()
+            })
+            .find(|bytes| vesta::Point::from_bytes(bytes).is_some().into())
+            .unwrap()
+    }
+
+    #[test]
+    fn vesta_spendauth_basepoint() {
+        assert_eq!(derive_basepoint(b"G"), super::SPENDAUTHSIG_BASEPOINT_BYTES);
+    }
+
+    #[test]
+    fn vesta_binding_basepoint() {
+        assert_eq!(derive_basepoint(b"r"), super::BINDINGSIG_BASEPOINT_BYTES);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[allow(non_snake_case)]
+    fn multiscalar_mul_matches_scalar_mul() {
+        use crate::scalar_mul::VartimeMultiscalarMul;
+        use alloc::vec::Vec;
+        use group::{ff::Field, Group};
+        use rand::thread_rng;
+
+        for n in [0, 1, 2, 7] {
+            let scalars: Vec<_> = (0..n)
+                .map(|_| vesta::Scalar::random(thread_rng()))
+                .collect();
+            let points: Vec<_> = (0..n).map(|_| vesta::Point::random(thread_rng())).collect();
+
+            let expected = scalars
+                .iter()
+                .zip(points.iter())
+                .fold(vesta::Point::identity(), |acc, (c, P)| acc + P * c);
+
+            assert_eq!(
+                vesta::Point::vartime_multiscalar_mul(scalars.iter(), points.iter()),
+                expected
+            );
+        }
+    }
+}
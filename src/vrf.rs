@@ -0,0 +1,208 @@
+// -*- mode: rust; -*-
+//
+// This file is part of reddsa.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! A verifiable random function (VRF) over the Pallas group, using RedPallas
+//! keys.
+//!
+//! The holder of a [`SigningKey`] can [`prove`] the pseudorandom [`Output`] for
+//! an input `alpha`, and anyone holding the matching [`VerificationKey`] can
+//! [`verify`] the [`Proof`] and obtain the same output. The output is
+//! determined by the key and the input alone, and is unpredictable without
+//! the signing key.
+//!
+//! The construction follows ECVRF ([RFC 9381]) with the hash functions of this
+//! crate. For a signing key \\(x\\) with verification key \\(Y = xB\\):
+//!
+//! - \\(H\\) is the hash of \\(\underline{Y} || \alpha\\) to the curve, with the
+//!   domain `z.cash:RedPallas-VRF`;
+//! - \\(\Gamma = xH\\);
+//! - the nonce is \\(k = H^\star(\texttt{"VRF\\_Nonce"} || \underline{x} || \underline{H})\\);
+//! - \\(c = H^\star(\texttt{"VRF\\_Challenge"} || \underline{Y} || \underline{H} ||
+//!   \underline{\Gamma} || \underline{kB} || \underline{kH})\\);
+//! - \\(s = k + cx\\),
+//!
+//! and the proof is \\((\underline{\Gamma}, \underline{c}, \underline{s})\\). The
+//! output is the BLAKE2b-512 hash of \\(\underline{\Gamma}\\), personalized with
+//! `RedPallas_VRFOut`.
+//!
+//! [RFC 9381]: https://www.rfc-editor.org/rfc/rfc9381
+
+use core::{convert::TryInto, marker::PhantomData};
+
+use blake2b_simd::Params;
+use group::{ff::PrimeField, Group, GroupEncoding};
+use pasta_curves::{arithmetic::CurveExt, pallas};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use crate::{signing_key::SecretScalar, Error, HStar, SigType, SigningKey, VerificationKey};
+
+/// The domain of the hash of the VRF input to the curve.
+const HASH_TO_CURVE_DOMAIN: &str = "z.cash:RedPallas-VRF";
+
+/// The BLAKE2b personalization of the VRF output.
+const OUTPUT_PERSONALIZATION: &[u8; 16] = b"RedPallas_VRFOut";
+
+/// The length of the encoding of a [`Proof`].
+pub const PROOF_BYTES: usize = 96;
+
+/// A VRF proof, showing that an [`Output`] was computed with the signing key
+/// of a [`VerificationKey`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Proof<T: SigType> {
+    gamma_bytes: [u8; 32],
+    c_bytes: [u8; 32],
+    s_bytes: [u8; 32],
+    _marker: PhantomData<T>,
+}
+
+impl<T: SigType> From<[u8; PROOF_BYTES]> for Proof<T> {
+    fn from(bytes: [u8; PROOF_BYTES]) -> Proof<T> {
+        Proof {
+            gamma_bytes: bytes[0..32].try_into().unwrap(),
+            c_bytes: bytes[32..64].try_into().unwrap(),
+            s_bytes: bytes[64..96].try_into().unwrap(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: SigType> From<Proof<T>> for [u8; PROOF_BYTES] {
+    fn from(proof: Proof<T>) -> [u8; PROOF_BYTES] {
+        let mut bytes = [0; PROOF_BYTES];
+        bytes[0..32].copy_from_slice(&proof.gamma_bytes[..]);
+        bytes[32..64].copy_from_slice(&proof.c_bytes[..]);
+        bytes[64..96].copy_from_slice(&proof.s_bytes[..]);
+        bytes
+    }
+}
+
+/// The pseudorandom output of the VRF.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Output(pub [u8; 64]);
+
+impl<T: SigType> Proof<T> {
+    /// Returns the VRF output that this proof claims, without verifying the
+    /// proof.
+    ///
+    /// Use [`verify`] to obtain the output of an untrusted proof.
+    pub fn output(&self) -> Result<Output, Error> {
+        decode_point(&self.gamma_bytes)?;
+        Ok(output(&self.gamma_bytes))
+    }
+}
+
+fn output(gamma_bytes: &[u8; 32]) -> Output {
+    let hash = Params::new()
+        .hash_length(64)
+        .personal(OUTPUT_PERSONALIZATION)
+        .to_state()
+        .update(gamma_bytes)
+        .finalize();
+    Output(*hash.as_array())
+}
+
+fn decode_point(bytes: &[u8; 32]) -> Result<pallas::Point, Error> {
+    Option::from(pallas::Point::from_bytes(bytes)).ok_or(Error::InvalidSignature)
+}
+
+fn decode_scalar(bytes: &[u8; 32]) -> Result<pallas::Scalar, Error> {
+    Option::from(pallas::Scalar::from_repr(*bytes)).ok_or(Error::InvalidSignature)
+}
+
+/// Hashes the verification key and the VRF input to a point.
+fn hash_to_curve(vk_bytes: &[u8; 32], alpha: &[u8]) -> pallas::Point {
+    let mut input = alloc::vec::Vec::with_capacity(32 + alpha.len());
+    input.extend_from_slice(vk_bytes);
+    input.extend_from_slice(alpha);
+    pallas::Point::hash_to_curve(HASH_TO_CURVE_DOMAIN)(&input)
+}
+
+fn challenge<T: SigType<Point = pallas::Point, Scalar = pallas::Scalar>>(
+    vk_bytes: &[u8; 32],
+    h: &pallas::Point,
+    gamma: &pallas::Point,
+    u: &pallas::Point,
+    v: &pallas::Point,
+) -> pallas::Scalar {
+    HStar::<T>::default()
+        .update(b"VRF_Challenge")
+        .update(vk_bytes)
+        .update(h.to_bytes())
+        .update(gamma.to_bytes())
+        .update(u.to_bytes())
+        .update(v.to_bytes())
+        .finalize()
+}
+
+/// Computes the VRF output for `alpha` under `sk`, together with a proof of
+/// its correctness.
+///
+/// Proving is deterministic: the same key and input always give the same
+/// proof.
+pub fn prove<T: SigType<Point = pallas::Point, Scalar = pallas::Scalar>>(
+    sk: &SigningKey<T>,
+    alpha: &[u8],
+) -> (Output, Proof<T>) {
+    let x = sk.sk.0;
+    let vk_bytes = sk.pk.bytes.bytes;
+
+    let h = hash_to_curve(&vk_bytes, alpha);
+    let gamma = h * x;
+
+    #[allow(unused_mut)]
+    let mut k = SecretScalar(
+        HStar::<T>::default()
+            .update(b"VRF_Nonce")
+            .update(x.to_repr())
+            .update(h.to_bytes())
+            .finalize(),
+    );
+    let c = challenge::<T>(&vk_bytes, &h, &gamma, &T::basepoint_mul(&k.0), &(h * k.0));
+    let s = k.0 + c * x;
+    #[cfg(feature = "zeroize")]
+    k.zeroize();
+
+    let gamma_bytes = gamma.to_bytes();
+    let proof = Proof {
+        gamma_bytes,
+        c_bytes: c.to_repr(),
+        s_bytes: s.to_repr(),
+        _marker: PhantomData,
+    };
+    (output(&gamma_bytes), proof)
+}
+
+/// Verifies that `proof` is a VRF proof for `alpha` under `vk`, and returns
+/// the VRF output.
+///
+/// Returns [`Error::IdentityVerificationKey`] if `vk` is the identity, for
+/// which anyone can prove any output. The Pallas group has prime order, so
+/// the identity is its only point of small order, and the VRF is not defined
+/// over Jubjub, whose small-order points would need the same check.
+pub fn verify<T: SigType<Point = pallas::Point, Scalar = pallas::Scalar>>(
+    vk: &VerificationKey<T>,
+    alpha: &[u8],
+    proof: &Proof<T>,
+) -> Result<Output, Error> {
+    if vk.point.is_identity().into() {
+        return Err(Error::IdentityVerificationKey);
+    }
+
+    let gamma = decode_point(&proof.gamma_bytes)?;
+    let c = decode_scalar(&proof.c_bytes)?;
+    let s = decode_scalar(&proof.s_bytes)?;
+
+    let h = hash_to_curve(&vk.bytes.bytes, alpha);
+    let u = T::basepoint() * s - vk.point * c;
+    let v = h * s - gamma * c;
+
+    if challenge::<T>(&vk.bytes.bytes, &h, &gamma, &u, &v) == c {
+        Ok(output(&proof.gamma_bytes))
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
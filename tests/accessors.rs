@@ -0,0 +1,81 @@
+use std::convert::TryInto;
+
+use group::{ff::Field, ff::PrimeField, Curve, GroupEncoding};
+use pasta_curves::pallas;
+use rand::thread_rng;
+
+use reddsa::*;
+
+#[test]
+fn orchard_witness_accessors() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    let vk = VerificationKey::from(&sk);
+    let msg = b"witness";
+    let sig = sk.sign(&mut rng, msg);
+
+    let sig_bytes: [u8; 64] = sig.into();
+    let vk_bytes: [u8; 32] = vk.into();
+    let sk_bytes: [u8; 32] = sk.into();
+
+    let r: pallas::Affine = sig.r_affine().unwrap();
+    let vk_affine: pallas::Affine = vk.to_affine();
+    assert_eq!(r.to_bytes(), sig_bytes[..32]);
+    assert_eq!(vk_affine.to_bytes(), vk_bytes);
+
+    // The signature equation s·B = R + c·vk holds with the exported challenge,
+    // where B = sk^-1·vk.
+    let c: pallas::Scalar = sig.challenge(&vk.into(), msg);
+    let s = pallas::Scalar::from_repr(sig_bytes[32..].try_into().unwrap()).unwrap();
+    let sk = pallas::Scalar::from_repr(sk_bytes).unwrap();
+    let basepoint = pallas::Point::from(vk_affine) * sk.invert().unwrap();
+    assert_eq!(
+        (basepoint * s).to_affine(),
+        (pallas::Point::from(r) + pallas::Point::from(vk_affine) * c).to_affine()
+    );
+}
+
+#[test]
+fn invalid_r_has_no_affine_form() {
+    let mut bytes = [0xff; 64];
+    bytes[32..].copy_from_slice(&[0; 32]);
+    assert_eq!(
+        Signature::<orchard::SpendAuth>::try_from(bytes),
+        Err(Error::MalformedSignaturePoint)
+    );
+
+    // Deserialization checks the encodings too.
+    #[cfg(feature = "serde")]
+    assert!(bincode::deserialize::<Signature<orchard::SpendAuth>>(&bytes).is_err());
+}
+
+/// Checks that `T::BASEPOINT_BYTES` is the verification key of the signing key
+/// one, so it is the basepoint that signatures are made with.
+fn check_basepoint<T: SigType>() {
+    let mut one = [0; 32];
+    one[0] = 1;
+    let sk = SigningKey::<T>::try_from(one).unwrap();
+    assert_eq!(
+        <[u8; 32]>::from(VerificationKey::from(&sk)),
+        T::BASEPOINT_BYTES
+    );
+}
+
+#[test]
+fn public_basepoints() {
+    check_basepoint::<sapling::SpendAuth>();
+    check_basepoint::<sapling::Binding>();
+    check_basepoint::<orchard::SpendAuth>();
+    check_basepoint::<orchard::Binding>();
+    check_basepoint::<vesta::SpendAuth>();
+    check_basepoint::<vesta::Binding>();
+
+    assert_eq!(
+        sapling::SpendAuth::BASEPOINT_BYTES,
+        sapling::SPENDAUTHSIG_BASEPOINT_BYTES
+    );
+    assert_eq!(
+        orchard::Binding::BASEPOINT_BYTES,
+        orchard::BINDINGSIG_BASEPOINT_BYTES
+    );
+}
@@ -0,0 +1,92 @@
+use std::convert::TryFrom;
+
+use rand::thread_rng;
+
+use reddsa::{adaptor::*, *};
+
+#[test]
+fn orchard_adaptor_swap() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    let vk = VerificationKey::from(&sk);
+    let msg = b"atomic swap";
+
+    let secret = AdaptorSecret::new(&mut rng);
+    let adaptor = AdaptorPoint::from(&secret);
+
+    let pre_sig = sk.pre_sign(&mut rng, &adaptor, msg);
+    assert!(vk.verify_pre_signature(&adaptor, msg, &pre_sig).is_ok());
+    assert!(vk
+        .verify_pre_signature(&adaptor, b"another message", &pre_sig)
+        .is_err());
+
+    // A pre-signature is not a valid signature until it is adapted.
+    let unadapted = Signature::try_from(<[u8; 64]>::from(pre_sig)).unwrap();
+    assert!(vk.verify(msg, &unadapted).is_err());
+
+    let sig = pre_sig.adapt(&secret).unwrap();
+    assert!(vk.verify(msg, &sig).is_ok());
+
+    let extracted = pre_sig.extract(&adaptor, &sig).unwrap();
+    assert_eq!(<[u8; 32]>::from(&extracted), <[u8; 32]>::from(&secret));
+
+    // An unrelated signature reveals nothing.
+    let other_sig = sk.sign(&mut rng, msg);
+    assert_eq!(
+        pre_sig.extract(&adaptor, &other_sig).err(),
+        Some(Error::InvalidSignature)
+    );
+}
+
+#[test]
+fn adaptor_encodings_roundtrip() {
+    let secret = AdaptorSecret::<sapling::SpendAuth>::new(thread_rng());
+    let adaptor = AdaptorPoint::from(&secret);
+
+    let secret_bytes = <[u8; 32]>::from(&secret);
+    let decoded = AdaptorSecret::<sapling::SpendAuth>::try_from(secret_bytes).unwrap();
+    assert_eq!(AdaptorPoint::from(&decoded), adaptor);
+    assert_eq!(
+        AdaptorPoint::try_from(<[u8; 32]>::from(adaptor)).unwrap(),
+        adaptor
+    );
+}
+
+#[test]
+fn sapling_adaptor_points_with_torsion_are_rejected() {
+    use group::GroupEncoding;
+    use jubjub::{AffinePoint, ExtendedPoint, Fq};
+
+    let secret = AdaptorSecret::<sapling::SpendAuth>::new(thread_rng());
+    let adaptor = AdaptorPoint::from(&secret);
+
+    // A point of order 4, T4.
+    let order4 =
+        AffinePoint::from_bytes(AffinePoint::from_raw_unchecked(Fq::one(), Fq::zero()).to_bytes())
+            .unwrap();
+    assert!(bool::from(order4.is_small_order()));
+
+    // With T = tB + T4, an adapted signature would still verify, but the
+    // pre-signer could not extract t from it.
+    let point = ExtendedPoint::from_bytes(&<[u8; 32]>::from(adaptor)).unwrap()
+        + ExtendedPoint::from(order4);
+    assert_eq!(
+        AdaptorPoint::<sapling::SpendAuth>::try_from(point.to_bytes()).err(),
+        Some(Error::InvalidAdaptorPoint)
+    );
+
+    // With the identity, the pre-signature would already be a signature.
+    assert_eq!(
+        AdaptorPoint::<sapling::SpendAuth>::try_from(AffinePoint::identity().to_bytes()).err(),
+        Some(Error::InvalidAdaptorPoint)
+    );
+
+    assert_eq!(
+        AdaptorPoint::<sapling::SpendAuth>::try_from([0xff; 32]).err(),
+        Some(Error::MalformedAdaptorPoint)
+    );
+    assert_eq!(
+        AdaptorSecret::<sapling::SpendAuth>::try_from([0xff; 32]).err(),
+        Some(Error::MalformedAdaptorSecret)
+    );
+}
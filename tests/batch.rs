@@ -87,7 +87,7 @@ fn bad_batch_verify() {
             }
             _ => unreachable!(),
         };
-        items.push(item.clone());
+        items.push(item);
         batch.queue(item);
     }
     assert!(batch.verify(rng).is_err());
@@ -99,3 +99,411 @@ fn bad_batch_verify() {
         }
     }
 }
+
+#[test]
+fn batch_verify_with_failures() {
+    let mut rng = thread_rng();
+    let bad_indices: [usize; 3] = [3, 20, 21];
+    let mut batch = batch::Verifier::<_, sapling::Binding>::new();
+    for i in 0..32 {
+        let sk = SigningKey::<sapling::SpendAuth>::new(&mut rng);
+        let vk = VerificationKey::from(&sk);
+        let msg = b"BatchVerifyTest";
+        let sig = if bad_indices.contains(&i) {
+            sk.sign(&mut rng, b"bad")
+        } else {
+            sk.sign(&mut rng, &msg[..])
+        };
+        batch.queue(batch::Item::from_spendauth(vk.into(), sig, msg));
+    }
+    assert_eq!(
+        batch.verify_with_failures(&mut rng),
+        Err(bad_indices.to_vec())
+    );
+
+    let mut batch = batch::Verifier::<_, sapling::Binding>::new();
+    for _ in 0..5 {
+        let sk = SigningKey::<sapling::SpendAuth>::new(&mut rng);
+        let vk = VerificationKey::from(&sk);
+        let msg = b"BatchVerifyTest";
+        let sig = sk.sign(&mut rng, &msg[..]);
+        batch.queue(batch::Item::from_spendauth(vk.into(), sig, msg));
+    }
+    assert_eq!(batch.verify_with_failures(rng), Ok(()));
+}
+
+#[test]
+fn batch_verify_each_modes() {
+    let mut rng = thread_rng();
+    let queue = |mode, rng: &mut rand::rngs::ThreadRng| {
+        let mut batch = batch::Verifier::<sapling::SpendAuth, sapling::Binding>::with_mode(mode);
+        for i in 0..8 {
+            let sk = SigningKey::<sapling::SpendAuth>::new(&mut *rng);
+            let sig = sk.sign(&mut *rng, b"modes");
+            let vk_bytes = if i == 5 {
+                [0xff; 32].into()
+            } else {
+                VerificationKey::from(&sk).into()
+            };
+            let msg: &[u8] = if i == 2 { b"other" } else { b"modes" };
+            batch.queue(batch::Item::from_spendauth(vk_bytes, sig, &msg));
+        }
+        batch
+    };
+
+    let batch = queue(batch::Mode::Exhaustive, &mut rng);
+    assert_eq!(batch.mode(), batch::Mode::Exhaustive);
+    let results = batch.verify_each(&mut rng);
+    for (i, result) in results.into_iter().enumerate() {
+        match i {
+            2 => assert_eq!(result, Err(Error::InvalidSignature)),
+            5 => assert_eq!(result, Err(Error::MalformedVerificationKey)),
+            _ => assert_eq!(result, Ok(())),
+        }
+    }
+
+    // A fail-fast verifier reports the batch's error for every item.
+    let batch = queue(batch::Mode::FailFast, &mut rng);
+    let results = batch.verify_each(&mut rng);
+    assert_eq!(results.len(), 8);
+    assert!(results.iter().all(|result| result.is_err()));
+
+    assert_eq!(
+        batch::Verifier::<sapling::SpendAuth, sapling::Binding>::new().mode(),
+        batch::Mode::FailFast
+    );
+}
+
+#[test]
+fn chunked_batch_verify() {
+    let mut rng = thread_rng();
+    let queue = |bad: Option<usize>, rng: &mut rand::rngs::ThreadRng| {
+        let mut batch = batch::Verifier::<sapling::SpendAuth, sapling::Binding>::new();
+        batch.set_max_batch_size(5);
+        for i in 0..23 {
+            let sk = SigningKey::<sapling::SpendAuth>::new(&mut *rng);
+            let msg: &[u8] = if Some(i) == bad { b"other" } else { b"chunks" };
+            let sig = sk.sign(&mut *rng, b"chunks");
+            batch.queue(batch::Item::from_spendauth(
+                VerificationKey::from(&sk).into(),
+                sig,
+                &msg,
+            ));
+        }
+        batch
+    };
+
+    let batch = queue(None, &mut rng);
+    assert_eq!(batch.max_batch_size(), Some(5));
+    assert!(batch.verify(&mut rng).is_ok());
+
+    // An invalid item in the last, partial chunk fails the batch, and is
+    // reported at its index among all the queued items.
+    assert_eq!(
+        queue(Some(21), &mut rng).verify(&mut rng),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!(
+        queue(Some(21), &mut rng).verify_with_failures(&mut rng),
+        Err(vec![21])
+    );
+    assert_eq!(
+        queue(Some(7), &mut rng).verify_with_failures(&mut rng),
+        Err(vec![7])
+    );
+}
+
+#[test]
+fn merged_batch_verify() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<batch::Verifier<sapling::SpendAuth, sapling::Binding>>();
+    assert_send_sync::<batch::Verifier<orchard::SpendAuth, orchard::Binding>>();
+
+    let verifiers: Vec<batch::Verifier<sapling::SpendAuth, sapling::Binding>> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                let mut rng = thread_rng();
+                let mut batch = batch::Verifier::new();
+                for _ in 0..8 {
+                    let sk = SigningKey::<sapling::SpendAuth>::new(&mut rng);
+                    let vk = VerificationKey::from(&sk);
+                    let msg = b"BatchVerifyTest";
+                    let sig = sk.sign(&mut rng, &msg[..]);
+                    batch.queue(batch::Item::from_spendauth(vk.into(), sig, msg));
+                }
+                batch
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    let mut batch = batch::Verifier::new();
+    for verifier in verifiers {
+        batch.merge(verifier);
+    }
+    assert!(batch.verify(thread_rng()).is_ok());
+}
+
+#[test]
+fn batch_item_bytes_roundtrip() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::new();
+    for i in 0..8 {
+        let msg = b"BatchVerifyTest";
+        let item: batch::Item<sapling::SpendAuth, sapling::Binding> = if i % 2 == 0 {
+            let sk = SigningKey::<sapling::SpendAuth>::new(&mut rng);
+            let sig = sk.sign(&mut rng, &msg[..]);
+            batch::Item::from_spendauth(VerificationKey::from(&sk).into(), sig, msg)
+        } else {
+            let sk = SigningKey::<sapling::Binding>::new(&mut rng);
+            let sig = sk.sign(&mut rng, &msg[..]);
+            batch::Item::from_binding(VerificationKey::from(&sk).into(), sig, msg)
+        };
+
+        let bytes = item.to_bytes();
+        let decoded =
+            batch::Item::<sapling::SpendAuth, sapling::Binding>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+
+        #[cfg(feature = "serde")]
+        let decoded: batch::Item<sapling::SpendAuth, sapling::Binding> =
+            bincode::deserialize(&bincode::serialize(&decoded).unwrap()).unwrap();
+
+        batch.queue(decoded);
+    }
+    assert!(batch.verify(rng).is_ok());
+
+    let mut bytes = [0; batch::ITEM_BYTES];
+    bytes[0] = 2;
+    assert_eq!(
+        batch::Item::<sapling::SpendAuth, sapling::Binding>::from_bytes(&bytes).err(),
+        Some(Error::MalformedBatchItem)
+    );
+}
+
+#[test]
+fn verify_many_under_one_key() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    let vk = VerificationKey::from(&sk);
+    let msgs: Vec<[u8; 8]> = (0..16u64).map(|i| i.to_le_bytes()).collect();
+    let mut items: Vec<(&[u8], Signature<orchard::SpendAuth>)> = msgs
+        .iter()
+        .map(|msg| (&msg[..], sk.sign(&mut rng, &msg[..])))
+        .collect();
+    assert_eq!(vk.verify_many(&mut rng, &items), Ok(()));
+    assert_eq!(vk.verify_many(&mut rng, &[]), Ok(()));
+
+    // Swap the signatures of two items, so that both are invalid.
+    let sig_3 = items[3].1;
+    items[3].1 = items[11].1;
+    items[11].1 = sig_3;
+    assert_eq!(vk.verify_many(&mut rng, &items), Err(vec![3, 11]));
+}
+
+#[test]
+fn batch_verify_from_bytes() {
+    let mut rng = thread_rng();
+    let sks: Vec<_> = (0..4)
+        .map(|_| SigningKey::<orchard::SpendAuth>::new(&mut rng))
+        .collect();
+
+    let mut batch = batch::Verifier::<orchard::SpendAuth, orchard::Binding>::new();
+    // Several signatures under each key, so that keys are repeated in the batch.
+    for (i, sk) in sks.iter().cycle().take(16).enumerate() {
+        let sighash = [i as u8; 32];
+        let rk_bytes: [u8; 32] = VerificationKey::from(sk).into();
+        let sig_bytes: [u8; 64] = sk.sign(&mut rng, &sighash).into();
+        batch.queue_bytes(rk_bytes, &sighash, sig_bytes);
+    }
+    assert!(batch.verify(&mut rng).is_ok());
+
+    // An undecodable key fails the batch.
+    let mut batch = batch::Verifier::<orchard::SpendAuth, orchard::Binding>::new();
+    let sig_bytes: [u8; 64] = sks[0].sign(&mut rng, b"sighash").into();
+    batch.queue_bytes([0xff; 32], b"sighash", sig_bytes);
+    assert_eq!(batch.verify(&mut rng), Err(Error::MalformedVerificationKey));
+}
+
+#[test]
+fn batch_verify_repeated_keys() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<sapling::Binding>::new(&mut rng);
+    let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+
+    let mut batch = batch::Verifier::<sapling::SpendAuth, _>::new();
+    for i in 0..8u8 {
+        let sig = sk.sign(&mut rng, &[i]);
+        batch.queue(batch::Item::from_binding(vk_bytes, sig, &[i]));
+    }
+    assert!(batch.verify(&mut rng).is_ok());
+
+    // A signature on the wrong message still fails when its key is shared.
+    let mut batch = batch::Verifier::<sapling::SpendAuth, _>::new();
+    for i in 0..8u8 {
+        let sig = sk.sign(&mut rng, &[i]);
+        batch.queue(batch::Item::from_binding(
+            vk_bytes,
+            sig,
+            &[i ^ (i == 5) as u8],
+        ));
+    }
+    assert_eq!(batch.verify_with_failures(&mut rng), Err(vec![5]));
+}
+
+#[test]
+fn vesta_batch_verify() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::new();
+    for i in 0..16u8 {
+        let msg = [i];
+        let item = if i % 2 == 0 {
+            let sk = SigningKey::<vesta::SpendAuth>::new(&mut rng);
+            let sig = sk.sign(&mut rng, &msg);
+            batch::Item::from_spendauth(VerificationKey::from(&sk).into(), sig, &msg)
+        } else {
+            let sk = SigningKey::<vesta::Binding>::new(&mut rng);
+            let sig = sk.sign(&mut rng, &msg);
+            batch::Item::from_binding(VerificationKey::from(&sk).into(), sig, &msg)
+        };
+        batch.queue(item);
+    }
+    assert!(batch.verify(&mut rng).is_ok());
+
+    let sk = SigningKey::<vesta::SpendAuth>::new(&mut rng);
+    let sig = sk.sign(&mut rng, b"message");
+    let vk = VerificationKey::from(&sk);
+    assert!(vk.verify(b"message", &sig).is_ok());
+    assert!(vk.verify(b"other message", &sig).is_err());
+}
+
+#[test]
+fn fixed_batch_verify() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<sapling::SpendAuth>::new(&mut rng);
+    let mut batch = batch::FixedVerifier::<_, sapling::Binding, 8>::new();
+    let mut items = Vec::new();
+    for i in 0..8u8 {
+        let item = if i % 4 == 3 {
+            let sk = SigningKey::<sapling::Binding>::new(&mut rng);
+            let sig = sk.sign(&mut rng, &[i]);
+            batch::Item::from_binding(VerificationKey::from(&sk).into(), sig, &[i])
+        } else {
+            // Several signatures under one key, which share a term.
+            let sig = sk.sign(&mut rng, &[i]);
+            batch::Item::from_spendauth(VerificationKey::from(&sk).into(), sig, &[i])
+        };
+        assert!(!batch.is_full());
+        batch.queue(item).unwrap();
+        items.push(item);
+    }
+    assert!(batch.is_full());
+    assert_eq!(batch.queue(items[0]), Err(Error::BatchFull));
+    assert_eq!(batch.len(), 8);
+    assert!(batch.verify(&mut rng).is_ok());
+
+    // The fixed verifier agrees with the allocating one on a bad batch.
+    let bad_sig = sk.sign(&mut rng, b"bad");
+    items[1] = batch::Item::from_spendauth(VerificationKey::from(&sk).into(), bad_sig, &[1u8]);
+    let mut batch = batch::FixedVerifier::<_, _, 16>::new();
+    let mut alloc_batch = batch::Verifier::new();
+    for item in &items {
+        batch.queue(*item).unwrap();
+        alloc_batch.queue(*item);
+    }
+    assert_eq!(batch.verify(&mut rng), Err(Error::InvalidSignature));
+    assert_eq!(alloc_batch.verify(&mut rng), Err(Error::InvalidSignature));
+
+    assert!(
+        batch::FixedVerifier::<sapling::SpendAuth, sapling::Binding, 4>::new()
+            .verify(&mut rng)
+            .is_ok()
+    );
+}
+
+/// An RNG that repeats the same word forever, so that every batch weight drawn
+/// from it is the same.
+struct RepeatingRng(u64);
+
+impl rand_core::RngCore for RepeatingRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0 as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest.iter_mut() {
+            *byte = self.0 as u8;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl rand_core::CryptoRng for RepeatingRng {}
+
+/// The batch equation weighs each item by a random 128-bit scalar z_i. Two
+/// invalid signatures whose errors cancel out, (R_1, s_1 + d) and
+/// (R_2, s_2 - d), leave the error term -(z_1 - z_2) * d * P in the batch
+/// equation, which only vanishes if z_1 = z_2. Since z_2 is drawn uniformly
+/// from 2^128 values independently of z_1, such a batch is accepted with
+/// probability 2^-128. The same argument bounds the probability of accepting
+/// any invalid batch by 2^-128, which matches the 128-bit security level of
+/// the curves at half the cost of full-width weights.
+#[test]
+fn batch_weights_soundness_bound() {
+    use group::ff::PrimeField;
+    use std::convert::TryFrom;
+
+    let mut rng = thread_rng();
+    let sk = SigningKey::<sapling::SpendAuth>::new(&mut rng);
+    let vk = VerificationKey::from(&sk);
+    let msg = b"BatchVerifyTest";
+
+    let shift = |sig: Signature<sapling::SpendAuth>, d: jubjub::Scalar| {
+        let mut bytes: [u8; 64] = sig.into();
+        let mut repr = [0; 32];
+        repr.copy_from_slice(&bytes[32..]);
+        let s = jubjub::Scalar::from_repr(repr).unwrap() + d;
+        bytes[32..].copy_from_slice(&s.to_repr());
+        Signature::<sapling::SpendAuth>::try_from(bytes).unwrap()
+    };
+    let d = jubjub::Scalar::one();
+    let sig_1 = shift(sk.sign(&mut rng, &msg[..]), d);
+    let sig_2 = shift(sk.sign(&mut rng, &msg[..]), -d);
+    assert!(vk.verify(msg, &sig_1).is_err());
+    assert!(vk.verify(msg, &sig_2).is_err());
+
+    let batch = || {
+        let mut batch = batch::Verifier::<sapling::SpendAuth, sapling::Binding>::new();
+        batch.queue(batch::Item::from_spendauth(vk.into(), sig_1, msg));
+        batch.queue(batch::Item::from_spendauth(vk.into(), sig_2, msg));
+        batch
+    };
+
+    // With independent weights, the errors do not cancel out.
+    for _ in 0..32 {
+        assert!(batch().verify(&mut rng).is_err());
+    }
+    assert!(vk
+        .verify_many(&mut rng, &[(&msg[..], sig_1), (&msg[..], sig_2)])
+        .is_err());
+
+    // The weights are all that keeps the errors from cancelling out: if they
+    // collide, the invalid batch is accepted.
+    assert!(batch().verify(RepeatingRng(0x0123_4567_89ab_cdef)).is_ok());
+    assert!(vk
+        .verify_many(
+            RepeatingRng(0x0123_4567_89ab_cdef),
+            &[(&msg[..], sig_1), (&msg[..], sig_2)]
+        )
+        .is_ok());
+}
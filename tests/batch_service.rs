@@ -0,0 +1,77 @@
+#![cfg(feature = "batch-service")]
+
+use std::time::Duration;
+
+use rand::thread_rng;
+
+use reddsa::{batch::service::VerificationService, *};
+
+#[tokio::test]
+async fn service_resolves_each_request() {
+    let service = VerificationService::<sapling::SpendAuth, sapling::Binding>::new(
+        8,
+        Duration::from_millis(10),
+    );
+    let mut rng = thread_rng();
+
+    let mut tasks = Vec::new();
+    for i in 0..20 {
+        let sk = SigningKey::<sapling::SpendAuth>::new(&mut rng);
+        let vk = VerificationKey::from(&sk);
+        let sig = sk.sign(&mut rng, b"service");
+        // Every fifth request is for a different message than the one signed.
+        let msg: &[u8] = if i % 5 == 0 { b"tampered" } else { b"service" };
+        let item = batch::Item::<_, sapling::Binding>::from_spendauth(vk.into(), sig, &msg);
+
+        let service = service.clone();
+        tasks.push(tokio::spawn(async move { service.verify(item).await }));
+    }
+
+    for (i, task) in tasks.into_iter().enumerate() {
+        let result = task.await.unwrap();
+        if i % 5 == 0 {
+            assert_eq!(result, Err(Error::InvalidSignature));
+        } else {
+            assert_eq!(result, Ok(()));
+        }
+    }
+}
+
+#[tokio::test]
+async fn service_flushes_partial_batch() {
+    // The batch is never full, so the request is only verified once the
+    // latency bound has passed.
+    let service = VerificationService::<orchard::SpendAuth, orchard::Binding>::new(
+        64,
+        Duration::from_millis(5),
+    );
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::Binding>::new(&mut rng);
+    let sig = sk.sign(&mut rng, b"service");
+    let item = batch::Item::<orchard::SpendAuth, _>::from_binding(
+        VerificationKey::from(&sk).into(),
+        sig,
+        b"service",
+    );
+
+    let result = tokio::time::timeout(Duration::from_secs(10), service.verify(item)).await;
+    assert_eq!(result, Ok(Ok(())));
+}
+
+#[tokio::test]
+async fn service_reports_malformed_keys() {
+    let service = VerificationService::<orchard::SpendAuth, orchard::Binding>::new(
+        4,
+        Duration::from_millis(5),
+    );
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    let sig = sk.sign(&mut rng, b"service");
+    let item =
+        batch::Item::<_, orchard::Binding>::from_spendauth([0xff; 32].into(), sig, b"service");
+
+    assert_eq!(
+        service.verify(item).await,
+        Err(Error::MalformedVerificationKey)
+    );
+}
@@ -93,19 +93,25 @@ proptest! {
             bytes
         };
 
-        let sig_bytes_from = Signature::<sapling::SpendAuth>::from(bytes);
-        let sig_bytes_bincode: Signature::<sapling::SpendAuth>
-            = bincode::deserialize(&bytes[..]).unwrap();
-
-        // Check 1: both decoding methods should have the same result.
-        assert_eq!(sig_bytes_from, sig_bytes_bincode);
-
-        // Check 2: bincode encoding should match original bytes.
-        let bytes_bincode = bincode::serialize(&sig_bytes_from).unwrap();
-        assert_eq!(&bytes[..], &bytes_bincode[..]);
+        let sig_result_try_from = Signature::<sapling::SpendAuth>::try_from(bytes);
+        let sig_result_bincode: Result<Signature::<sapling::SpendAuth>, _>
+            = bincode::deserialize(&bytes[..]);
 
-        // Check 3: From encoding should match original bytes.
-        let bytes_from: [u8; 64] = sig_bytes_bincode.into();
-        assert_eq!(&bytes[..], &bytes_from[..]);
+        // Check 1: both decoding methods should have the same result
+        match (sig_result_try_from, sig_result_bincode) {
+            // Both agree on success
+            (Ok(sig_try_from), Ok(sig_bincode)) => {
+                assert_eq!(sig_try_from, sig_bincode);
+                // Check 2: bincode encoding should match original bytes
+                let bytes_bincode = bincode::serialize(&sig_try_from).unwrap();
+                assert_eq!(&bytes[..], &bytes_bincode[..]);
+                // Check 3: From encoding should match original bytes
+                let bytes_from: [u8; 64] = sig_bincode.into();
+                assert_eq!(&bytes[..], &bytes_from[..]);
+            },
+            // Both agree on failure
+            (Err(_), Err(_)) => {},
+            _ => panic!("bincode and try_from do not agree"),
+        }
     }
 }
@@ -0,0 +1,183 @@
+//! Timing-leak tests for signing, following the [dudect] methodology.
+//!
+//! Each test times an operation on inputs from two classes, one with a fixed
+//! secret key or nonce and one with random ones, interleaved in random order,
+//! and applies Welch's t-test to the two timing distributions. If signing runs in constant
+//! time, the distributions do not differ. The tests are ignored by default,
+//! because they are slow and only meaningful in release mode on a quiet
+//! machine:
+//!
+//! ```sh
+//! cargo test --release --test constant_time -- --ignored
+//! ```
+//!
+//! [dudect]: https://eprint.iacr.org/2016/1123
+#![cfg(feature = "std")]
+
+use std::{convert::TryFrom, time::Instant};
+
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use reddsa::{frost, orchard, sapling, SigType, SigningKey};
+
+/// The number of timed operations in each test.
+const MEASUREMENTS: usize = 20_000;
+
+/// The t statistic above which dudect considers an operation to leak.
+const T_THRESHOLD: f64 = 10.0;
+
+/// Online mean and variance of a timing class.
+#[derive(Default)]
+struct Class {
+    n: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Class {
+    fn push(&mut self, x: f64) {
+        self.n += 1.0;
+        let delta = x - self.mean;
+        self.mean += delta / self.n;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn variance(&self) -> f64 {
+        self.m2 / (self.n - 1.0)
+    }
+}
+
+/// Times `op` on inputs of the fixed class (`true`) and the random class
+/// (`false`), and returns Welch's t statistic of the two timing distributions.
+///
+/// Inputs are prepared by `prepare` outside of the timed region. The slowest
+/// measurements are cropped, as dudect does, to remove interruptions.
+fn welch_t<I>(mut prepare: impl FnMut(bool) -> I, mut op: impl FnMut(I)) -> f64 {
+    let mut rng = thread_rng();
+    let mut timings = Vec::with_capacity(MEASUREMENTS);
+    for _ in 0..MEASUREMENTS {
+        let class = rng.gen::<bool>();
+        let input = prepare(class);
+        let start = Instant::now();
+        op(input);
+        timings.push((class, start.elapsed().as_nanos() as f64));
+    }
+
+    let mut sorted: Vec<f64> = timings.iter().map(|(_, t)| *t).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let crop = sorted[sorted.len() * 9 / 10];
+
+    let (mut fixed, mut random) = (Class::default(), Class::default());
+    for (class, t) in timings.into_iter().filter(|(_, t)| *t <= crop) {
+        if class {
+            fixed.push(t)
+        } else {
+            random.push(t)
+        }
+    }
+
+    (fixed.mean - random.mean) / (fixed.variance() / fixed.n + random.variance() / random.n).sqrt()
+}
+
+/// Times deriving a verification key, which multiplies the basepoint by the
+/// signing key, for a fixed key whose radix-16 digits are almost all zero and
+/// for random keys. Fixed-base multiplication that adds the identity for zero
+/// digits with a non-complete addition leaks in this test.
+fn check_basepoint_mul<T: SigType>() {
+    let mut rng = thread_rng();
+    let mut fixed = [0; 32];
+    fixed[0] = 1;
+    let keys: Vec<[u8; 32]> = (0..64)
+        .map(|_| SigningKey::<T>::new(&mut rng).into())
+        .collect();
+
+    let t = welch_t(
+        |class| {
+            if class {
+                fixed
+            } else {
+                keys[rng.gen_range(0..keys.len())]
+            }
+        },
+        |bytes| {
+            SigningKey::<T>::try_from(bytes).unwrap();
+        },
+    );
+    assert!(t.abs() < T_THRESHOLD, "t = {}", t);
+}
+
+#[test]
+#[ignore]
+fn basepoint_mul_is_constant_time() {
+    check_basepoint_mul::<sapling::SpendAuth>();
+    check_basepoint_mul::<orchard::SpendAuth>();
+}
+
+/// The RNG for a signing operation: always the same seed for the fixed class,
+/// so that every operation uses the same nonce, and a random seed for the
+/// random class.
+fn nonce_rng(class: bool, rng: &mut impl Rng) -> ChaCha20Rng {
+    if class {
+        ChaCha20Rng::from_seed([0; 32])
+    } else {
+        ChaCha20Rng::from_seed(rng.gen())
+    }
+}
+
+/// Times signing with one key, with a fixed nonce and with random nonces.
+fn check_sign<T: SigType>() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<T>::new(&mut rng);
+
+    let t = welch_t(
+        |class| nonce_rng(class, &mut rng),
+        |nonce_rng| {
+            sk.sign(nonce_rng, b"constant time");
+        },
+    );
+    assert!(t.abs() < T_THRESHOLD, "t = {}", t);
+}
+
+#[test]
+#[ignore]
+fn sign_is_constant_time() {
+    check_sign::<sapling::SpendAuth>();
+    check_sign::<orchard::SpendAuth>();
+}
+
+/// Times a FROST participant signing with one share, with fixed nonces and
+/// with random nonces. Every measurement signs a package of the same size,
+/// with fresh nonces for the other participant.
+fn check_frost_sign<S: SigType>() {
+    let mut rng = thread_rng();
+    let (shares, pubkeys) = frost::keygen_with_dealer::<_, S>(2, 2, &mut rng).unwrap();
+    let session_id = frost::SessionId::new(&mut rng);
+
+    let t = welch_t(
+        |class| {
+            let (nonces, commitments1) =
+                frost::preprocess(1, shares[0].index, &mut nonce_rng(class, &mut rng));
+            let (_, commitments2) = frost::preprocess(1, shares[1].index, &mut rng);
+            let signing_package = frost::SigningPackage::new(
+                &pubkeys,
+                session_id,
+                vec![commitments1[0], commitments2[0]],
+                b"constant time".to_vec(),
+            )
+            .unwrap();
+            (signing_package, nonces[0])
+        },
+        |(signing_package, nonces)| {
+            frost::sign(&signing_package, session_id, nonces, &shares[0]).unwrap();
+        },
+    );
+    assert!(t.abs() < T_THRESHOLD, "t = {}", t);
+}
+
+#[test]
+#[ignore]
+fn frost_sign_is_constant_time() {
+    check_frost_sign::<sapling::SpendAuth>();
+    check_frost_sign::<orchard::SpendAuth>();
+}
@@ -0,0 +1,69 @@
+use group::{ff::PrimeField, Group, GroupEncoding};
+use pasta_curves::pallas;
+use rand::thread_rng;
+
+use reddsa::*;
+
+#[test]
+fn signing_key_errors() {
+    assert_eq!(
+        SigningKey::<orchard::SpendAuth>::try_from([0xff; 32]).err(),
+        Some(Error::MalformedSigningKey)
+    );
+    assert_eq!(
+        SigningKey::<orchard::SpendAuth>::try_from([0; 32]).err(),
+        Some(Error::ZeroSigningKey)
+    );
+    assert!(SigningKey::<orchard::SpendAuth>::try_from(pallas::Scalar::one().to_repr()).is_ok());
+}
+
+#[test]
+fn verification_key_errors() {
+    assert_eq!(
+        VerificationKey::<orchard::SpendAuth>::try_from([0xff; 32]),
+        Err(Error::MalformedVerificationKey)
+    );
+
+    assert_eq!(
+        VerificationKey::<orchard::SpendAuth>::from_bytes_strict([0xff; 32]),
+        Err(Error::MalformedVerificationKey)
+    );
+
+    // The identity is allowed by the consensus rules, which decoding applies,
+    // but rejected by strict decoding.
+    let identity = pallas::Point::identity().to_bytes();
+    assert!(VerificationKey::<orchard::SpendAuth>::try_from(identity).is_ok());
+    assert!(
+        VerificationKey::<orchard::SpendAuth>::try_from(VerificationKeyBytes::from(identity))
+            .is_ok()
+    );
+    assert_eq!(
+        VerificationKey::<orchard::SpendAuth>::from_bytes_strict(identity),
+        Err(Error::IdentityVerificationKey)
+    );
+}
+
+#[test]
+fn signature_errors() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    let bytes: [u8; 64] = sk.sign(&mut rng, b"decoding").into();
+    assert_eq!(
+        Signature::<orchard::SpendAuth>::try_from(bytes).map(<[u8; 64]>::from),
+        Ok(bytes)
+    );
+
+    let mut bad_r = bytes;
+    bad_r[..32].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        Signature::<orchard::SpendAuth>::try_from(bad_r),
+        Err(Error::MalformedSignaturePoint)
+    );
+
+    let mut bad_s = bytes;
+    bad_s[32..].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        Signature::<orchard::SpendAuth>::try_from(bad_s),
+        Err(Error::MalformedSignatureScalar)
+    );
+}
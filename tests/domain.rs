@@ -0,0 +1,43 @@
+use rand::thread_rng;
+
+use reddsa::*;
+
+fn check_domain_separation<T: SigType>() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<T>::new(&mut rng);
+    let vk = VerificationKey::from(&sk);
+    let msg = b"transfer 10";
+
+    let sig = sk.sign_with_domain(&mut rng, b"app/v1", msg);
+    assert!(vk.verify_with_domain(b"app/v1", msg, &sig).is_ok());
+    assert!(vk.verify_with_domain(b"app/v2", msg, &sig).is_err());
+    assert!(vk.verify_with_domain(b"", msg, &sig).is_err());
+    assert!(vk.verify(msg, &sig).is_err());
+
+    // The length prefix keeps the split between domain and message unambiguous.
+    assert!(vk
+        .verify_with_domain(b"app/v1t", b"ransfer 10", &sig)
+        .is_err());
+
+    // The signature is a plain signature on the framed message.
+    let mut framed = Vec::new();
+    framed.extend_from_slice(&6u64.to_le_bytes());
+    framed.extend_from_slice(b"app/v1");
+    framed.extend_from_slice(msg);
+    assert!(vk.verify(&framed, &sig).is_ok());
+
+    let plain = sk.sign(&mut rng, msg);
+    assert!(vk.verify_with_domain(b"", msg, &plain).is_err());
+}
+
+#[test]
+fn sapling_domain_separation() {
+    check_domain_separation::<sapling::SpendAuth>();
+    check_domain_separation::<sapling::Binding>();
+}
+
+#[test]
+fn orchard_domain_separation() {
+    check_domain_separation::<orchard::SpendAuth>();
+    check_domain_separation::<orchard::Binding>();
+}
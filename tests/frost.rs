@@ -29,13 +29,14 @@ fn check_sign_with_dealer() {
     // This is what the signature aggregator / coordinator needs to do:
     // - decide what message to sign
     // - take one (unused) commitment per signing participant
+    // - pick a fresh session ID, which the participants learn along with the
+    //   message
     let mut signature_shares: Vec<frost::SignatureShare<sapling::SpendAuth>> =
         Vec::with_capacity(threshold as usize);
     let message = "message to sign".as_bytes();
-    let signing_package = frost::SigningPackage {
-        message: message.to_vec(),
-        signing_commitments: commitments,
-    };
+    let session_id = frost::SessionId::new(&mut rng);
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.to_vec()).unwrap();
 
     // Round 2: each participant generates their signature share
     for (participant_index, nonce) in nonces {
@@ -45,7 +46,8 @@ fn check_sign_with_dealer() {
             .unwrap();
         let nonce_to_use = nonce[0];
         // Each participant generates their signature share.
-        let signature_share = frost::sign(&signing_package, nonce_to_use, share_package).unwrap();
+        let signature_share =
+            frost::sign(&signing_package, session_id, nonce_to_use, share_package).unwrap();
         signature_shares.push(signature_share);
     }
 
@@ -59,8 +61,1350 @@ fn check_sign_with_dealer() {
     // key (aka verification key).
     assert!(pubkeys
         .group_public
-        .verify(&message, &group_signature)
+        .verify(message, &group_signature)
         .is_ok());
 
     // TODO: also check that the SharePackage.group_public also verifies the group signature.
+
+    // Without the share of one of the participants, every share is valid but
+    // the aggregated signature is not.
+    assert_eq!(
+        frost::aggregate(&signing_package, &signature_shares[1..], &pubkeys).err(),
+        Some(frost::Error::InvalidSignature)
+    );
+}
+
+#[test]
+fn check_sign_with_default_rng() {
+    let (shares, pubkeys) = frost::keygen_with_dealer_default::<sapling::SpendAuth>(3, 2).unwrap();
+
+    let (nonces1, commitments1) = frost::preprocess_default(1, shares[0].index);
+    let (nonces2, commitments2) = frost::preprocess_default(1, shares[1].index);
+    let session_id = frost::SessionId::new(&mut thread_rng());
+    let message = b"message to sign";
+    let signing_package = frost::SigningPackage::new(
+        &pubkeys,
+        session_id,
+        vec![commitments1[0], commitments2[0]],
+        message.to_vec(),
+    )
+    .unwrap();
+
+    let signature_shares = vec![
+        frost::sign(&signing_package, session_id, nonces1[0], &shares[0]).unwrap(),
+        frost::sign(&signing_package, session_id, nonces2[0], &shares[1]).unwrap(),
+    ];
+    let signature = frost::aggregate(&signing_package, &signature_shares, &pubkeys).unwrap();
+    assert!(pubkeys.group_public.verify(message, &signature).is_ok());
+
+    let sk = reddsa::SigningKey::<sapling::SpendAuth>::new_default();
+    let signature = sk.sign(thread_rng(), message);
+    assert!(reddsa::VerificationKey::from(&sk)
+        .verify(message, &signature)
+        .is_ok());
+}
+
+#[test]
+fn check_sign_rejects_other_sessions() {
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+
+    let (nonces1, commitments1) = frost::preprocess(1, shares[0].index, &mut rng);
+    let (nonces2, commitments2) = frost::preprocess(1, shares[1].index, &mut rng);
+    let session_id = frost::SessionId::new(&mut rng);
+    let other_session_id = frost::SessionId::new(&mut rng);
+    let signing_commitments = vec![commitments1[0], commitments2[0]];
+    let message = "message to sign".as_bytes();
+    let signing_package = frost::SigningPackage::new(
+        &pubkeys,
+        session_id,
+        signing_commitments.clone(),
+        message.to_vec(),
+    )
+    .unwrap();
+
+    // A signer only signs for the session it expects.
+    assert_eq!(
+        frost::sign(&signing_package, other_session_id, nonces1[0], &shares[0]).err(),
+        Some(frost::Error::SessionMismatch)
+    );
+
+    // Shares made for one session do not verify in another session with the
+    // same commitments and message.
+    let spliced_package = frost::SigningPackage::new(
+        &pubkeys,
+        other_session_id,
+        signing_commitments,
+        message.to_vec(),
+    )
+    .unwrap();
+    let signature_shares = vec![
+        frost::sign(&spliced_package, other_session_id, nonces1[0], &shares[0]).unwrap(),
+        frost::sign(&signing_package, session_id, nonces2[0], &shares[1]).unwrap(),
+    ];
+    assert_eq!(
+        frost::aggregate(&signing_package, &signature_shares, &pubkeys).err(),
+        Some(frost::Error::InvalidSignatureShare)
+    );
+    assert_eq!(
+        frost::find_invalid_shares(&signing_package, &signature_shares, &pubkeys),
+        Ok(vec![shares[0].index])
+    );
+}
+
+#[test]
+fn check_signing_package_validation() {
+    let mut rng = thread_rng();
+    let (_shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let session_id = frost::SessionId::new(&mut rng);
+    let message = "message to sign".as_bytes();
+
+    let (_, commitments1) = frost::preprocess(2, 1, &mut rng);
+    let (_, commitments2) = frost::preprocess(1, 2, &mut rng);
+    let (_, commitments4) = frost::preprocess(1, 4, &mut rng);
+
+    let new_package = |commitments: Vec<frost::SigningCommitments<sapling::SpendAuth>>| {
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.to_vec()).err()
+    };
+
+    assert_eq!(new_package(vec![commitments1[0], commitments2[0]]), None);
+    assert_eq!(
+        new_package(vec![commitments1[0], commitments1[1]]),
+        Some(frost::Error::DuplicateCommitment)
+    );
+    assert_eq!(
+        new_package(vec![commitments1[0]]),
+        Some(frost::Error::NotEnoughCommitments)
+    );
+    assert_eq!(
+        new_package(vec![commitments1[0], commitments4[0]]),
+        Some(frost::Error::UnknownSigner)
+    );
+}
+
+#[test]
+fn check_derive_lagrange_coefficient() {
+    use jubjub::Scalar;
+
+    // Interpolating at zero over {1, 2, 3}: the coefficients are 3, -3 and 1.
+    let signers = [1, 2, 3];
+    let coefficients: Vec<Scalar> = signers
+        .iter()
+        .map(|index| frost::derive_lagrange_coefficient::<sapling::SpendAuth>(*index, &signers))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        coefficients,
+        vec![Scalar::from(3), -Scalar::from(3), Scalar::from(1)]
+    );
+    assert_eq!(
+        coefficients.iter().fold(Scalar::zero(), |sum, c| sum + c),
+        Scalar::from(1),
+        "the coefficients of a constant polynomial sum to one"
+    );
+
+    assert_eq!(
+        frost::derive_lagrange_coefficient::<sapling::SpendAuth>(4, &signers),
+        Err(frost::Error::UnknownSigner)
+    );
+    assert_eq!(
+        frost::derive_lagrange_coefficient::<sapling::SpendAuth>(1, &[1, 2, 2]),
+        Err(frost::Error::DuplicateShares)
+    );
+}
+
+#[test]
+fn check_constant_time_eq() {
+    use subtle::ConstantTimeEq;
+
+    let mut rng = thread_rng();
+
+    let sk = reddsa::SigningKey::<sapling::SpendAuth>::new(&mut rng);
+    let other_sk = reddsa::SigningKey::<sapling::SpendAuth>::new(&mut rng);
+    assert!(bool::from(sk.ct_eq(&sk.clone())));
+    assert!(!bool::from(sk.ct_eq(&other_sk)));
+
+    let (nonces, _) = frost::preprocess::<_, sapling::SpendAuth>(2, 1, &mut rng);
+    assert!(bool::from(nonces[0].ct_eq(&nonces[0])));
+    assert!(!bool::from(nonces[0].ct_eq(&nonces[1])));
+}
+
+#[test]
+fn check_sign_heapless_interoperates() {
+    use reddsa::frost_heapless;
+    use std::convert::TryFrom;
+
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(5, 3, &mut rng).unwrap();
+    let heapless_pubkeys = frost_heapless::PublicKeyPackage::<_, 8>::try_from(&pubkeys).unwrap();
+
+    let key_packages: Vec<frost_heapless::KeyPackage<sapling::SpendAuth>> = shares[..3]
+        .iter()
+        .map(frost_heapless::KeyPackage::from)
+        .collect();
+    let nonces: Vec<frost_heapless::SigningNonces<sapling::SpendAuth>> = key_packages
+        .iter()
+        .map(|_| frost_heapless::SigningNonces::new(&mut rng))
+        .collect();
+    let commitments: Vec<frost_heapless::SigningCommitments<sapling::SpendAuth>> = key_packages
+        .iter()
+        .zip(nonces.iter())
+        .map(|(key_package, nonces)| {
+            frost_heapless::SigningCommitments::from((key_package.index(), nonces))
+        })
+        .collect();
+
+    let session_id = [1; 32];
+    let message = "message to sign".as_bytes();
+    let signing_package =
+        frost_heapless::SigningPackage::<_, 8>::new(session_id, &commitments, message).unwrap();
+    let signature_shares: Vec<frost_heapless::SignatureShare<sapling::SpendAuth>> = key_packages
+        .iter()
+        .zip(nonces)
+        .map(|(key_package, nonces)| {
+            frost_heapless::sign(&signing_package, session_id, nonces, key_package).unwrap()
+        })
+        .collect();
+
+    let group_signature =
+        frost_heapless::aggregate(&signing_package, &signature_shares, &heapless_pubkeys).unwrap();
+    assert!(pubkeys
+        .group_public
+        .verify(message, &group_signature)
+        .is_ok());
+
+    // Valid shares of only some of the signers do not aggregate into a
+    // signature.
+    assert_eq!(
+        frost_heapless::aggregate(&signing_package, &signature_shares[..2], &heapless_pubkeys)
+            .err(),
+        Some(frost_heapless::Error::InvalidSignature)
+    );
+
+    // The same shares are accepted by a `frost` coordinator.
+    let frost_package = frost::SigningPackage::new(
+        &pubkeys,
+        frost::SessionId::from(session_id),
+        commitments.into_iter().map(Into::into).collect(),
+        message.to_vec(),
+    )
+    .unwrap();
+    let frost_shares: Vec<frost::SignatureShare<sapling::SpendAuth>> =
+        signature_shares.into_iter().map(Into::into).collect();
+    assert!(frost::aggregate(&frost_package, &frost_shares, &pubkeys).is_ok());
+
+    // Signing packages are limited to their capacity.
+    assert_eq!(
+        frost_heapless::SigningPackage::<sapling::SpendAuth, 2>::new(
+            session_id,
+            &[commitments_for(1), commitments_for(2), commitments_for(3)],
+            message
+        )
+        .err(),
+        Some(frost_heapless::Error::CapacityExceeded)
+    );
+}
+
+fn commitments_for(index: u64) -> reddsa::frost_heapless::SigningCommitments<sapling::SpendAuth> {
+    let nonces = reddsa::frost_heapless::SigningNonces::new(&mut thread_rng());
+    reddsa::frost_heapless::SigningCommitments::from((index, &nonces))
+}
+
+#[test]
+fn check_roast_with_unresponsive_and_malicious_signers() {
+    use frost::roast::{Coordinator, Output, Signer};
+
+    let mut rng = thread_rng();
+    let numsigners = 5;
+    let threshold = 3;
+    let (shares, pubkeys) = frost::keygen_with_dealer(numsigners, threshold, &mut rng).unwrap();
+
+    let message = "message to sign".as_bytes();
+    let run_id = frost::SessionId::new(&mut rng);
+    let mut coordinator = Coordinator::<sapling::SpendAuth>::new(
+        pubkeys.clone(),
+        threshold,
+        message.to_vec(),
+        run_id,
+    )
+    .unwrap();
+
+    let mut signers: HashMap<u64, Signer<sapling::SpendAuth>> = shares
+        .into_iter()
+        .map(|share| (share.index, Signer::new(share, run_id, &mut rng)))
+        .collect();
+
+    // Signer 5 never responds. The first three signers to respond are put into
+    // the first session.
+    let mut first_session = None;
+    for index in 1..5 {
+        match coordinator
+            .receive(index, None, signers[&index].commitments())
+            .unwrap()
+        {
+            Output::NewSession(request) => first_session = Some(request),
+            Output::Pending => {}
+            Output::Signature(_) => panic!("no shares were sent yet"),
+        }
+    }
+    let first_session = first_session.unwrap();
+    assert_eq!(first_session.signers, vec![1, 2, 3]);
+
+    // The coordinator cannot pass the first session off as a later one.
+    assert_eq!(
+        signers
+            .get_mut(&1)
+            .unwrap()
+            .sign(1, &first_session.signing_package, &mut rng)
+            .err(),
+        Some(frost::Error::SessionMismatch)
+    );
+
+    // Signer 1 misbehaves by signing a different message.
+    let bad_package = frost::SigningPackage::new(
+        &pubkeys,
+        frost::roast::session_id(run_id, first_session.session_index),
+        first_session
+            .signers
+            .iter()
+            .map(|index| signers[index].commitments())
+            .collect(),
+        "another message".as_bytes().to_vec(),
+    )
+    .unwrap();
+    let (bad_share, next) = signers
+        .get_mut(&1)
+        .unwrap()
+        .sign(first_session.session_index, &bad_package, &mut rng)
+        .unwrap();
+    assert!(matches!(
+        coordinator.receive(1, Some(bad_share), next),
+        Ok(Output::Pending)
+    ));
+    assert_eq!(coordinator.malicious_signers(), vec![1]);
+
+    // Signers 2 and 3 respond honestly; together with signer 4 they form the
+    // second session, which completes.
+    let mut second_session = None;
+    for index in [2, 3] {
+        let (share, next) = signers
+            .get_mut(&index)
+            .unwrap()
+            .sign(
+                first_session.session_index,
+                &first_session.signing_package,
+                &mut rng,
+            )
+            .unwrap();
+        if let Output::NewSession(request) = coordinator.receive(index, Some(share), next).unwrap()
+        {
+            second_session = Some(request);
+        }
+    }
+    let second_session = second_session.unwrap();
+    assert_eq!(second_session.signers, vec![4, 2, 3]);
+
+    let mut group_signature = None;
+    for index in second_session.signers.iter() {
+        let (share, next) = signers
+            .get_mut(index)
+            .unwrap()
+            .sign(
+                second_session.session_index,
+                &second_session.signing_package,
+                &mut rng,
+            )
+            .unwrap();
+        if let Output::Signature(signature) =
+            coordinator.receive(*index, Some(share), next).unwrap()
+        {
+            group_signature = Some(signature);
+        }
+    }
+
+    assert!(pubkeys
+        .group_public
+        .verify(message, &group_signature.unwrap())
+        .is_ok());
+}
+
+#[test]
+fn check_roast_session_expiration_and_cancellation() {
+    use frost::roast::{session_id, Coordinator, Output, Signer};
+    use std::time::Duration;
+
+    let mut rng = thread_rng();
+    let (shares, pubkeys) = frost::keygen_with_dealer(3, 2, &mut rng).unwrap();
+    let run_id = frost::SessionId::new(&mut rng);
+    let mut coordinator =
+        Coordinator::<sapling::SpendAuth>::new(pubkeys, 2, b"message".to_vec(), run_id).unwrap();
+    coordinator.set_session_timeout(Duration::from_secs(10));
+
+    let mut signers: HashMap<u64, Signer<sapling::SpendAuth>> = shares
+        .into_iter()
+        .map(|share| (share.index, Signer::new(share, run_id, &mut rng)))
+        .collect();
+
+    let start = |coordinator: &mut Coordinator<_>, signers: &HashMap<_, Signer<_>>, indices| {
+        let mut request = None;
+        for index in indices {
+            match coordinator
+                .receive(index, None, signers[&index].commitments())
+                .unwrap()
+            {
+                Output::NewSession(new_request) => request = Some(new_request),
+                Output::Pending => {}
+                Output::Signature(_) => panic!("no shares were sent yet"),
+            }
+        }
+        request.unwrap()
+    };
+
+    // The first session expires before signer 1 answers.
+    let first_session = start(&mut coordinator, &signers, [1, 2]);
+    assert!(coordinator.advance_time(Duration::from_secs(5)).is_empty());
+    assert_eq!(
+        coordinator.advance_time(Duration::from_secs(10)),
+        vec![first_session.session_index]
+    );
+    let (share, next) = signers
+        .get_mut(&1)
+        .unwrap()
+        .sign(
+            first_session.session_index,
+            &first_session.signing_package,
+            &mut rng,
+        )
+        .unwrap();
+    assert_eq!(
+        coordinator.receive(1, Some(share), next).err(),
+        Some(frost::Error::SessionExpired)
+    );
+    assert!(coordinator.malicious_signers().is_empty());
+
+    // Signer 1 rejoins with its next commitments, and a second session is
+    // started with signer 3, which is then cancelled.
+    let second_session = start(&mut coordinator, &signers, [1, 3]);
+    assert_eq!(
+        coordinator.cancel(frost::SessionId::new(&mut rng)),
+        Err(frost::Error::UnknownSession)
+    );
+    coordinator
+        .cancel(session_id(run_id, second_session.session_index))
+        .unwrap();
+    let (share, next) = signers
+        .get_mut(&3)
+        .unwrap()
+        .sign(
+            second_session.session_index,
+            &second_session.signing_package,
+            &mut rng,
+        )
+        .unwrap();
+    assert_eq!(
+        coordinator.receive(3, Some(share), next).err(),
+        Some(frost::Error::SessionCancelled)
+    );
+    assert!(coordinator.malicious_signers().is_empty());
+}
+
+#[test]
+fn check_roast_signing_policy() {
+    use frost::{
+        policy::{SigningCounters, SigningRequest},
+        roast::{session_id, Coordinator, Output, Signer},
+    };
+
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let run_id = frost::SessionId::new(&mut rng);
+    let share = shares.into_iter().next().unwrap();
+    let mut signer = Signer::new(share, run_id, &mut rng);
+
+    // Sign at most once, and only the expected message.
+    signer.set_policy(|request: &SigningRequest<sapling::SpendAuth>| {
+        request.counters.signed < 1 && request.message() == b"message"
+    });
+
+    let signing_package = |signer: &Signer<_>, session_index, message: &[u8]| {
+        let (_, other) = frost::preprocess(1, 2, &mut thread_rng());
+        frost::SigningPackage::new(
+            &pubkeys,
+            session_id(run_id, session_index),
+            vec![signer.commitments(), other[0]],
+            message.to_vec(),
+        )
+        .unwrap()
+    };
+
+    let refused = signing_package(&signer, 0, b"another message");
+    let stale = signing_package(&signer, 1, b"message");
+    assert_eq!(
+        signer.sign(0, &refused, &mut rng).err(),
+        Some(frost::Error::PolicyRejected)
+    );
+    // A refused request discards the nonces, so the commitments that appeared
+    // in the refused package can no longer be signed with.
+    assert_eq!(
+        signer.sign(1, &stale, &mut rng).err(),
+        Some(frost::Error::CommitmentMismatch)
+    );
+    let approved = signing_package(&signer, 2, b"message");
+    signer.sign(2, &approved, &mut rng).unwrap();
+
+    let package = signing_package(&signer, 3, b"message");
+    assert_eq!(
+        signer.sign(3, &package, &mut rng).err(),
+        Some(frost::Error::PolicyRejected)
+    );
+    assert_eq!(
+        signer.counters(),
+        SigningCounters {
+            signed: 1,
+            refused: 2
+        }
+    );
+
+    // A coordinator takes a declining signer out of its session without
+    // marking it as malicious, and the signer can rejoin later.
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let mut coordinator =
+        Coordinator::new(pubkeys.clone(), 2, b"message".to_vec(), run_id).unwrap();
+    let mut signers: Vec<Signer<sapling::SpendAuth>> = shares
+        .into_iter()
+        .map(|share| Signer::new(share, run_id, &mut rng))
+        .collect();
+    signers[0].set_policy(|_: &SigningRequest<sapling::SpendAuth>| false);
+    coordinator
+        .receive(1, None, signers[0].commitments())
+        .unwrap();
+    let request = match coordinator
+        .receive(2, None, signers[1].commitments())
+        .unwrap()
+    {
+        Output::NewSession(request) => request,
+        _ => panic!("expected a new session"),
+    };
+    assert_eq!(
+        signers[0]
+            .sign(request.session_index, &request.signing_package, &mut rng)
+            .err(),
+        Some(frost::Error::PolicyRejected)
+    );
+    coordinator.decline(1, request.session_index).unwrap();
+    assert_eq!(
+        coordinator.decline(1, request.session_index).err(),
+        Some(frost::Error::UnknownSession)
+    );
+    assert!(coordinator.malicious_signers().is_empty());
+
+    let (share, next) = signers[1]
+        .sign(request.session_index, &request.signing_package, &mut rng)
+        .unwrap();
+    assert!(matches!(
+        coordinator.receive(2, Some(share), next),
+        Ok(Output::Pending)
+    ));
+    let request = match coordinator.receive(3, None, signers[2].commitments()) {
+        Ok(Output::NewSession(request)) => request,
+        _ => panic!("expected a new session"),
+    };
+    let mut signature = None;
+    for index in request.signers.iter() {
+        let signer = &mut signers[*index as usize - 1];
+        let (share, next) = signer
+            .sign(request.session_index, &request.signing_package, &mut rng)
+            .unwrap();
+        if let Output::Signature(output) = coordinator.receive(*index, Some(share), next).unwrap() {
+            signature = Some(output);
+        }
+    }
+    assert!(pubkeys
+        .group_public
+        .verify(b"message", &signature.unwrap())
+        .is_ok());
+}
+
+#[test]
+fn check_plain_signing_policy() {
+    use frost::policy::{self, SigningCounters, SigningRequest};
+    use subtle::ConstantTimeEq;
+
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let (nonces, commitments) = frost::preprocess(1, shares[0].index, &mut rng);
+    let (_, other) = frost::preprocess(1, shares[1].index, &mut rng);
+    let session_id = frost::SessionId::new(&mut rng);
+    let signing_package = frost::SigningPackage::new(
+        &pubkeys,
+        session_id,
+        vec![commitments[0], other[0]],
+        b"message".to_vec(),
+    )
+    .unwrap();
+
+    let mut counters = SigningCounters::default();
+    let mut refuse = |request: &SigningRequest<sapling::SpendAuth>| {
+        assert_eq!(request.run_id, None);
+        assert_eq!(request.session_id, session_id);
+        false
+    };
+    assert_eq!(
+        policy::sign(
+            &mut refuse,
+            &mut counters,
+            &signing_package,
+            session_id,
+            nonces[0],
+            &shares[0]
+        )
+        .err(),
+        Some(frost::Error::PolicyRejected)
+    );
+
+    let mut approve = |request: &SigningRequest<sapling::SpendAuth>| request.counters.refused == 1;
+    let share = policy::sign(
+        &mut approve,
+        &mut counters,
+        &signing_package,
+        session_id,
+        nonces[0],
+        &shares[0],
+    )
+    .unwrap();
+    let expected = frost::sign(&signing_package, session_id, nonces[0], &shares[0]).unwrap();
+    assert!(bool::from(share.ct_eq(&expected)));
+    assert_eq!(
+        counters,
+        SigningCounters {
+            signed: 1,
+            refused: 1
+        }
+    );
+}
+
+#[test]
+fn check_roast_snapshot_and_restore() {
+    use frost::roast::{Coordinator, Output, Signer};
+    use std::time::Duration;
+
+    let mut rng = thread_rng();
+    let seed = b"roast snapshot seed";
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(seed, 3, 2).unwrap();
+    let run_id = frost::SessionId::new(&mut rng);
+    let message = b"message to sign";
+    let mut coordinator =
+        Coordinator::<sapling::SpendAuth>::new(pubkeys.clone(), 2, message.to_vec(), run_id)
+            .unwrap();
+    coordinator.set_session_timeout(Duration::from_secs(60));
+    let mut signers: Vec<Signer<sapling::SpendAuth>> = shares
+        .into_iter()
+        .map(|share| Signer::new(share, run_id, &mut rng))
+        .collect();
+
+    // Only signers 1 and 2 take part, so that signer 1 is the only ready
+    // signer after it answers.
+    coordinator
+        .receive(1, None, signers[0].commitments())
+        .unwrap();
+    let request = match coordinator.receive(2, None, signers[1].commitments()) {
+        Ok(Output::NewSession(request)) => request,
+        _ => panic!("signers 1 and 2 are ready"),
+    };
+    assert_eq!(request.signers, vec![1, 2]);
+
+    // Signer 1 answers, and then the coordinator and signer 2 restart.
+    let (share, next) = signers[0]
+        .sign(request.session_index, &request.signing_package, &mut rng)
+        .unwrap();
+    assert!(matches!(
+        coordinator.receive(1, Some(share), next),
+        Ok(Output::Pending)
+    ));
+
+    let coordinator_snapshot = coordinator.snapshot();
+    let signer_snapshot = signers[1].snapshot();
+    drop(coordinator);
+
+    let (mut shares, _) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(seed, 3, 2).unwrap();
+    let mut coordinator =
+        Coordinator::<sapling::SpendAuth>::restore(&coordinator_snapshot).unwrap();
+    signers[1] = Signer::restore(shares.remove(1), &signer_snapshot, &mut rng).unwrap();
+
+    // The snapshot does not hold the nonces, so the restored signer has new
+    // ones and cannot sign the session it was assigned before the restart.
+    assert_eq!(
+        signers[1]
+            .sign(request.session_index, &request.signing_package, &mut rng)
+            .err(),
+        Some(frost::Error::CommitmentMismatch)
+    );
+
+    // Once the session expires, signer 2 rejoins with its new commitments,
+    // and the next session completes the signature.
+    assert_eq!(
+        coordinator.advance_time(Duration::from_secs(61)),
+        vec![request.session_index]
+    );
+    let request = match coordinator.receive(2, None, signers[1].commitments()) {
+        Ok(Output::NewSession(request)) => request,
+        _ => panic!("signers 1 and 2 are ready"),
+    };
+    let mut signature = None;
+    for index in request.signers.iter() {
+        let signer = &mut signers[*index as usize - 1];
+        let (share, next) = signer
+            .sign(request.session_index, &request.signing_package, &mut rng)
+            .unwrap();
+        if let Output::Signature(output) = coordinator.receive(*index, Some(share), next).unwrap() {
+            signature = Some(output);
+        }
+    }
+    assert!(pubkeys
+        .group_public
+        .verify(message, &signature.unwrap())
+        .is_ok());
+
+    // Snapshots are rejected if they are malformed, or restored with the
+    // share package of another signer.
+    assert_eq!(
+        Coordinator::<sapling::SpendAuth>::restore(&coordinator_snapshot[1..]).err(),
+        Some(frost::Error::MalformedSnapshot)
+    );
+    assert_eq!(
+        Signer::restore(shares.remove(0), &signer_snapshot, &mut rng).err(),
+        Some(frost::Error::MalformedSnapshot)
+    );
+}
+
+#[test]
+fn check_sign_with_dealer_weighted() {
+    let mut rng = thread_rng();
+    let threshold = 3;
+    let (packages, pubkeys) = frost::weighted::keygen_with_dealer::<_, sapling::SpendAuth>(
+        &[3, 1, 1],
+        threshold,
+        &mut rng,
+    )
+    .unwrap();
+    assert_eq!(packages[0].share_packages.len(), 3);
+    assert_eq!(packages[2].participant, 3);
+
+    // The first participant holds enough weight to sign on its own.
+    let (nonces, commitments) = frost::weighted::preprocess(&packages[0], &mut rng);
+    let message = "message to sign".as_bytes();
+    let session_id = frost::SessionId::new(&mut rng);
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.to_vec()).unwrap();
+
+    let signature_shares =
+        frost::weighted::sign(&signing_package, session_id, nonces, &packages[0]).unwrap();
+    let group_signature =
+        frost::aggregate(&signing_package, &signature_shares[..], &pubkeys).unwrap();
+
+    assert!(pubkeys
+        .group_public
+        .verify(message, &group_signature)
+        .is_ok());
+}
+
+#[cfg(feature = "frost-reconstruct-dangerous")]
+#[test]
+fn check_reconstruct_with_dealer() {
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(5, 3, &mut rng).unwrap();
+
+    let signing_key = frost::reconstruct(&shares[1..4]).unwrap();
+    assert_eq!(
+        reddsa::VerificationKey::from(&signing_key),
+        pubkeys.group_public
+    );
+
+    let message = "message to sign".as_bytes();
+    let signature = signing_key.sign(&mut rng, message);
+    assert!(pubkeys.group_public.verify(message, &signature).is_ok());
+
+    // Fewer shares than the threshold do not reconstruct the key.
+    assert!(frost::reconstruct(&shares[..2]).is_err());
+}
+
+#[cfg(feature = "frost-backup")]
+#[test]
+fn check_share_backup_roundtrip() {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+    use std::convert::TryInto;
+
+    let mut rng = thread_rng();
+    let (shares, _pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let passphrase = b"correct horse battery staple";
+
+    let backup = shares[0]
+        .export_encrypted(passphrase, ChaChaRng::seed_from_u64(0))
+        .unwrap();
+    let restored =
+        frost::SharePackage::<sapling::SpendAuth>::import_encrypted(&backup, passphrase).unwrap();
+    assert_eq!(restored.index, shares[0].index);
+
+    // Re-exporting with the same randomness yields the same backup, so the
+    // restored share is identical to the original.
+    let reexported = restored
+        .export_encrypted(passphrase, ChaChaRng::seed_from_u64(0))
+        .unwrap();
+    assert_eq!(backup, reexported);
+    let _: frost::KeyPackage<sapling::SpendAuth> = restored.try_into().unwrap();
+
+    assert_eq!(
+        frost::SharePackage::<sapling::SpendAuth>::import_encrypted(&backup, b"wrong").err(),
+        Some(frost::Error::BackupDecryptionFailed)
+    );
+
+    let mut future_version = backup.clone();
+    future_version[0] = 2;
+    assert_eq!(
+        frost::SharePackage::<sapling::SpendAuth>::import_encrypted(&future_version, passphrase)
+            .err(),
+        Some(frost::Error::UnsupportedBackupVersion)
+    );
+
+    // The header is authenticated, so tampering with the salt is detected.
+    let mut tampered = backup.clone();
+    tampered[13] ^= 1;
+    assert_eq!(
+        frost::SharePackage::<sapling::SpendAuth>::import_encrypted(&tampered, passphrase).err(),
+        Some(frost::Error::BackupDecryptionFailed)
+    );
+
+    // Excessive memory, time or parallelism costs are rejected before the key
+    // is derived.
+    for offset in [1, 5, 9] {
+        let mut expensive = backup.clone();
+        expensive[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            frost::SharePackage::<sapling::SpendAuth>::import_encrypted(&expensive, passphrase)
+                .err(),
+            Some(frost::Error::MalformedBackup)
+        );
+    }
+}
+
+#[test]
+fn check_echo_broadcast() {
+    use frost::echo::EchoBroadcast;
+
+    let session_id = frost::SessionId::new(&mut thread_rng());
+    let participants = [1, 2, 3];
+
+    // Runs a broadcast round in which `message(sender, receiver)` is what
+    // `sender` sends to `receiver`, and returns every participant's state
+    // after the echoes were exchanged.
+    let run = |message: &dyn Fn(u64, u64) -> Vec<u8>| {
+        let mut states: Vec<EchoBroadcast> = participants
+            .iter()
+            .map(|index| EchoBroadcast::new(session_id, *index, participants).unwrap())
+            .collect();
+        for (receiver, state) in participants.iter().zip(states.iter_mut()) {
+            for sender in participants.iter() {
+                state
+                    .receive(*sender, &message(*sender, *receiver))
+                    .unwrap();
+            }
+        }
+        let echoes: Vec<_> = states.iter().map(|state| state.echo().unwrap()).collect();
+        for state in states.iter_mut() {
+            for echo in echoes.iter() {
+                state.receive_echo(echo.clone()).unwrap();
+            }
+        }
+        states
+    };
+
+    let honest = run(&|sender, _| vec![sender as u8]);
+    let transcript = honest[0].finalize().unwrap();
+    for state in honest.iter() {
+        assert_eq!(state.finalize(), Ok(transcript));
+    }
+
+    // Participant 3 sends participant 1 a different message than the others.
+    let equivocating = run(&|sender, receiver| {
+        if sender == 3 && receiver == 1 {
+            vec![0xff]
+        } else {
+            vec![sender as u8]
+        }
+    });
+    for state in equivocating.iter() {
+        assert_eq!(state.finalize(), Err(frost::Error::EquivocationDetected));
+        assert_eq!(state.equivocators(), vec![3]);
+    }
+
+    let mut state = EchoBroadcast::new(session_id, 1, participants).unwrap();
+    assert_eq!(
+        state.receive(4, b"message"),
+        Err(frost::Error::UnknownSigner)
+    );
+    state.receive(1, b"message").unwrap();
+    assert_eq!(state.echo().err(), Some(frost::Error::IncompleteBroadcast));
+    assert_eq!(state.finalize(), Err(frost::Error::IncompleteBroadcast));
+}
+
+#[cfg(feature = "frost-share-encryption")]
+#[test]
+fn check_encrypted_share_distribution() {
+    use reddsa::{SigningKey, VerificationKey};
+    use std::convert::{TryFrom, TryInto};
+
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let (_, other_pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let identities: Vec<SigningKey<sapling::SpendAuth>> =
+        shares.iter().map(|_| SigningKey::new(&mut rng)).collect();
+
+    // The dealer broadcasts every encrypted share.
+    let broadcast: Vec<Vec<u8>> = shares
+        .iter()
+        .zip(identities.iter())
+        .map(|(share, identity)| {
+            share
+                .encrypt_to(&VerificationKey::from(identity), &mut rng)
+                .unwrap()
+                .to_bytes()
+        })
+        .collect();
+
+    for (bytes, identity) in broadcast.iter().zip(identities.iter()) {
+        let encrypted = frost::EncryptedSharePackage::from_bytes(bytes).unwrap();
+        encrypted.verify(&pubkeys).unwrap();
+        assert_eq!(
+            encrypted.verify(&other_pubkeys),
+            Err(frost::Error::DifferentGroups)
+        );
+
+        let share = encrypted.decrypt(identity).unwrap();
+        assert_eq!(share.index, encrypted.index());
+        let _: frost::KeyPackage<sapling::SpendAuth> = share.try_into().unwrap();
+    }
+
+    // Only the recipient can decrypt its share.
+    let encrypted = frost::EncryptedSharePackage::from_bytes(&broadcast[0]).unwrap();
+    assert_eq!(
+        encrypted.decrypt(&identities[1]).err(),
+        Some(frost::Error::ShareDecryptionFailed)
+    );
+
+    let mut tampered = broadcast[0].clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    let tampered = frost::EncryptedSharePackage::from_bytes(&tampered).unwrap();
+    assert_eq!(
+        tampered.decrypt(&identities[0]).err(),
+        Some(frost::Error::ShareDecryptionFailed)
+    );
+
+    assert_eq!(
+        frost::EncryptedSharePackage::<sapling::SpendAuth>::from_bytes(&broadcast[0][1..]).err(),
+        Some(frost::Error::MalformedEncryptedShare)
+    );
+
+    // Shares cannot be encrypted to the identity or to a point of small order,
+    // such as the point of order 4 that the encoding of (1, 0) decodes to.
+    let small_order = [
+        jubjub::AffinePoint::identity(),
+        jubjub::AffinePoint::from_raw_unchecked(jubjub::Fq::one(), jubjub::Fq::zero()),
+    ];
+    for point in small_order.iter() {
+        let recipient = VerificationKey::try_from(point.to_bytes()).unwrap();
+        assert_eq!(
+            shares[0].encrypt_to(&recipient, &mut rng).err(),
+            Some(frost::Error::InvalidEncryptionKey)
+        );
+    }
+
+    // Nor can an ephemeral point of small order be used, since the shared
+    // secret would then not depend on the recipient's key.
+    let mut small_order_ephemeral = broadcast[0].clone();
+    let offset = small_order_ephemeral.len() - 48 - 32;
+    small_order_ephemeral[offset..offset + 32].copy_from_slice(&small_order[1].to_bytes());
+    let encrypted = frost::EncryptedSharePackage::from_bytes(&small_order_ephemeral).unwrap();
+    assert_eq!(
+        encrypted.decrypt(&identities[0]).err(),
+        Some(frost::Error::ShareDecryptionFailed)
+    );
+}
+
+#[cfg(feature = "frost-mnemonic")]
+#[test]
+fn check_share_mnemonic_roundtrip() {
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+    let (_, other_pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+
+    let mnemonic = shares[1].to_mnemonic().unwrap();
+    let words: Vec<&str> = mnemonic.split(' ').collect();
+    assert_eq!(words.len(), frost::mnemonic::MNEMONIC_WORDS);
+
+    let restored = frost::mnemonic::from_mnemonic::<sapling::SpendAuth>(&mnemonic).unwrap();
+    assert_eq!(restored.index(), shares[1].index);
+    assert_eq!(restored.threshold(), 2);
+    assert!(restored.into_key_package(&pubkeys).is_ok());
+
+    // Shares are bound to their group.
+    let restored = frost::mnemonic::from_mnemonic::<sapling::SpendAuth>(&mnemonic).unwrap();
+    assert_eq!(
+        restored.into_key_package(&other_pubkeys).err(),
+        Some(frost::Error::DifferentGroups)
+    );
+
+    // Swapping two words is caught by the checksum.
+    let mut swapped = words.clone();
+    swapped.swap(10, 20);
+    if swapped != words {
+        assert_eq!(
+            frost::mnemonic::from_mnemonic::<sapling::SpendAuth>(&swapped.join(" ")).err(),
+            Some(frost::Error::MnemonicChecksumMismatch)
+        );
+    }
+
+    assert_eq!(
+        frost::mnemonic::from_mnemonic::<sapling::SpendAuth>(&words[1..].join(" ")).err(),
+        Some(frost::Error::MalformedMnemonic)
+    );
+    let mut unknown = words.clone();
+    unknown[0] = "notaword";
+    assert_eq!(
+        frost::mnemonic::from_mnemonic::<sapling::SpendAuth>(&unknown.join(" ")).err(),
+        Some(frost::Error::MalformedMnemonic)
+    );
+}
+
+#[test]
+fn check_keygen_with_dealer_from_seed() {
+    use std::convert::TryInto;
+
+    let seed = b"recorded ceremony seed";
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(seed, 5, 3).unwrap();
+    let (_, same_pubkeys) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(seed, 5, 3).unwrap();
+    let (_, other_pubkeys) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(b"another seed", 5, 3).unwrap();
+    let (_, other_threshold_pubkeys) =
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(seed, 5, 2).unwrap();
+
+    assert_eq!(pubkeys.group_public, same_pubkeys.group_public);
+    for index in 1..=5 {
+        assert_eq!(
+            pubkeys.signer_pubkey(index),
+            same_pubkeys.signer_pubkey(index)
+        );
+    }
+    assert_ne!(pubkeys.group_public, other_pubkeys.group_public);
+    assert_ne!(pubkeys.group_public, other_threshold_pubkeys.group_public);
+
+    for share in shares {
+        let _: frost::KeyPackage<sapling::SpendAuth> = share.try_into().unwrap();
+    }
+
+    assert_eq!(
+        frost::keygen_with_dealer_from_seed::<sapling::SpendAuth>(seed, 2, 3).err(),
+        Some(frost::Error::ThresholdExceedsShares)
+    );
+}
+
+#[cfg(feature = "gen-vectors")]
+#[test]
+fn check_test_vectors_are_deterministic() {
+    use reddsa::orchard;
+    use std::convert::TryFrom;
+
+    fn unhex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    let vectors =
+        frost::vectors::generate::<orchard::SpendAuth>(b"seed", 3, 2, b"message").unwrap();
+    let again = frost::vectors::generate::<orchard::SpendAuth>(b"seed", 3, 2, b"message").unwrap();
+    assert_eq!(
+        serde_json::to_string(&vectors).unwrap(),
+        serde_json::to_string(&again).unwrap()
+    );
+    assert_eq!(vectors.shares.len(), 3);
+    assert_eq!(vectors.signers.len(), 2);
+
+    let group_public = reddsa::VerificationKey::<orchard::SpendAuth>::try_from(
+        <[u8; 32]>::try_from(unhex(&vectors.group_public_key)).unwrap(),
+    )
+    .unwrap();
+    let signature = reddsa::Signature::<orchard::SpendAuth>::try_from(
+        <[u8; 64]>::try_from(unhex(&vectors.signature)).unwrap(),
+    )
+    .unwrap();
+    assert!(group_public.verify(b"message", &signature).is_ok());
+}
+
+#[test]
+fn check_signer_pubkeys() {
+    use std::convert::TryFrom;
+
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(5, 3, &mut rng).unwrap();
+
+    let mut indices: Vec<u64> = pubkeys.signer_pubkeys().map(|(index, _)| index).collect();
+    indices.sort_unstable();
+    assert_eq!(indices, vec![1, 2, 3, 4, 5]);
+    assert_eq!(pubkeys.signer_indices(), indices);
+    assert_eq!(pubkeys.num_signers(), 5);
+    assert_eq!(pubkeys.threshold(), 3);
+    assert!(pubkeys.signer_pubkey(0).is_none());
+    assert!(pubkeys.signer_pubkey(6).is_none());
+
+    for share in shares.iter() {
+        let public = pubkeys.signer_pubkey(share.index).unwrap();
+        let bytes: [u8; 32] = (*public).into();
+        assert_eq!(
+            frost::Public::<sapling::SpendAuth>::try_from(bytes).unwrap(),
+            *public
+        );
+    }
+}
+
+#[test]
+fn check_signing_package_accessors() {
+    let mut rng = thread_rng();
+    let (_shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(5, 2, &mut rng).unwrap();
+
+    let commitments: Vec<frost::SigningCommitments<sapling::SpendAuth>> = [4, 2]
+        .iter()
+        .map(|index| frost::preprocess(1, *index, &mut rng).1[0])
+        .collect();
+    let session_id = frost::SessionId::new(&mut rng);
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, b"payload".to_vec()).unwrap();
+
+    assert_eq!(signing_package.session_id(), session_id);
+    assert_eq!(signing_package.message(), b"payload");
+    assert_eq!(signing_package.num_signers(), 2);
+    assert_eq!(signing_package.signer_indices(), vec![4, 2]);
+    assert_eq!(
+        signing_package
+            .signing_commitments()
+            .map(|commitment| commitment.index())
+            .collect::<Vec<_>>(),
+        vec![4, 2]
+    );
+    assert_eq!(signing_package.signing_commitment(2).unwrap().index(), 2);
+    assert!(signing_package.signing_commitment(1).is_none());
+}
+
+fn sign_with_dealer<S: reddsa::SigType>(message: &[u8]) {
+    let mut rng = thread_rng();
+    let (shares, pubkeys) = frost::keygen_with_dealer::<_, S>(3, 2, &mut rng).unwrap();
+
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for share in shares.iter().take(2) {
+        let (nonce, commitment) = frost::preprocess::<_, S>(1, share.index, &mut rng);
+        nonces.push(nonce[0]);
+        commitments.push(commitment[0]);
+    }
+
+    let session_id = frost::SessionId::new(&mut rng);
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.to_vec()).unwrap();
+    let signature_shares: Vec<_> = shares
+        .iter()
+        .zip(nonces)
+        .map(|(share, nonce)| frost::sign(&signing_package, session_id, nonce, share).unwrap())
+        .collect();
+
+    let signature = frost::aggregate(&signing_package, &signature_shares, &pubkeys).unwrap();
+    assert!(pubkeys.group_public.verify(message, &signature).is_ok());
+}
+
+#[test]
+fn check_sign_with_dealer_binding() {
+    sign_with_dealer::<sapling::Binding>(b"sighash");
+    sign_with_dealer::<reddsa::orchard::Binding>(b"sighash");
+}
+
+#[test]
+fn check_sign_with_additive_shares() {
+    let mut rng = thread_rng();
+    let (shares, pubkeys) =
+        frost::additive::keygen_with_dealer::<_, reddsa::orchard::SpendAuth>(2, &mut rng).unwrap();
+    assert_eq!(pubkeys.threshold(), 2);
+
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for share in shares.iter() {
+        let (nonce, commitment) = frost::preprocess(1, share.index, &mut rng);
+        nonces.push(nonce[0]);
+        commitments.push(commitment[0]);
+    }
+
+    // Every participant must take part.
+    let session_id = frost::SessionId::new(&mut rng);
+    assert_eq!(
+        frost::SigningPackage::new(&pubkeys, session_id, commitments[..1].to_vec(), vec![]).err(),
+        Some(frost::Error::NotEnoughCommitments)
+    );
+
+    let message = b"2-of-2";
+    let signing_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.to_vec()).unwrap();
+    let signature_shares: Vec<_> = shares
+        .iter()
+        .zip(nonces)
+        .map(|(share, nonce)| {
+            frost::additive::sign(&signing_package, session_id, nonce, share).unwrap()
+        })
+        .collect();
+
+    let signature =
+        frost::additive::aggregate(&signing_package, &signature_shares, &pubkeys).unwrap();
+    assert!(pubkeys.group_public.verify(message, &signature).is_ok());
+    assert_eq!(
+        frost::additive::aggregate(&signing_package, &signature_shares[..1], &pubkeys).err(),
+        Some(frost::Error::InvalidSignature)
+    );
+}
+
+#[test]
+fn check_aggregate_large_signer_set() {
+    let mut rng = thread_rng();
+    let num_signers = 64;
+    let (shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(num_signers, num_signers, &mut rng)
+            .unwrap();
+
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for share in &shares {
+        let (nonce, commitment) = frost::preprocess(1, share.index, &mut rng);
+        nonces.push(nonce[0]);
+        commitments.push(commitment[0]);
+    }
+
+    let session_id = frost::SessionId::new(&mut rng);
+    let signing_package = frost::SigningPackage::new(
+        &pubkeys,
+        session_id,
+        commitments.clone(),
+        b"message".to_vec(),
+    )
+    .unwrap();
+    let other_package =
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, b"other".to_vec()).unwrap();
+
+    // Two participants sign a different message, so their shares are invalid.
+    let signature_shares: Vec<_> = shares
+        .iter()
+        .zip(nonces)
+        .map(|(share, nonce)| {
+            let package = if share.index == 10 || share.index == 40 {
+                &other_package
+            } else {
+                &signing_package
+            };
+            frost::sign(package, session_id, nonce, share).unwrap()
+        })
+        .collect();
+
+    // Shares are verified in parallel with the `rayon` feature, but the
+    // invalid shares are still reported in order.
+    assert_eq!(
+        frost::find_invalid_shares(&signing_package, &signature_shares, &pubkeys),
+        Ok(vec![10, 40])
+    );
+    assert_eq!(
+        frost::aggregate(&signing_package, &signature_shares, &pubkeys).err(),
+        Some(frost::Error::InvalidSignatureShare)
+    );
+}
+
+#[cfg(feature = "frost-audit")]
+#[test]
+fn check_audit_log_chain() {
+    let mut rng = thread_rng();
+    let (_shares, pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(5, 2, &mut rng).unwrap();
+
+    let mut signing_package = |message: &[u8]| {
+        let commitments: Vec<frost::SigningCommitments<sapling::SpendAuth>> = [4, 2]
+            .iter()
+            .map(|index| frost::preprocess(1, *index, &mut rng).1[0])
+            .collect();
+        let session_id = frost::SessionId::new(&mut rng);
+        frost::SigningPackage::new(&pubkeys, session_id, commitments, message.to_vec()).unwrap()
+    };
+    let first = signing_package(b"first");
+    let second = signing_package(b"second");
+
+    let mut log = frost::audit::AuditLog::new();
+    assert_eq!(log.head(), [0; 32]);
+    let entry = log
+        .record(&first, frost::audit::Outcome::Failed(vec![4]))
+        .unwrap();
+    assert_eq!(entry.sequence(), 0);
+    assert_eq!(entry.session_id(), first.session_id());
+    assert_eq!(entry.signers(), &[4, 2]);
+    assert_eq!(entry.previous_hash(), [0; 32]);
+    let first_hash = entry.hash();
+    let entry = log.record(&second, frost::audit::Outcome::Expired).unwrap();
+    assert_eq!(entry.previous_hash(), first_hash);
+    assert_ne!(
+        log.entries()[0].message_hash(),
+        log.entries()[1].message_hash()
+    );
+    assert_ne!(
+        log.entries()[0].group_commitment(),
+        log.entries()[1].group_commitment()
+    );
+
+    let exported = log.export();
+    let imported = frost::audit::AuditLog::import(&exported).unwrap();
+    assert_eq!(imported.entries(), log.entries());
+    assert_eq!(imported.head(), log.head());
+
+    // Changing an entry breaks the chain.
+    let mut tampered = exported.clone();
+    tampered[40] ^= 1;
+    assert_eq!(
+        frost::audit::AuditLog::import(&tampered).err(),
+        Some(frost::Error::BrokenAuditChain)
+    );
+
+    // So does removing the first entry.
+    let mut single = frost::audit::AuditLog::new();
+    single
+        .record(&first, frost::audit::Outcome::Failed(vec![4]))
+        .unwrap();
+    let first_len = single.export().len();
+    assert_eq!(
+        frost::audit::AuditLog::import(&exported[first_len..]).err(),
+        Some(frost::Error::BrokenAuditChain)
+    );
+
+    assert_eq!(
+        frost::audit::AuditLog::import(&exported[..exported.len() - 1]).err(),
+        Some(frost::Error::MalformedAuditLog)
+    );
 }
@@ -0,0 +1,55 @@
+#![cfg(feature = "mnemonic-keys")]
+
+use reddsa::{orchard, sapling, Error, SigType, SigningKey, VerificationKey};
+
+/// The mnemonic of the first BIP-39 test vector, and its seed with the
+/// passphrase "TREZOR".
+const PHRASE: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const SEED: [u8; 64] = [
+    0xc5, 0x52, 0x57, 0xc3, 0x60, 0xc0, 0x7c, 0x72, 0x02, 0x9a, 0xeb, 0xc1, 0xb5, 0x3c, 0x05, 0xed,
+    0x03, 0x62, 0xad, 0xa3, 0x8e, 0xad, 0x3e, 0x3e, 0x9e, 0xfa, 0x37, 0x08, 0xe5, 0x34, 0x95, 0x53,
+    0x1f, 0x09, 0xa6, 0x98, 0x75, 0x99, 0xd1, 0x82, 0x64, 0xc1, 0xe1, 0xc9, 0x2f, 0x2c, 0xf1, 0x41,
+    0x63, 0x0c, 0x7a, 0x3c, 0x4a, 0xb7, 0xc8, 0x1b, 0x2f, 0x00, 0x16, 0x98, 0xe7, 0x46, 0x3b, 0x04,
+];
+
+fn check_derivation<T: SigType>() {
+    let vk_bytes = |sk: &SigningKey<T>| <[u8; 32]>::from(VerificationKey::from(sk));
+
+    let sk = SigningKey::<T>::from_mnemonic(PHRASE, "TREZOR", 0).unwrap();
+    assert_eq!(vk_bytes(&sk), vk_bytes(&SigningKey::from_seed(&SEED, 0)));
+
+    // Derivation is deterministic, and each index gives a different key.
+    let again = SigningKey::<T>::from_mnemonic(PHRASE, "TREZOR", 0).unwrap();
+    assert_eq!(vk_bytes(&again), vk_bytes(&sk));
+    assert_ne!(vk_bytes(&SigningKey::from_seed(&SEED, 1)), vk_bytes(&sk));
+
+    // The passphrase is part of the seed.
+    let without_passphrase = SigningKey::<T>::from_mnemonic(PHRASE, "", 0).unwrap();
+    assert_ne!(vk_bytes(&without_passphrase), vk_bytes(&sk));
+
+    let sig = sk.sign(rand::thread_rng(), b"message");
+    assert!(VerificationKey::from(&again)
+        .verify(b"message", &sig)
+        .is_ok());
+}
+
+#[test]
+fn derive_from_mnemonic() {
+    check_derivation::<sapling::SpendAuth>();
+    check_derivation::<orchard::SpendAuth>();
+}
+
+#[test]
+fn reject_malformed_mnemonic() {
+    // The last word fails the checksum.
+    let bad_checksum = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+    assert_eq!(
+        SigningKey::<sapling::SpendAuth>::from_mnemonic(bad_checksum, "", 0).err(),
+        Some(Error::MalformedMnemonic)
+    );
+    assert_eq!(
+        SigningKey::<sapling::SpendAuth>::from_mnemonic("not a mnemonic", "", 0).err(),
+        Some(Error::MalformedMnemonic)
+    );
+}
@@ -0,0 +1,59 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rand::thread_rng;
+
+use reddsa::*;
+
+#[test]
+fn verification_key_bytes_ordering() {
+    let mut rng = thread_rng();
+    let keys: Vec<VerificationKeyBytes<orchard::SpendAuth>> = (0..8)
+        .map(|_| VerificationKey::from(&SigningKey::new(&mut rng)).into())
+        .collect();
+
+    // Keys are ordered lexicographically by their encoding.
+    let mut sorted = keys.clone();
+    sorted.sort();
+    let mut sorted_bytes: Vec<[u8; 32]> = keys.iter().map(|&vk| vk.into()).collect();
+    sorted_bytes.sort();
+    assert_eq!(
+        sorted
+            .iter()
+            .map(|&vk| vk.into())
+            .collect::<Vec<[u8; 32]>>(),
+        sorted_bytes
+    );
+
+    let by_key: BTreeMap<_, _> = keys.iter().zip(0..).map(|(&vk, i)| (vk, i)).collect();
+    let hashed: HashMap<_, _> = keys.iter().zip(0..).map(|(&vk, i)| (vk, i)).collect();
+    for (i, vk) in keys.iter().enumerate() {
+        assert_eq!(by_key[vk], i);
+        assert_eq!(hashed[vk], i);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn participant_id_ordering() {
+    use reddsa::messages::ParticipantId;
+
+    let mut ids = vec![
+        ParticipantId::Aggregator,
+        ParticipantId::Signer(3),
+        ParticipantId::Dealer,
+        ParticipantId::Signer(0),
+    ];
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            ParticipantId::Signer(0),
+            ParticipantId::Signer(3),
+            ParticipantId::Dealer,
+            ParticipantId::Aggregator,
+        ]
+    );
+    assert!(ids
+        .windows(2)
+        .all(|pair| u64::from(pair[0]) < u64::from(pair[1])));
+}
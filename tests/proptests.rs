@@ -57,7 +57,7 @@ impl<T: SigType> SignatureCase<T> {
         // conversion to raw bytes to exercise those code paths.
         let sig = {
             let bytes: [u8; 64] = self.sig.into();
-            Signature::<T>::from(bytes)
+            Signature::<T>::try_from(bytes).unwrap()
         };
         let pk_bytes = {
             let bytes: [u8; 32] = self.pk_bytes.into();
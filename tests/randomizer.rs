@@ -0,0 +1,57 @@
+use group::ff::Field;
+use rand::thread_rng;
+
+use reddsa::*;
+
+fn check_randomizer_arithmetic<T: SpendAuth>() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<T>::new(&mut rng);
+    let ak = VerificationKey::from(&sk);
+    let alpha = <Randomizer<T>>::random(&mut rng);
+    let beta = <Randomizer<T>>::random(&mut rng);
+
+    let rk = ak.randomize(&alpha);
+    assert_eq!(rk.derandomize(&alpha), ak);
+    assert_eq!(rk.randomize(&beta), ak.randomize(&(alpha + beta)));
+    assert_eq!(ak.randomize(&-alpha).randomize(&alpha), ak);
+
+    let rsk = sk.randomize(&alpha);
+    assert_eq!(VerificationKey::from(&rsk), rk);
+    assert_eq!(VerificationKey::from(&rsk.derandomize(&alpha)), ak);
+
+    let msg = b"spend";
+    let sig = rsk.sign(&mut rng, msg);
+    assert!(ak.verify_with_randomizer(&alpha, msg, &sig).is_ok());
+    assert!(ak.verify_with_randomizer(&beta, msg, &sig).is_err());
+    assert!(ak.verify(msg, &sig).is_err());
+}
+
+#[test]
+fn sapling_randomizer_arithmetic() {
+    check_randomizer_arithmetic::<sapling::SpendAuth>();
+}
+
+#[test]
+fn orchard_randomizer_arithmetic() {
+    check_randomizer_arithmetic::<orchard::SpendAuth>();
+}
+
+#[test]
+fn derived_randomizers() {
+    let sk = SigningKey::<orchard::SpendAuth>::new(thread_rng());
+    let ak = VerificationKey::from(&sk);
+    let seed = [7; 32];
+
+    let keys = ak.derive_randomized_keys(&seed, 4);
+    assert_eq!(keys.len(), 4);
+    for (index, (alpha, rk)) in keys.iter().enumerate() {
+        assert_eq!(
+            *alpha,
+            VerificationKey::<orchard::SpendAuth>::derive_randomizer(&seed, index as u32)
+        );
+        assert_eq!(*rk, ak.randomize(alpha));
+        assert_eq!(VerificationKey::from(&sk.randomize(alpha)), *rk);
+    }
+    assert_ne!(keys[0].0, keys[1].0);
+    assert_ne!(ak.derive_randomized_keys(&[8; 32], 1)[0].0, keys[0].0);
+}
@@ -0,0 +1,76 @@
+use std::convert::TryFrom;
+
+use rand::thread_rng;
+
+use reddsa::*;
+
+/// Returns the lowercase hex encoding of `bytes`.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[test]
+fn signing_key_debug_is_redacted() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    let sk_bytes: [u8; 32] = sk.clone().into();
+
+    let debug = format!("{:?}", sk);
+    assert!(debug.starts_with("SigningKey { fingerprint: "));
+    assert!(!debug.contains(&hex(&sk_bytes)));
+
+    // The fingerprint identifies the key without revealing it.
+    let decoded = SigningKey::<orchard::SpendAuth>::try_from(sk_bytes).unwrap();
+    assert_eq!(format!("{:?}", decoded), debug);
+    let other = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    assert_ne!(format!("{:?}", other), debug);
+}
+
+#[test]
+fn adaptor_secret_debug_is_redacted() {
+    let secret = adaptor::AdaptorSecret::<sapling::SpendAuth>::new(thread_rng());
+    let secret_bytes: [u8; 32] = (&secret).into();
+
+    let debug = format!("{:?}", secret);
+    assert!(debug.starts_with("AdaptorSecret { fingerprint: "));
+    assert!(!debug.contains(&hex(&secret_bytes)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn frost_debug_is_redacted() {
+    let mut rng = thread_rng();
+    let (shares, _pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, &mut rng).unwrap();
+
+    let debug = format!("{:?}", shares[0]);
+    assert!(debug.starts_with("SharePackage { "));
+    assert!(debug.contains("value: Secret { fingerprint: "));
+    assert_ne!(debug, format!("{:?}", shares[1]));
+
+    let (nonces, _commitments) = frost::preprocess::<_, sapling::SpendAuth>(1, 1, &mut rng);
+    let nonce_bytes: [u8; 64] = (&nonces[0]).into();
+    let debug = format!("{:?}", nonces[0]);
+    assert!(debug.starts_with("SigningNonces { fingerprint: "));
+    assert!(!debug.contains(&hex(&nonce_bytes[..32])));
+    assert!(!debug.contains(&hex(&nonce_bytes[32..])));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn messages_debug_is_redacted() {
+    let (shares, _pubkeys) =
+        frost::keygen_with_dealer::<_, sapling::SpendAuth>(3, 2, thread_rng()).unwrap();
+    let share_package = messages::SharePackage::from(&shares[0]);
+    let secret_bytes = share_package.secret_share.0;
+
+    assert_eq!(
+        format!("{:?}", share_package.secret_share),
+        "Secret(<redacted>)"
+    );
+    let debug = format!("{:?}", share_package);
+    assert!(debug.starts_with("SharePackage { "));
+    assert!(debug.contains("secret_share: Secret(<redacted>)"));
+    assert!(!debug.contains(&hex(&secret_bytes)));
+    assert!(!debug.contains(&format!("{:?}", secret_bytes)));
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "signature", feature = "std"))]
+
+use rand::thread_rng;
+use signature::{RandomizedSigner, Signer, Verifier};
+
+use reddsa::*;
+
+fn sign_and_verify<S, V, Sig>(signer: &S, verifier: &V)
+where
+    S: Signer<Sig> + RandomizedSigner<Sig>,
+    V: Verifier<Sig>,
+{
+    let msg = b"signature traits";
+
+    let sig = signer.sign(msg);
+    assert!(verifier.verify(msg, &sig).is_ok());
+    assert!(verifier.verify(b"another message", &sig).is_err());
+
+    let sig = signer.sign_with_rng(&mut thread_rng(), msg);
+    assert!(verifier.verify(msg, &sig).is_ok());
+}
+
+#[test]
+fn sapling_signature_traits() {
+    let sk = SigningKey::<sapling::SpendAuth>::new(thread_rng());
+    sign_and_verify(&sk, &VerificationKey::from(&sk));
+
+    let sk = SigningKey::<sapling::Binding>::new(thread_rng());
+    sign_and_verify(&sk, &VerificationKey::from(&sk));
+}
+
+#[test]
+fn orchard_signature_traits() {
+    let sk = SigningKey::<orchard::SpendAuth>::new(thread_rng());
+    sign_and_verify(&sk, &VerificationKey::from(&sk));
+
+    let sk = SigningKey::<orchard::Binding>::new(thread_rng());
+    sign_and_verify(&sk, &VerificationKey::from(&sk));
+}
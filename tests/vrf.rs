@@ -0,0 +1,59 @@
+use group::{Group, GroupEncoding};
+use pasta_curves::pallas;
+use rand::thread_rng;
+
+use reddsa::{vrf, *};
+
+#[test]
+fn vrf_prove_and_verify() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    let vk = VerificationKey::from(&sk);
+
+    let (output, proof) = vrf::prove(&sk, b"epoch 42");
+    assert_eq!(vrf::verify(&vk, b"epoch 42", &proof), Ok(output));
+    assert_eq!(proof.output(), Ok(output));
+
+    // Proving is deterministic.
+    assert_eq!(vrf::prove(&sk, b"epoch 42"), (output, proof));
+
+    // Different inputs and keys give different outputs.
+    let (other_output, other_proof) = vrf::prove(&sk, b"epoch 43");
+    assert_ne!(other_output, output);
+    let other_sk = SigningKey::<orchard::SpendAuth>::new(&mut rng);
+    assert_ne!(vrf::prove(&other_sk, b"epoch 42").0, output);
+
+    // A proof does not verify for another input or key.
+    assert!(vrf::verify(&vk, b"epoch 43", &proof).is_err());
+    assert!(vrf::verify(&vk, b"epoch 42", &other_proof).is_err());
+    assert!(vrf::verify(&VerificationKey::from(&other_sk), b"epoch 42", &proof).is_err());
+}
+
+#[test]
+fn vrf_proof_roundtrip() {
+    let sk = SigningKey::<orchard::Binding>::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+
+    let (output, proof) = vrf::prove(&sk, b"");
+    let bytes: [u8; vrf::PROOF_BYTES] = proof.into();
+    let decoded = vrf::Proof::from(bytes);
+    assert_eq!(vrf::verify(&vk, b"", &decoded), Ok(output));
+
+    let mut tampered = bytes;
+    tampered[70] ^= 1;
+    assert!(vrf::verify(&vk, b"", &vrf::Proof::from(tampered)).is_err());
+}
+
+#[test]
+fn vrf_rejects_identity_verification_key() {
+    let sk = SigningKey::<orchard::SpendAuth>::new(thread_rng());
+    let (_, proof) = vrf::prove(&sk, b"epoch 42");
+
+    // The identity decodes under the consensus rules, but proves nothing.
+    let identity = pallas::Point::identity().to_bytes();
+    let identity = VerificationKey::<orchard::SpendAuth>::try_from(identity).unwrap();
+    assert_eq!(
+        vrf::verify(&identity, b"epoch 42", &proof),
+        Err(Error::IdentityVerificationKey)
+    );
+}